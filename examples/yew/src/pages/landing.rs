@@ -459,6 +459,7 @@ pub fn example13() -> Html {
         pagination: "flex justify-between items-center mt-4",
         pagination_button:
             "px-4 py-2 text-sm text-white bg-blue-500 rounded hover:bg-blue-600 disabled:opacity-50",
+        ..Default::default()
     };
 
     let styles = hashmap! {
@@ -472,6 +473,7 @@ pub fn example13() -> Html {
         previous_button: "← Previous",
         next_button: "Next →",
         page_indicator: "Page {current} of {total}",
+        ..Default::default()
     };
 
     html! {
@@ -606,6 +608,7 @@ pub fn example3() -> Html {
         tbody: "custom-tbody",
         pagination: "custom-pagination",
         search_input: "custom-search-input",
+        ..Default::default()
     };
 
     html! {
@@ -1060,6 +1063,7 @@ pub fn example13() -> Html {
         empty_row: "text-center py-4 text-gray-500",
         pagination: "flex justify-between items-center mt-4",
         pagination_button: "px-4 py-2 text-sm text-white bg-blue-500 rounded hover:bg-blue-600 disabled:opacity-50",
+        ..Default::default()
     };
 
     let styles = hashmap! {
@@ -1073,6 +1077,7 @@ pub fn example13() -> Html {
         previous_button: "← Previous",
         next_button: "Next →",
         page_indicator: "Page {current} of {total}",
+        ..Default::default()
     };
 
     html! {
@@ -0,0 +1,114 @@
+//! Framework-agnostic table engine shared by the Yew, Dioxus and Leptos backends.
+//!
+//! The engine holds no UI state of its own: a backend keeps a [`TableState`] (or the pieces of it)
+//! in its own reactive primitives and calls [`compute_view`] to find out which rows to render.
+//! Because everything in here is plain Rust, it can be exercised with `cargo test` without a browser.
+
+pub mod column;
+pub mod filter;
+pub mod fuzzy;
+pub mod index;
+pub mod normalize;
+pub mod query;
+pub mod row;
+pub mod selection;
+pub mod sliced;
+pub mod sort;
+pub mod source;
+pub mod state;
+pub mod value;
+pub mod view;
+pub mod virtualize;
+pub mod worker;
+
+pub use column::{ColumnDef, TableColumn, search_columns};
+pub use filter::{ColumnFilter, FilterValue, apply_filters};
+pub use fuzzy::{FuzzyMatch, fuzzy_highlight, fuzzy_match, fuzzy_score};
+pub use index::{SearchIndex, SearchIndexKind};
+pub use normalize::SearchOptions;
+pub use query::{Query, SearchSyntax};
+pub use row::{RowKey, TableRow, ToCell, row_keys};
+pub use selection::{CheckState, Selection, SelectionMode};
+pub use sliced::{SlicedView, TimeSlicing};
+pub use sort::{SortFn, natural_cmp};
+pub use source::{
+    DataFuture, DataPage, DataSource, DataSourceHandle, LoadedPages, LocalDataSource, RetryPolicy,
+};
+pub use state::{
+    PaginationMode, SEARCH_DEBOUNCE_MS, SortCycle, SortKey, SortOrder, TableState,
+    sort_key_position, toggle_sort_key,
+};
+pub use value::{CellValue, ColumnKind};
+pub use view::{
+    TableView, compute_view, filter_rows, matching_rows, page_view, paginate_rows, search_rows,
+    sort_rows,
+};
+pub use virtualize::{
+    ColumnSlot, ColumnWindow, RowHeight, RowHeights, VirtualColumns, VirtualScroll, VirtualWindow,
+};
+pub use worker::{ViewData, WorkerRequest};
+
+/// Derives [`TableRow`] for a struct with named fields, plus a `columns()` constructor.
+///
+/// Supported field attributes are `id = "..."`, `header = "..."`, `sortable`, `skip`,
+/// `format = path::to::fn`, where the function has the signature `fn(&FieldType) -> String`, and
+/// `kind = ...` to set the [`ColumnKind`] used for sorting. Numeric and boolean fields get the
+/// matching kind automatically. `natural` and `sort_fn = path::to::fn` set the column's
+/// [`SortFn`] to natural order or to a `fn(&CellValue, &CellValue) -> Ordering` comparator, and
+/// `first_sort_order = desc` makes the first header click sort descending. `filter = ...` adds a
+/// [`ColumnFilter`] (`text`, `equals`, `number_range` or `date_range`) to the filter row, and
+/// `searchable = false` keeps the search input from looking at the column.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{
+///     CellValue, ColumnDef, ColumnFilter, ColumnKind, FilterValue, SortFn, SortOrder, TableRow,
+/// };
+///
+/// fn years(age: &u8) -> String {
+///     format!("{age} years")
+/// }
+///
+/// #[derive(TableRow)]
+/// struct User {
+///     #[table(sortable, natural)]
+///     first_name: String,
+///     #[table(id = "mail", header = "E-mail")]
+///     email: Option<String>,
+///     #[table(format = years, filter = number_range)]
+///     age: u8,
+///     #[table(kind = date, first_sort_order = desc, searchable = false)]
+///     joined: String,
+///     #[table(skip)]
+///     password_hash: String,
+/// }
+///
+/// let user = User {
+///     first_name: "Ferris".into(),
+///     email: None,
+///     age: 9,
+///     joined: "2024-05-01".into(),
+///     password_hash: "hunter2".into(),
+/// };
+/// assert_eq!(user.cell("first_name").as_deref(), Some("Ferris"));
+/// assert_eq!(user.cell("mail"), None);
+/// assert_eq!(user.cell("age").as_deref(), Some("9 years"));
+/// assert_eq!(user.cell("password_hash"), None);
+/// assert_eq!(user.value("age", ColumnKind::Integer), CellValue::Integer(9));
+///
+/// let columns: Vec<ColumnDef> = User::columns();
+/// assert_eq!(columns.len(), 4);
+/// assert_eq!((columns[0].header, columns[0].sortable), ("First Name", true));
+/// assert_eq!(columns[0].sort_fn, Some(SortFn::Natural));
+/// assert_eq!((columns[1].id, columns[1].header), ("mail", "E-mail"));
+/// assert_eq!(columns[2].kind, ColumnKind::Integer);
+/// assert_eq!(columns[2].filter, Some(ColumnFilter::NumberRange));
+///
+/// let adult = FilterValue::Range { min: "18".into(), max: "".into() };
+/// assert!(!ColumnFilter::NumberRange.matches(&user, "age", &adult));
+/// assert_eq!(columns[3].kind, ColumnKind::Date);
+/// assert_eq!(columns[3].first_sort_order, SortOrder::Desc);
+/// assert!(columns[0].searchable && !columns[3].searchable);
+/// ```
+#[cfg(feature = "derive")]
+pub use table_rs_macros::TableRow;
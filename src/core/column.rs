@@ -0,0 +1,169 @@
+use crate::core::filter::ColumnFilter;
+use crate::core::sort::SortFn;
+use crate::core::state::SortOrder;
+use crate::core::value::ColumnKind;
+
+/// The column metadata the table engine needs from a backend's column definition.
+///
+/// Each backend has its own `Column` type carrying framework-specific renderers and styling;
+/// they all implement this trait so that [`compute_view`](crate::core::compute_view) can work
+/// with any of them. It is also implemented for `&'static str`, which is handy in tests.
+pub trait TableColumn {
+    /// Unique identifier of the column, also used as the key to look up cells in a row.
+    fn id(&self) -> &'static str;
+
+    /// How the column's values are parsed and compared when sorting.
+    fn kind(&self) -> ColumnKind {
+        ColumnKind::Text
+    }
+
+    /// Custom comparator used instead of the kind's default ordering, if any.
+    fn sort_fn(&self) -> Option<SortFn> {
+        None
+    }
+
+    /// Direction applied by the first click on the column's header.
+    fn first_sort_order(&self) -> SortOrder {
+        SortOrder::Asc
+    }
+
+    /// Filter offered in the filter row for this column, if any.
+    fn filter(&self) -> Option<ColumnFilter> {
+        None
+    }
+
+    /// Whether the search input looks at this column.
+    fn searchable(&self) -> bool {
+        true
+    }
+
+    /// Minimum width of the column in pixels, or `0` to leave it to the layout.
+    fn min_width(&self) -> u32 {
+        0
+    }
+
+    /// Whether the column stays at the left edge, and in the DOM, when the table scrolls sideways.
+    fn pinned(&self) -> bool {
+        false
+    }
+}
+
+impl TableColumn for &'static str {
+    fn id(&self) -> &'static str {
+        self
+    }
+}
+
+/// A framework-agnostic column description, convertible into any backend's `Column`.
+///
+/// This is what `#[derive(TableRow)]` generates for each field; every backend implements
+/// `From<ColumnDef>` for its own `Column` type and fills the remaining fields with defaults.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnDef {
+    /// Unique identifier for the column.
+    pub id: &'static str,
+
+    /// Header text displayed at the top of the column.
+    pub header: &'static str,
+
+    /// Determines if the column is sortable.
+    pub sortable: bool,
+
+    /// How the column's values are parsed and compared when sorting.
+    pub kind: ColumnKind,
+
+    /// Custom comparator used instead of the kind's default ordering.
+    pub sort_fn: Option<SortFn>,
+
+    /// Direction applied by the first click on the column's header.
+    pub first_sort_order: SortOrder,
+
+    /// Filter offered in the filter row for this column, if any.
+    pub filter: Option<ColumnFilter>,
+
+    /// Whether the search input looks at this column.
+    pub searchable: bool,
+}
+
+impl Default for ColumnDef {
+    fn default() -> Self {
+        Self {
+            id: "",
+            header: "",
+            sortable: false,
+            kind: ColumnKind::Text,
+            sort_fn: None,
+            first_sort_order: SortOrder::Asc,
+            filter: None,
+            searchable: true,
+        }
+    }
+}
+
+impl TableColumn for ColumnDef {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn kind(&self) -> ColumnKind {
+        self.kind
+    }
+
+    fn sort_fn(&self) -> Option<SortFn> {
+        self.sort_fn
+    }
+
+    fn first_sort_order(&self) -> SortOrder {
+        self.first_sort_order
+    }
+
+    fn filter(&self) -> Option<ColumnFilter> {
+        self.filter
+    }
+
+    fn searchable(&self) -> bool {
+        self.searchable
+    }
+}
+
+/// Returns the columns the search input looks at: the searchable ones of `columns`, followed by
+/// `search_fields`, row keys that are searched without being displayed.
+///
+/// Hidden fields are searched as text; a field that is also a column is only listed once.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{ColumnDef, TableColumn, search_columns};
+///
+/// let columns = [
+///     ColumnDef { id: "name", ..Default::default() },
+///     ColumnDef { id: "avatar", searchable: false, ..Default::default() },
+/// ];
+///
+/// let searched = search_columns(&columns, &["tags", "name"]);
+/// let ids: Vec<_> = searched.iter().map(|col| col.id()).collect();
+/// assert_eq!(ids, vec!["name", "tags"]);
+/// ```
+pub fn search_columns<C: TableColumn>(
+    columns: &[C],
+    search_fields: &[&'static str],
+) -> Vec<ColumnDef> {
+    let mut searched: Vec<ColumnDef> = columns
+        .iter()
+        .filter(|col| col.searchable())
+        .map(|col| ColumnDef {
+            id: col.id(),
+            kind: col.kind(),
+            ..Default::default()
+        })
+        .collect();
+    for &id in search_fields {
+        if !searched.iter().any(|col| col.id == id) {
+            searched.push(ColumnDef {
+                id,
+                ..Default::default()
+            });
+        }
+    }
+    searched
+}
@@ -0,0 +1,256 @@
+use crate::core::column::TableColumn;
+use crate::core::row::TableRow;
+use crate::core::value::{CellValue, ColumnKind};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// The filter a column offers, which also decides the widget rendered for it in the filter row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColumnFilter {
+    /// A text input; rows match when the cell contains the text, ignoring case.
+    Text,
+
+    /// A text input; rows match when the cell equals the text, ignoring case.
+    Equals,
+
+    /// A drop-down of the given options; rows match when the cell is the chosen option.
+    Select(&'static [&'static str]),
+
+    /// Minimum and maximum number inputs; both bounds are inclusive and optional.
+    NumberRange,
+
+    /// "From" and "to" date inputs (`YYYY-MM-DD`); both days are included and optional.
+    DateRange,
+}
+
+/// The user's input for a column filter, as typed into its widget.
+///
+/// Values are kept as text so they can be bound straight to inputs; they are parsed when rows
+/// are matched. An empty value (or a range with two empty bounds) filters nothing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FilterValue {
+    /// Value of a [`ColumnFilter::Text`], [`ColumnFilter::Equals`] or [`ColumnFilter::Select`].
+    Text(String),
+
+    /// Bounds of a [`ColumnFilter::NumberRange`] or [`ColumnFilter::DateRange`].
+    Range {
+        /// Lower bound, or an empty string for none.
+        min: String,
+
+        /// Upper bound, or an empty string for none.
+        max: String,
+    },
+}
+
+impl Default for FilterValue {
+    fn default() -> Self {
+        FilterValue::Text(String::new())
+    }
+}
+
+impl FilterValue {
+    /// Returns `true` if the value restricts the rows at all.
+    pub fn is_active(&self) -> bool {
+        match self {
+            FilterValue::Text(text) => !text.trim().is_empty(),
+            FilterValue::Range { min, max } => !min.trim().is_empty() || !max.trim().is_empty(),
+        }
+    }
+
+    /// Text of a text value, or an empty string for a range.
+    pub fn text(&self) -> &str {
+        match self {
+            FilterValue::Text(text) => text,
+            FilterValue::Range { .. } => "",
+        }
+    }
+
+    /// Bounds of a range value, or two empty strings for a text value.
+    pub fn range(&self) -> (&str, &str) {
+        match self {
+            FilterValue::Range { min, max } => (min, max),
+            FilterValue::Text(_) => ("", ""),
+        }
+    }
+}
+
+impl ColumnFilter {
+    /// Returns `true` if the cell of `row` in column `id` passes this filter with `value`.
+    ///
+    /// Text filters look at the cell text, range filters at the typed [`TableRow::value`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use maplit::hashmap;
+    /// use table_rs::core::{ColumnFilter, FilterValue};
+    ///
+    /// let adult = FilterValue::Range { min: "18".into(), max: "".into() };
+    /// let row = hashmap! { "age" => "42".to_string() };
+    /// assert!(ColumnFilter::NumberRange.matches(&row, "age", &adult));
+    /// assert!(!ColumnFilter::NumberRange.matches(&row, "email", &adult));
+    ///
+    /// let until_may = FilterValue::Range { min: "".into(), max: "2024-05-01".into() };
+    /// let row = hashmap! { "seen" => "2024-05-01T23:00:00Z".to_string() };
+    /// assert!(ColumnFilter::DateRange.matches(&row, "seen", &until_may));
+    /// ```
+    pub fn matches<T: TableRow + ?Sized>(&self, row: &T, id: &str, value: &FilterValue) -> bool {
+        if !value.is_active() {
+            return true;
+        }
+
+        let cell = || row.cell(id).unwrap_or_default();
+        let text = value.text().trim();
+
+        match self {
+            ColumnFilter::Text => cell().to_lowercase().contains(&text.to_lowercase()),
+            ColumnFilter::Equals => cell().trim().to_lowercase() == text.to_lowercase(),
+            ColumnFilter::Select(_) => cell() == value.text(),
+            ColumnFilter::NumberRange => in_range(
+                row.value(id, ColumnKind::Float),
+                value.range(),
+                ColumnKind::Float,
+            ),
+            ColumnFilter::DateRange => in_range(
+                row.value(id, ColumnKind::Date),
+                value.range(),
+                ColumnKind::Date,
+            ),
+        }
+    }
+}
+
+/// Checks that `cell` lies between the inclusive bounds, parsed as `kind`.
+///
+/// Cells without a typed value never match. A date upper bound includes the whole day, so that
+/// timestamps on that day still match.
+fn in_range(cell: CellValue<'_>, (min, max): (&str, &str), kind: ColumnKind) -> bool {
+    if matches!(cell, CellValue::Null | CellValue::Text(_)) {
+        return false;
+    }
+
+    let above_min = match CellValue::parse(min, kind) {
+        CellValue::Null | CellValue::Text(_) => true,
+        min => cell.compare(&min) != Ordering::Less,
+    };
+    let below_max = match CellValue::parse(max, kind) {
+        CellValue::Date(day) => cell.compare(&CellValue::Date(day + 1)) == Ordering::Less,
+        CellValue::Null | CellValue::Text(_) => true,
+        max => cell.compare(&max) != Ordering::Greater,
+    };
+
+    above_min && below_max
+}
+
+/// Keeps only the rows of `indices` that pass every active column filter (filters are ANDed).
+///
+/// Filters on columns that are not in `columns` or that have no [`ColumnFilter`] are ignored.
+///
+/// # Examples
+/// ```rust
+/// use maplit::{btreemap, hashmap};
+/// use table_rs::core::{ColumnFilter, FilterValue, TableColumn, apply_filters};
+///
+/// struct Col(&'static str, ColumnFilter);
+///
+/// impl TableColumn for Col {
+///     fn id(&self) -> &'static str {
+///         self.0
+///     }
+///
+///     fn filter(&self) -> Option<ColumnFilter> {
+///         Some(self.1)
+///     }
+/// }
+///
+/// let data = vec![
+///     hashmap! { "team" => "core".to_string(), "age" => "30".to_string() },
+///     hashmap! { "team" => "web".to_string(), "age" => "41".to_string() },
+///     hashmap! { "team" => "core".to_string(), "age" => "52".to_string() },
+/// ];
+/// let columns = [
+///     Col("team", ColumnFilter::Select(&["core", "web"])),
+///     Col("age", ColumnFilter::NumberRange),
+/// ];
+/// let filters = btreemap! {
+///     "team" => FilterValue::Text("core".into()),
+///     "age" => FilterValue::Range { min: "40".into(), max: "".into() },
+/// };
+///
+/// let mut indices = vec![0, 1, 2];
+/// apply_filters(&data, &columns, &filters, &mut indices);
+/// assert_eq!(indices, vec![2]);
+/// ```
+pub fn apply_filters<T: TableRow, C: TableColumn>(
+    data: &[T],
+    columns: &[C],
+    filters: &BTreeMap<&'static str, FilterValue>,
+    indices: &mut Vec<usize>,
+) {
+    let active: Vec<_> = filters
+        .iter()
+        .filter(|(_, value)| value.is_active())
+        .filter_map(|(&id, value)| {
+            let filter = columns.iter().find(|col| col.id() == id)?.filter()?;
+            Some((id, filter, value))
+        })
+        .collect();
+    if active.is_empty() {
+        return;
+    }
+
+    indices.retain(|&idx| {
+        active
+            .iter()
+            .all(|(id, filter, value)| filter.matches(&data[idx], id, value))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn row(city: &str) -> HashMap<&'static str, String> {
+        HashMap::from([("city", city.to_string())])
+    }
+
+    fn text(value: &str) -> FilterValue {
+        FilterValue::Text(value.to_string())
+    }
+
+    #[test]
+    fn equals_ignores_unicode_case_and_surrounding_spaces() {
+        assert!(ColumnFilter::Equals.matches(&row("Évian"), "city", &text("évian ")));
+        assert!(ColumnFilter::Equals.matches(&row(" ÉVIAN"), "city", &text("Évian")));
+        assert!(!ColumnFilter::Equals.matches(&row("Évian-les-Bains"), "city", &text("évian")));
+    }
+
+    #[test]
+    fn text_ignores_unicode_case_and_surrounding_spaces() {
+        assert!(ColumnFilter::Text.matches(&row("Évian-les-Bains"), "city", &text(" ÉVIAN ")));
+        assert!(!ColumnFilter::Text.matches(&row("Evian"), "city", &text("évian")));
+    }
+
+    #[test]
+    fn inactive_values_match_every_row() {
+        assert!(ColumnFilter::Equals.matches(&row("Paris"), "city", &text("  ")));
+        let open = FilterValue::Range {
+            min: " ".into(),
+            max: String::new(),
+        };
+        assert!(ColumnFilter::NumberRange.matches(&row("Paris"), "city", &open));
+    }
+
+    #[test]
+    fn ranges_skip_cells_without_a_typed_value() {
+        let range = FilterValue::Range {
+            min: "1".into(),
+            max: "10".into(),
+        };
+        let age = |age: &str| HashMap::from([("age", age.to_string())]);
+        assert!(ColumnFilter::NumberRange.matches(&age("10"), "age", &range));
+        assert!(!ColumnFilter::NumberRange.matches(&age("10.5"), "age", &range));
+        assert!(!ColumnFilter::NumberRange.matches(&age("n/a"), "age", &range));
+        assert!(!ColumnFilter::NumberRange.matches(&age(""), "age", &range));
+    }
+}
@@ -0,0 +1,295 @@
+use crate::core::column::TableColumn;
+use crate::core::normalize::SearchOptions;
+use crate::core::row::TableRow;
+
+/// How a fuzzy search term matched a piece of text.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FuzzyMatch {
+    /// Relevance of the match; higher is better. Contiguous runs, matches at the start of words
+    /// and exact spellings score higher than scattered characters or typos.
+    pub score: i32,
+
+    /// Byte offsets of the matched characters in the text, in ascending order.
+    pub positions: Vec<usize>,
+}
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_CONSECUTIVE: i32 = 8;
+const BONUS_WORD_START: i32 = 8;
+const BONUS_TEXT_START: i32 = 8;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_TYPO: i32 = 16;
+
+/// Matches `pattern` against `text`, both normalized with `options`.
+///
+/// The characters of `pattern` may appear in `text` with gaps between them, so `frs` finds
+/// `Ferris`. Failing that, terms of three characters or more also match a word of `text` that is
+/// one typo away (two for terms of eight characters or more), counting insertions, deletions,
+/// substitutions and swapped neighbours. Such a word may also be the start of a longer one.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{SearchOptions, fuzzy_match};
+///
+/// let options = SearchOptions::default();
+/// let exact = fuzzy_match("fer", "Ferris", &options).unwrap();
+/// assert_eq!(exact.positions, vec![0, 1, 2]);
+///
+/// let scattered = fuzzy_match("frs", "Ferris", &options).unwrap();
+/// assert_eq!(scattered.positions, vec![0, 2, 5]);
+/// assert!(scattered.score < exact.score);
+///
+/// let typo = fuzzy_match("fertis", "Ferris the crab", &options).unwrap();
+/// assert_eq!(typo.positions, vec![0, 1, 2, 3, 4, 5]);
+/// assert!(fuzzy_match("fera", "Ferris", &options).is_some());
+///
+/// assert_eq!(fuzzy_match("xyz", "Ferris", &options), None);
+/// assert_eq!(fuzzy_match("jose", "José", &options).unwrap().positions, vec![0, 1, 2, 3]);
+/// ```
+pub fn fuzzy_match(pattern: &str, text: &str, options: &SearchOptions) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = options.normalize(pattern).chars().collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+    let mut chars = Vec::with_capacity(text.len());
+    options.normalize_chars(text, &mut chars);
+
+    let matched = find_substring(&chars, &pattern).or_else(|| find_subsequence(&chars, &pattern));
+    if let Some(indices) = matched {
+        return Some(FuzzyMatch {
+            score: score(&chars, &indices),
+            positions: positions(&chars, &indices),
+        });
+    }
+
+    find_typo(&chars, &pattern)
+}
+
+/// Scores `row` against every whitespace-separated word of `query`, or returns `None` if some
+/// word matches none of `columns`.
+///
+/// Each word counts with its best match across the columns; an empty query scores `0`.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{SearchOptions, fuzzy_score};
+///
+/// let columns = ["name", "email"];
+/// let ferris = hashmap! {
+///     "name" => "Ferris".to_string(),
+///     "email" => "ferris@opensass.org".to_string(),
+/// };
+///
+/// let options = SearchOptions::default();
+/// assert!(fuzzy_score(&ferris, &columns, "fer opnsass", &options).is_some());
+/// assert_eq!(fuzzy_score(&ferris, &columns, "fer gmail", &options), None);
+/// ```
+pub fn fuzzy_score<T: TableRow + ?Sized, C: TableColumn>(
+    row: &T,
+    columns: &[C],
+    query: &str,
+    options: &SearchOptions,
+) -> Option<i32> {
+    query.split_whitespace().try_fold(0, |total, word| {
+        let best = columns
+            .iter()
+            .filter_map(|col| fuzzy_match(word, &row.cell(col.id())?, options))
+            .map(|found| found.score)
+            .max()?;
+        Some(total + best)
+    })
+}
+
+/// Splits `text` into segments, flagging the ones matched by a word of the fuzzy `query`.
+///
+/// The segments cover the whole text in order, so a renderer can wrap the flagged ones in
+/// `<mark>` and output the rest as is.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{SearchOptions, fuzzy_highlight};
+///
+/// let options = SearchOptions::default();
+/// assert_eq!(
+///     fuzzy_highlight("Ferris", "frs", &options),
+///     vec![("F", true), ("e", false), ("r", true), ("ri", false), ("s", true)],
+/// );
+/// assert_eq!(fuzzy_highlight("Ferris", "", &options), vec![("Ferris", false)]);
+/// ```
+pub fn fuzzy_highlight<'t>(
+    text: &'t str,
+    query: &str,
+    options: &SearchOptions,
+) -> Vec<(&'t str, bool)> {
+    let mut positions: Vec<usize> = query
+        .split_whitespace()
+        .filter_map(|word| fuzzy_match(word, text, options))
+        .flat_map(|found| found.positions)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+
+    let mut segments = Vec::new();
+    let mut plain_start = 0;
+    let mut iter = positions.into_iter().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start + char_len(text, start);
+        while iter.next_if_eq(&end).is_some() {
+            end += char_len(text, end);
+        }
+        if plain_start < start {
+            segments.push((&text[plain_start..start], false));
+        }
+        segments.push((&text[start..end], true));
+        plain_start = end;
+    }
+    if plain_start < text.len() || segments.is_empty() {
+        segments.push((&text[plain_start..], false));
+    }
+    segments
+}
+
+/// Byte offsets in the original text of the matched normalized characters, without repeats.
+fn positions(chars: &[(usize, char)], indices: &[usize]) -> Vec<usize> {
+    let mut positions: Vec<usize> = indices.iter().map(|&idx| chars[idx].0).collect();
+    positions.dedup();
+    positions
+}
+
+fn char_len(text: &str, offset: usize) -> usize {
+    text[offset..].chars().next().map_or(1, char::len_utf8)
+}
+
+fn is_word_start(chars: &[(usize, char)], idx: usize) -> bool {
+    idx == 0 || !chars[idx - 1].1.is_alphanumeric()
+}
+
+/// Finds `pattern` as a contiguous run, preferring one that starts a word.
+fn find_substring(chars: &[(usize, char)], pattern: &[char]) -> Option<Vec<usize>> {
+    let starts = (0..=chars.len().checked_sub(pattern.len())?).filter(|&start| {
+        chars[start..start + pattern.len()]
+            .iter()
+            .map(|&(_, c)| c)
+            .eq(pattern.iter().copied())
+    });
+    let mut first = None;
+    for start in starts {
+        if is_word_start(chars, start) {
+            first = Some(start);
+            break;
+        }
+        first.get_or_insert(start);
+    }
+    first.map(|start| (start..start + pattern.len()).collect())
+}
+
+/// Finds `pattern` as a subsequence, shrinking the window to the shortest one ending at the
+/// first complete match.
+fn find_subsequence(chars: &[(usize, char)], pattern: &[char]) -> Option<Vec<usize>> {
+    let mut next = 0;
+    let end = chars.iter().position(|&(_, c)| {
+        if c == pattern[next] {
+            next += 1;
+        }
+        next == pattern.len()
+    })?;
+
+    let mut remaining = pattern.len();
+    let start = (0..=end).rev().find(|&idx| {
+        if chars[idx].1 == pattern[remaining - 1] {
+            remaining -= 1;
+        }
+        remaining == 0
+    })?;
+
+    let mut next = 0;
+    let mut indices = Vec::with_capacity(pattern.len());
+    for (idx, &(_, c)) in chars.iter().enumerate().take(end + 1).skip(start) {
+        if next < pattern.len() && c == pattern[next] {
+            indices.push(idx);
+            next += 1;
+        }
+    }
+    Some(indices)
+}
+
+fn score(chars: &[(usize, char)], indices: &[usize]) -> i32 {
+    let mut score = 0;
+    for (k, &idx) in indices.iter().enumerate() {
+        score += SCORE_MATCH;
+        if is_word_start(chars, idx) {
+            score += BONUS_WORD_START;
+        }
+        if k > 0 {
+            match idx - indices[k - 1] - 1 {
+                0 => score += BONUS_CONSECUTIVE,
+                gap => score -= PENALTY_GAP_START + gap as i32,
+            }
+        }
+    }
+    if indices.first() == Some(&0) {
+        score += BONUS_TEXT_START;
+    }
+    score
+}
+
+/// Finds the word (or word prefix) of `chars` closest to `pattern` within the allowed typos.
+fn find_typo(chars: &[(usize, char)], pattern: &[char]) -> Option<FuzzyMatch> {
+    let max_typos = match pattern.len() {
+        0..=2 => return None,
+        3..=7 => 1,
+        _ => 2,
+    };
+
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut start = 0;
+    while start < chars.len() {
+        if !chars[start].1.is_alphanumeric() {
+            start += 1;
+            continue;
+        }
+        let end = (start..chars.len())
+            .find(|&idx| !chars[idx].1.is_alphanumeric())
+            .unwrap_or(chars.len());
+        let word: Vec<char> = chars[start..end].iter().map(|&(_, c)| c).collect();
+
+        // Also try prefixes around the length of the term, for words still being typed.
+        let prefixes = (pattern.len() - 1..=pattern.len() + 1).filter(|&len| len < word.len());
+        for len in std::iter::once(word.len()).chain(prefixes) {
+            let typos = distance(pattern, &word[..len]);
+            if typos <= max_typos && best.is_none_or(|(best_typos, ..)| typos < best_typos) {
+                best = Some((typos, start, len));
+            }
+        }
+        start = end;
+    }
+
+    let (typos, start, len) = best?;
+    let indices: Vec<usize> = (start..start + len).collect();
+    Some(FuzzyMatch {
+        score: score(chars, &indices) - PENALTY_TYPO * (typos as i32 + 1),
+        positions: positions(chars, &indices),
+    })
+}
+
+/// Optimal string alignment distance: edits with adjacent transpositions counted as one.
+fn distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>(); a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
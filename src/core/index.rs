@@ -0,0 +1,368 @@
+use crate::core::column::{TableColumn, search_columns};
+use crate::core::filter::apply_filters;
+use crate::core::normalize::SearchOptions;
+use crate::core::query::SearchSyntax;
+use crate::core::row::TableRow;
+use crate::core::state::TableState;
+use crate::core::view::matching_rows;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// What a [`SearchIndex`] keeps besides the normalized text of each row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SearchIndexKind {
+    /// Only the normalized text, which searches scan without normalizing any cell (default).
+    #[default]
+    Text,
+
+    /// Also the rows containing each run of three characters, so that queries of three
+    /// characters or more only look at the rows containing all of theirs. Slower to build and
+    /// several times larger.
+    Trigrams,
+}
+
+/// The searched cells of every row, normalized once, for [plain](SearchSyntax::Plain) searches
+/// that stay fast on large data.
+///
+/// [`sync`](Self::sync) brings the index up to date with the data: rows already indexed are
+/// recognized by a hash of their searched cells, so rows appended to the data are indexed on
+/// their own, and an edited row only causes the rows from it on to be indexed again.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{SearchIndex, SearchIndexKind, SearchOptions, filter_rows};
+///
+/// let mut data = vec![
+///     hashmap! { "name" => "Ferris".to_string(), "city" => "Zürich".to_string() },
+///     hashmap! { "name" => "Crab".to_string(), "city" => "Oslo".to_string() },
+/// ];
+/// let columns = ["name", "city"];
+/// let options = SearchOptions::default();
+///
+/// let mut index = SearchIndex::new(&columns, options, SearchIndexKind::Trigrams);
+/// assert_eq!(index.sync(&data), 2);
+/// assert_eq!(index.search("zuri"), vec![0]);
+/// assert_eq!(index.search("sz"), Vec::<usize>::new());
+/// assert_eq!(index.search("zuri"), filter_rows(&data, &columns, "zuri", &options));
+///
+/// data.push(hashmap! { "name" => "Ferros".to_string(), "city" => "Zurich".to_string() });
+/// assert_eq!(index.sync(&data), 1);
+/// assert_eq!(index.search("zuri"), vec![0, 2]);
+///
+/// data[1].insert("city", "Zug".to_string());
+/// assert_eq!(index.sync(&data), 2);
+/// assert_eq!(index.search("zu"), vec![0, 1, 2]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchIndex {
+    kind: SearchIndexKind,
+    options: SearchOptions,
+    columns: Vec<&'static str>,
+    rows: Vec<IndexedRow>,
+    /// Rows containing each trigram, ascending; empty unless `kind` is `Trigrams`.
+    trigrams: HashMap<[char; 3], Vec<u32>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct IndexedRow {
+    /// Hash of the searched cells as found in the data.
+    fingerprint: u64,
+    /// Normalized text of each present cell, each preceded by a NUL so that no match spans two
+    /// cells; empty when the row has none of the searched cells.
+    text: Box<str>,
+}
+
+impl SearchIndex {
+    /// Creates an empty index of the cells in `columns`, normalized with `options`.
+    ///
+    /// Pass the output of [`search_columns`] to index what a table searches.
+    pub fn new<C: TableColumn>(
+        columns: &[C],
+        options: SearchOptions,
+        kind: SearchIndexKind,
+    ) -> Self {
+        Self {
+            kind,
+            options,
+            columns: columns.iter().map(|col| col.id()).collect(),
+            rows: Vec::new(),
+            trigrams: HashMap::new(),
+        }
+    }
+
+    /// What the index keeps besides the normalized text.
+    pub fn kind(&self) -> SearchIndexKind {
+        self.kind
+    }
+
+    /// Returns `true` if the index holds the cells of `columns`, in that order, normalized with
+    /// `options`; otherwise it should be replaced by a new one.
+    pub fn covers<C: TableColumn>(&self, columns: &[C], options: &SearchOptions) -> bool {
+        self.options == *options
+            && self
+                .columns
+                .iter()
+                .copied()
+                .eq(columns.iter().map(|col| col.id()))
+    }
+
+    /// Number of rows indexed.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if no row is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Updates the index to hold `data`, keeping the leading rows whose searched cells did not
+    /// change, and returns the number of rows indexed anew.
+    pub fn sync<T: TableRow>(&mut self, data: &[T]) -> usize {
+        let kept = self
+            .rows
+            .iter()
+            .zip(data)
+            .take_while(|(indexed, row)| indexed.fingerprint == self.fingerprint(*row))
+            .count();
+        self.truncate(kept);
+        self.extend(&data[kept..]);
+        data.len() - kept
+    }
+
+    /// Indexes `rows` after the rows already indexed, as when they are appended to the data.
+    pub fn extend<T: TableRow>(&mut self, rows: &[T]) {
+        self.rows.reserve(rows.len());
+        for row in rows {
+            let mut text = String::new();
+            for id in &self.columns {
+                if let Some(cell) = row.cell(id) {
+                    text.push('\0');
+                    text.push_str(&self.options.normalize(&cell));
+                }
+            }
+
+            let position = self.rows.len() as u32;
+            if self.kind == SearchIndexKind::Trigrams {
+                let chars: Vec<char> = text.chars().collect();
+                for window in chars.windows(3) {
+                    if window.contains(&'\0') {
+                        continue;
+                    }
+                    let rows = self
+                        .trigrams
+                        .entry([window[0], window[1], window[2]])
+                        .or_default();
+                    if rows.last() != Some(&position) {
+                        rows.push(position);
+                    }
+                }
+            }
+
+            self.rows.push(IndexedRow {
+                fingerprint: self.fingerprint(row),
+                text: text.into_boxed_str(),
+            });
+        }
+    }
+
+    /// Drops the rows from `len` on.
+    fn truncate(&mut self, len: usize) {
+        if len >= self.rows.len() {
+            return;
+        }
+        self.rows.truncate(len);
+        self.trigrams.retain(|_, rows| {
+            rows.truncate(rows.partition_point(|&row| (row as usize) < len));
+            !rows.is_empty()
+        });
+    }
+
+    fn fingerprint<T: TableRow>(&self, row: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for id in &self.columns {
+            row.cell(id).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Returns the indices of the rows where some indexed cell contains `query`, like
+    /// [`filter_rows`](crate::core::filter_rows) over the indexed columns.
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        if query.is_empty() {
+            return (0..self.rows.len()).collect();
+        }
+
+        let needle = self.options.normalize(query);
+        let matches = |row: usize| {
+            let text = &self.rows[row].text;
+            !text.is_empty() && text.contains(needle.as_str())
+        };
+
+        let chars: Vec<char> = needle.chars().collect();
+        if self.kind == SearchIndexKind::Trigrams && chars.len() >= 3 {
+            let mut candidates = Vec::new();
+            for window in chars.windows(3) {
+                match self.trigrams.get(&[window[0], window[1], window[2]]) {
+                    Some(rows) => candidates.push(rows),
+                    None => return Vec::new(),
+                }
+            }
+            candidates.sort_by_key(|rows| rows.len());
+            let (fewest, others) = candidates.split_first().expect("at least one trigram");
+            return fewest
+                .iter()
+                .filter(|row| others.iter().all(|rows| rows.binary_search(row).is_ok()))
+                .map(|&row| row as usize)
+                .filter(|&row| matches(row))
+                .collect();
+        }
+
+        (0..self.rows.len()).filter(|&row| matches(row)).collect()
+    }
+
+    /// [`matching_rows`] answered from the index where it can be: for a plain search of the
+    /// columns and with the options the index was built for. Otherwise the rows are searched
+    /// directly.
+    ///
+    /// [`sync`](Self::sync) the index with `data` first whenever `data` may have changed: an
+    /// index of another length falls back to searching the rows, but one of the same length
+    /// cannot tell that rows were edited or replaced.
+    pub fn matching_rows<T: TableRow, C: TableColumn>(
+        &self,
+        data: &[T],
+        columns: &[C],
+        state: &TableState,
+    ) -> Vec<usize> {
+        let searched = search_columns(columns, &state.search_fields);
+        let usable = state.search_syntax == SearchSyntax::Plain
+            && self.rows.len() == data.len()
+            && self.covers(&searched, &state.search_options);
+        if !usable {
+            return matching_rows(data, columns, state);
+        }
+
+        let mut indices = self.search(&state.query);
+        apply_filters(data, columns, &state.filters, &mut indices);
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::view::filter_rows;
+
+    type Row = HashMap<&'static str, String>;
+
+    const COLUMNS: [&str; 2] = ["name", "city"];
+    const KINDS: [SearchIndexKind; 2] = [SearchIndexKind::Text, SearchIndexKind::Trigrams];
+
+    fn row(name: &str, city: &str) -> Row {
+        HashMap::from([("name", name.to_string()), ("city", city.to_string())])
+    }
+
+    fn data() -> Vec<Row> {
+        vec![
+            row("Ferris", "Zürich"),
+            row("Crab", "Oslo"),
+            row("Ferros", "Zurich"),
+            row("Lobster", "Bern"),
+        ]
+    }
+
+    /// Checks the index answers like a scan of `data`.
+    fn assert_searches(index: &SearchIndex, data: &[Row]) {
+        assert_eq!(index.len(), data.len());
+        let options = SearchOptions::default();
+        for query in [
+            "", "zur", "zu", "fer", "ferris", "oslo", "bern", "zug", "rab", "sz",
+        ] {
+            assert_eq!(
+                index.search(query),
+                filter_rows(data, &COLUMNS, query, &options),
+                "query {query:?}"
+            );
+        }
+    }
+
+    fn synced(kind: SearchIndexKind, data: &[Row]) -> SearchIndex {
+        let mut index = SearchIndex::new(&COLUMNS, SearchOptions::default(), kind);
+        assert_eq!(index.sync(data), data.len());
+        index
+    }
+
+    #[test]
+    fn sync_reindexes_from_an_edited_row() {
+        for kind in KINDS {
+            let mut data = data();
+            let mut index = synced(kind, &data);
+            data[2].insert("city", "Zug".to_string());
+            assert_eq!(index.sync(&data), 2);
+            assert_searches(&index, &data);
+            assert_eq!(index.sync(&data), 0);
+        }
+    }
+
+    #[test]
+    fn sync_drops_rows_removed_from_the_end() {
+        for kind in KINDS {
+            let mut data = data();
+            let mut index = synced(kind, &data);
+            data.truncate(2);
+            assert_eq!(index.sync(&data), 0);
+            assert_searches(&index, &data);
+
+            data.clear();
+            assert_eq!(index.sync(&data), 0);
+            assert!(index.is_empty());
+            assert_searches(&index, &data);
+        }
+    }
+
+    #[test]
+    fn sync_handles_truncated_then_refilled_data() {
+        for kind in KINDS {
+            let mut data = data();
+            let mut index = synced(kind, &data);
+            data.truncate(1);
+            data.push(row("Crab", "Zug"));
+            data.push(row("Shrimp", "Oslo"));
+            assert_eq!(index.sync(&data), 2);
+            assert_searches(&index, &data);
+        }
+    }
+
+    #[test]
+    fn sync_replaces_data_of_the_same_length() {
+        for kind in KINDS {
+            let mut index = synced(kind, &data());
+            let mut replaced = data();
+            replaced.reverse();
+            assert_eq!(index.sync(&replaced), 4);
+            assert_searches(&index, &replaced);
+        }
+    }
+
+    #[test]
+    fn rows_without_searched_cells_match_only_the_empty_query() {
+        for kind in KINDS {
+            let data = vec![row("Ferris", "Zürich"), HashMap::new()];
+            let index = synced(kind, &data);
+            assert_eq!(index.search(""), vec![0, 1]);
+            assert_searches(&index, &data);
+        }
+    }
+
+    #[test]
+    fn matching_rows_searches_the_data_when_the_index_is_stale() {
+        let mut data = data();
+        let index = synced(SearchIndexKind::Trigrams, &data);
+        data.push(row("Ferrum", "Basel"));
+        let mut state = TableState::new(10);
+        state.set_query("fer");
+        assert_eq!(index.matching_rows(&data, &COLUMNS, &state), vec![0, 2, 4]);
+    }
+}
@@ -0,0 +1,86 @@
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+/// How search text and cell text are normalized before they are compared.
+///
+/// Both sides are always decomposed with Unicode NFKD, so composed and decomposed accents match
+/// each other and full-width or styled characters (`Ｆｅｒｒｉｓ`, `ﬁ`) match their plain forms.
+/// The flags then decide what else is ignored.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::SearchOptions;
+///
+/// let options = SearchOptions::default();
+/// assert_eq!(options.normalize("José Ｍüller"), "jose muller");
+/// assert_eq!(options.normalize("Straße"), "strasse");
+///
+/// let strict = SearchOptions { ignore_diacritics: false, ..Default::default() };
+/// assert_ne!(strict.normalize("José"), strict.normalize("Jose"));
+/// assert_eq!(strict.normalize("Jose\u{301}"), strict.normalize("José"));
+///
+/// let loose = SearchOptions { ignore_punctuation: true, ..Default::default() };
+/// assert_eq!(loose.normalize("O'Brien-Smith"), "obriensmith");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SearchOptions {
+    /// Compare with Unicode case folding, so `STRASSE` finds `Straße` (default: `true`).
+    pub ignore_case: bool,
+
+    /// Strip accents and other combining marks, so `Jose` finds `José` (default: `true`).
+    pub ignore_diacritics: bool,
+
+    /// Drop punctuation and symbols, so `obrien` finds `O'Brien` (default: `false`).
+    pub ignore_punctuation: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            ignore_case: true,
+            ignore_diacritics: true,
+            ignore_punctuation: false,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Returns `text` normalized for comparison.
+    pub fn normalize(&self, text: &str) -> String {
+        if text.is_ascii() && !self.ignore_punctuation {
+            return if self.ignore_case {
+                text.to_ascii_lowercase()
+            } else {
+                text.to_string()
+            };
+        }
+
+        let mut chars = Vec::with_capacity(text.len());
+        self.normalize_chars(text, &mut chars);
+        chars.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Appends the normalized characters of `text` to `out`, each with the byte offset of the
+    /// character of `text` it came from, so that matches can be mapped back for highlighting.
+    pub(crate) fn normalize_chars(&self, text: &str, out: &mut Vec<(usize, char)>) {
+        for (offset, c) in text.char_indices() {
+            decompose_compatible(c, |c| {
+                if self.ignore_diacritics && is_combining_mark(c) {
+                    return;
+                }
+                if self.ignore_punctuation && !(c.is_alphanumeric() || c.is_whitespace()) {
+                    return;
+                }
+                if !self.ignore_case {
+                    out.push((offset, c));
+                    return;
+                }
+                // `to_lowercase` is close to case folding; these are the common exceptions.
+                match c {
+                    'ß' | 'ẞ' => out.extend([(offset, 's'), (offset, 's')]),
+                    'ς' => out.push((offset, 'σ')),
+                    _ => out.extend(c.to_lowercase().map(|c| (offset, c))),
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::core::column::TableColumn;
+use crate::core::normalize::SearchOptions;
+use crate::core::row::TableRow;
+use crate::core::value::{CellValue, ColumnKind};
+use std::cmp::Ordering;
+
+/// How the text of the search input is interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SearchSyntax {
+    /// The whole input is a substring that any column may contain (default).
+    #[default]
+    Plain,
+
+    /// The input is a [`Query`], such as `status:open priority:>2 -assignee:bot`.
+    Structured,
+
+    /// Every word of the input must [fuzzy match](crate::core::fuzzy_match) some column, which
+    /// tolerates typos and skipped letters; rows are ranked by how well they match.
+    Fuzzy,
+}
+
+/// A parsed structured search query.
+///
+/// A query is a list of whitespace-separated terms that must all match:
+/// - `word` or `"quoted phrase"`: some column contains the text.
+/// - `field:value` or `field:"quoted value"`: the column with that id contains the value.
+/// - `field:=value`: the column equals the value (compared by value for typed columns).
+/// - `field:>value`, `field:>=value`, `field:<value`, `field:<=value`: compares the column's
+///   typed value, using its [`ColumnKind`], or as a number for text columns.
+/// - `-term`: negates any of the above.
+///
+/// Text is compared after normalizing both sides with the [`SearchOptions`] given to
+/// [`Query::parse`].
+///
+/// A `field` that is not the id of a searched column makes the whole term a plain word, so text
+/// like `http://example.com` still searches as typed.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{Query, SearchOptions};
+///
+/// let columns = ["status", "priority", "assignee"];
+/// let ticket = hashmap! {
+///     "status" => "Open".to_string(),
+///     "priority" => "3".to_string(),
+///     "assignee" => "Ferris".to_string(),
+/// };
+///
+/// let parse = |input| Query::parse(input, &SearchOptions::default());
+/// assert!(parse("status:open priority:>2 -assignee:bot").matches(&ticket, &columns));
+/// assert!(!parse("priority:<=2").matches(&ticket, &columns));
+/// assert!(parse("\"fer\" -closed").matches(&ticket, &columns));
+/// assert!(!parse("status:\"in progress\"").matches(&ticket, &columns));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Query {
+    terms: Vec<Term>,
+    options: SearchOptions,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Term {
+    negated: bool,
+    field: Option<String>,
+    op: Op,
+    /// Value as typed, without quotes, parsed for comparisons.
+    value: String,
+    /// Normalized value, for text matching.
+    needle: String,
+    /// Normalized term as typed, without the negation, used when `field` is not a column.
+    raw: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Contains,
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Query {
+    /// Parses a query whose text is matched with `options`; malformed parts are read as plain
+    /// words rather than rejected.
+    pub fn parse(input: &str, options: &SearchOptions) -> Self {
+        let mut terms = Vec::new();
+        let mut rest = input.trim_start();
+
+        while !rest.is_empty() {
+            let (term, tail) = parse_term(rest, options);
+            terms.extend(term);
+            rest = tail.trim_start();
+        }
+
+        Self {
+            terms,
+            options: *options,
+        }
+    }
+
+    /// Returns `true` if the query has no terms and therefore matches every row.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns `true` if `row` matches every term, looking only at `columns`.
+    pub fn matches<T: TableRow + ?Sized, C: TableColumn>(&self, row: &T, columns: &[C]) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.matches(row, columns, &self.options) != term.negated)
+    }
+}
+
+impl Term {
+    fn matches<T: TableRow + ?Sized, C: TableColumn>(
+        &self,
+        row: &T,
+        columns: &[C],
+        options: &SearchOptions,
+    ) -> bool {
+        let column = self.field.as_deref().and_then(|field| {
+            columns
+                .iter()
+                .find(|col| col.id().eq_ignore_ascii_case(field))
+        });
+
+        let Some(column) = column else {
+            let text = if self.field.is_some() {
+                &self.raw
+            } else {
+                &self.needle
+            };
+            return columns
+                .iter()
+                .any(|col| contains(row, col.id(), text, options));
+        };
+
+        let id = column.id();
+        match self.op {
+            Op::Contains => contains(row, id, &self.needle, options),
+            Op::Eq if column.kind() == ColumnKind::Text => row
+                .cell(id)
+                .is_some_and(|cell| options.normalize(&cell) == self.needle),
+            op => {
+                let kind = match column.kind() {
+                    ColumnKind::Text => ColumnKind::Float,
+                    kind => kind,
+                };
+                let cell = row.value(id, kind);
+                let bound = CellValue::parse(self.value.as_str(), kind);
+                let comparable = match (&cell, &bound) {
+                    (CellValue::Null, _) | (_, CellValue::Null) => false,
+                    (CellValue::Text(_), other) | (other, CellValue::Text(_)) => {
+                        matches!(other, CellValue::Text(_))
+                    }
+                    _ => true,
+                };
+                if !comparable {
+                    return false;
+                }
+
+                let ordering = cell.compare(&bound);
+                match op {
+                    Op::Eq => ordering == Ordering::Equal,
+                    Op::Lt => ordering == Ordering::Less,
+                    Op::Le => ordering != Ordering::Greater,
+                    Op::Gt => ordering == Ordering::Greater,
+                    Op::Ge => ordering != Ordering::Less,
+                    Op::Contains => unreachable!("handled above"),
+                }
+            }
+        }
+    }
+}
+
+fn contains<T: TableRow + ?Sized>(row: &T, id: &str, text: &str, options: &SearchOptions) -> bool {
+    row.cell(id)
+        .is_some_and(|cell| options.normalize(&cell).contains(text))
+}
+
+/// Parses one term from the start of `input` and returns it with the unparsed rest.
+fn parse_term<'i>(input: &'i str, options: &SearchOptions) -> (Option<Term>, &'i str) {
+    let (negated, body) = match input.strip_prefix('-') {
+        Some(body) if !body.is_empty() && !body.starts_with(char::is_whitespace) => (true, body),
+        _ => (false, input),
+    };
+
+    let field_end = body.find(|c: char| c == ':' || c == '"' || c.is_whitespace());
+    let (field, value) = match field_end {
+        Some(end) if end > 0 && body[end..].starts_with(':') => {
+            (Some(&body[..end]), &body[end + 1..])
+        }
+        _ => (None, body),
+    };
+    let (op, value) = match field {
+        Some(_) => parse_op(value),
+        None => (Op::Contains, value),
+    };
+    let (value, rest) = read_value(value);
+
+    let raw = &body[..body.len() - rest.len()];
+    let needle = options.normalize(value);
+    let term = (!needle.is_empty()).then(|| Term {
+        negated,
+        field: field.map(str::to_string),
+        op,
+        value: value.to_string(),
+        needle,
+        raw: options.normalize(raw.trim_matches('"')),
+    });
+    (term, rest)
+}
+
+fn parse_op(input: &str) -> (Op, &str) {
+    for (prefix, op) in [
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+        ("=", Op::Eq),
+    ] {
+        if let Some(rest) = input.strip_prefix(prefix) {
+            return (op, rest);
+        }
+    }
+    (Op::Contains, input)
+}
+
+/// Reads a quoted phrase or a bare word, returning it and the rest of the input.
+fn read_value(input: &str) -> (&str, &str) {
+    if let Some(quoted) = input.strip_prefix('"') {
+        match quoted.find('"') {
+            Some(end) => (&quoted[..end], &quoted[end + 1..]),
+            None => (quoted, ""),
+        }
+    } else {
+        input.split_at(input.find(char::is_whitespace).unwrap_or(input.len()))
+    }
+}
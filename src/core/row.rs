@@ -0,0 +1,228 @@
+use crate::core::value::{CellValue, ColumnKind};
+use std::borrow::{Borrow, Cow};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::ptr;
+
+/// A row of table data whose cells can be looked up by column id.
+///
+/// Implement this for your own structs to pass them to a table directly, without building a
+/// `HashMap` per row. Maps keyed by strings (such as `HashMap<&'static str, String>`) implement it
+/// out of the box.
+///
+/// # Examples
+/// ```rust
+/// use std::borrow::Cow;
+/// use table_rs::core::TableRow;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct User {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// impl TableRow for User {
+///     fn cell(&self, id: &str) -> Option<Cow<'_, str>> {
+///         match id {
+///             "name" => Some(Cow::Borrowed(&self.name)),
+///             "age" => Some(Cow::Owned(self.age.to_string())),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let user = User { name: "Ferris".into(), age: 9 };
+/// assert_eq!(user.cell("age").as_deref(), Some("9"));
+/// assert_eq!(user.cell("email"), None);
+/// ```
+pub trait TableRow {
+    /// Returns the text of the cell in column `id`, or `None` if the row has no such field.
+    fn cell(&self, id: &str) -> Option<Cow<'_, str>>;
+
+    /// Returns the typed value of the cell in column `id`, used for sorting.
+    ///
+    /// The default parses the cell text according to the column's `kind`. Override it when the
+    /// row already holds typed data, to skip the round trip through text.
+    fn value(&self, id: &str, kind: ColumnKind) -> CellValue<'_> {
+        match self.cell(id) {
+            Some(text) => CellValue::parse(text, kind),
+            None => CellValue::Null,
+        }
+    }
+}
+
+impl<K, V, S> TableRow for HashMap<K, V, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn cell(&self, id: &str) -> Option<Cow<'_, str>> {
+        self.get(id).map(|v| Cow::Borrowed(v.as_ref()))
+    }
+}
+
+impl<K, V> TableRow for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn cell(&self, id: &str) -> Option<Cow<'_, str>> {
+        self.get(id).map(|v| Cow::Borrowed(v.as_ref()))
+    }
+}
+
+/// How a table tells its rows apart across renders, pages, sorts and filters.
+///
+/// The key of each row becomes the key of its `<tr>`, so that the DOM nodes, focus and any
+/// per-row state follow the row when it moves, and identifies the row in the selection. Keys
+/// must be unique among the rows of a table; without a `RowKey`, rows are keyed by their index.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{RowKey, TableRow, row_keys};
+///
+/// let rows = vec![
+///     hashmap! { "id" => "7".to_string(), "name" => "Ferris".to_string() },
+///     hashmap! { "id" => "3".to_string(), "name" => "Crab".to_string() },
+/// ];
+///
+/// assert_eq!(RowKey::Column("id").key(&rows[0]), "7");
+/// let by_name = RowKey::Computed(|row| row.cell("name").unwrap_or_default().to_lowercase());
+/// assert_eq!(by_name.key(&rows[1]), "crab");
+///
+/// assert_eq!(row_keys(Some(RowKey::Column("id")), &[1, 0], |idx| rows.get(idx), 0), ["3", "7"]);
+/// assert_eq!(row_keys(None, &[1, 0], |idx| rows.get(idx), 20), ["21", "20"]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum RowKey {
+    /// The text of the cell in this column, such as an id.
+    Column(&'static str),
+
+    /// A key computed from the whole row, for rows identified by several cells.
+    Computed(fn(&dyn TableRow) -> String),
+}
+
+// Key functions are compared by address, which is all the props diffing of the backends needs.
+impl PartialEq for RowKey {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RowKey::Column(a), RowKey::Column(b)) => a == b,
+            (RowKey::Computed(a), RowKey::Computed(b)) => ptr::fn_addr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RowKey {}
+
+impl RowKey {
+    /// Returns the key of `row`; a missing key cell gives an empty key.
+    pub fn key(&self, row: &dyn TableRow) -> String {
+        match self {
+            RowKey::Column(id) => row.cell(id).unwrap_or_default().into_owned(),
+            RowKey::Computed(key) => key(row),
+        }
+    }
+}
+
+/// Returns the keys of the rows at `indices`, looked up with `row`: from `row_key`, or else the
+/// index itself plus `offset`, such as the position of the page among the results.
+pub fn row_keys<'a, T: TableRow + 'a>(
+    row_key: Option<RowKey>,
+    indices: &[usize],
+    row: impl Fn(usize) -> Option<&'a T>,
+    offset: usize,
+) -> Vec<String> {
+    indices
+        .iter()
+        .map(|&idx| match (row_key, row(idx)) {
+            (Some(row_key), Some(row)) => row_key.key(row),
+            _ => (offset + idx).to_string(),
+        })
+        .collect()
+}
+
+/// Conversion of a field value into cell text, used by `#[derive(TableRow)]`.
+///
+/// Strings are borrowed, other primitives are formatted, and `None` yields a missing cell.
+/// Implement it for your own field types, or use `#[table(format = ...)]` on the field instead.
+pub trait ToCell {
+    /// Returns the text of the cell, or `None` if the cell is empty.
+    fn to_cell(&self) -> Option<Cow<'_, str>>;
+
+    /// Returns the typed value of the cell; by default the text parsed as `kind`.
+    fn to_value(&self, kind: ColumnKind) -> CellValue<'_> {
+        match self.to_cell() {
+            Some(text) => CellValue::parse(text, kind),
+            None => CellValue::Null,
+        }
+    }
+}
+
+impl ToCell for String {
+    fn to_cell(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl ToCell for &str {
+    fn to_cell(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl ToCell for Cow<'_, str> {
+    fn to_cell(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl<T: ToCell> ToCell for Option<T> {
+    fn to_cell(&self) -> Option<Cow<'_, str>> {
+        self.as_ref().and_then(ToCell::to_cell)
+    }
+
+    fn to_value(&self, kind: ColumnKind) -> CellValue<'_> {
+        match self {
+            Some(value) => value.to_value(kind),
+            None => CellValue::Null,
+        }
+    }
+}
+
+impl ToCell for char {
+    fn to_cell(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Owned(self.to_string()))
+    }
+}
+
+impl ToCell for bool {
+    fn to_cell(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Owned(self.to_string()))
+    }
+
+    fn to_value(&self, _kind: ColumnKind) -> CellValue<'_> {
+        CellValue::Bool(*self)
+    }
+}
+
+macro_rules! impl_to_cell_number {
+    ($variant:ident as $as:ty: $($ty:ty),*) => {
+        $(
+            impl ToCell for $ty {
+                fn to_cell(&self) -> Option<Cow<'_, str>> {
+                    Some(Cow::Owned(self.to_string()))
+                }
+
+                fn to_value(&self, _kind: ColumnKind) -> CellValue<'_> {
+                    CellValue::$variant(*self as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_cell_number!(Integer as i64: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_to_cell_number!(Float as f64: i128, u128, f32, f64);
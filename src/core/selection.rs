@@ -0,0 +1,279 @@
+use std::collections::BTreeSet;
+
+/// How many rows of a table can be selected at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SelectionMode {
+    /// Rows cannot be selected, and no checkbox column is shown (default).
+    #[default]
+    None,
+
+    /// Checking a row unchecks the one checked before.
+    Single,
+
+    /// Any number of rows, with shift-click checking ranges and a "select all" checkbox.
+    Multiple,
+}
+
+impl SelectionMode {
+    /// Returns `true` if rows can be selected, so that the checkbox column is shown.
+    pub fn is_enabled(self) -> bool {
+        self != SelectionMode::None
+    }
+}
+
+/// State of a "select all" checkbox, from the rows it covers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CheckState {
+    /// None of the rows is selected, or there are none (default).
+    #[default]
+    Unchecked,
+
+    /// Some of the rows are selected.
+    Indeterminate,
+
+    /// All of the rows are selected.
+    Checked,
+}
+
+impl CheckState {
+    /// Value of the `aria-checked` attribute for a checkbox in this state.
+    pub fn aria_checked(self) -> &'static str {
+        match self {
+            CheckState::Unchecked => "false",
+            CheckState::Indeterminate => "mixed",
+            CheckState::Checked => "true",
+        }
+    }
+}
+
+/// The selected rows of a table, by key.
+///
+/// Rows are identified by stable keys rather than by their position on the page, so the
+/// selection survives paging, sorting and filtering; rows filtered out stay selected.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{CheckState, Selection, SelectionMode};
+///
+/// let order = || ["a", "b", "c", "d"].map(String::from);
+/// let mut selection = Selection::default();
+///
+/// selection.toggle(SelectionMode::Multiple, "b", false, order());
+/// selection.toggle(SelectionMode::Multiple, "d", true, order());
+/// assert_eq!(selection.keys(), vec!["b", "c", "d"]);
+/// assert_eq!(selection.check_state(order()), CheckState::Indeterminate);
+///
+/// // Shift-clicking a checked row unchecks the range instead.
+/// selection.toggle(SelectionMode::Multiple, "c", true, order());
+/// assert_eq!(selection.keys(), vec!["d"]);
+///
+/// selection.set_all(order(), true);
+/// assert_eq!(selection.check_state(order()), CheckState::Checked);
+///
+/// selection.clear();
+/// selection.toggle(SelectionMode::Single, "a", false, order());
+/// selection.toggle(SelectionMode::Single, "c", false, order());
+/// assert_eq!(selection.keys(), vec!["c"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Selection {
+    keys: BTreeSet<String>,
+    /// Row last clicked without shift, where ranges start.
+    anchor: Option<String>,
+}
+
+impl Selection {
+    /// Returns `true` if the row `key` is selected.
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Number of rows selected.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no row is selected.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The keys of the selected rows, sorted.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.iter().cloned().collect()
+    }
+
+    /// Unselects every row.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.anchor = None;
+    }
+
+    /// Checks or unchecks the row `key`, as a click on its checkbox does.
+    ///
+    /// In [`SelectionMode::Multiple`], `extend` (shift held) gives every row between the last
+    /// row clicked and `key` the new state of `key`. `order` lists the keys of the rows in
+    /// display order, across pages; it is only read to find such a range.
+    pub fn toggle(
+        &mut self,
+        mode: SelectionMode,
+        key: &str,
+        extend: bool,
+        order: impl IntoIterator<Item = String>,
+    ) {
+        let selected = !self.contains(key);
+        match mode {
+            SelectionMode::None => return,
+            SelectionMode::Single => {
+                self.keys.clear();
+            }
+            SelectionMode::Multiple => {
+                if let Some(anchor) = self.anchor.as_deref().filter(|_| extend) {
+                    let range = range_between(order, anchor, key);
+                    self.set_all(range, selected);
+                }
+            }
+        }
+        self.set(key, selected);
+        if !extend {
+            self.anchor = Some(key.to_string());
+        }
+    }
+
+    /// Selects or unselects every row of `keys`, as the "select all" checkbox does.
+    pub fn set_all(&mut self, keys: impl IntoIterator<Item = String>, selected: bool) {
+        if selected {
+            self.keys.extend(keys);
+        } else {
+            for key in keys {
+                self.keys.remove(&key);
+            }
+        }
+    }
+
+    /// State of a "select all" checkbox covering the rows of `keys`.
+    pub fn check_state(&self, keys: impl IntoIterator<Item = String>) -> CheckState {
+        let (mut some, mut all) = (false, true);
+        for key in keys {
+            if self.contains(&key) {
+                some = true;
+            } else {
+                all = false;
+            }
+            if some && !all {
+                return CheckState::Indeterminate;
+            }
+        }
+        match (some, all) {
+            (true, true) => CheckState::Checked,
+            _ => CheckState::Unchecked,
+        }
+    }
+
+    fn set(&mut self, key: &str, selected: bool) {
+        if selected {
+            self.keys.insert(key.to_string());
+        } else {
+            self.keys.remove(key);
+        }
+    }
+}
+
+/// The keys of `order` from `a` to `b` or from `b` to `a`, or none if either is missing.
+fn range_between(order: impl IntoIterator<Item = String>, a: &str, b: &str) -> Vec<String> {
+    let mut range = Vec::new();
+    let mut end = None;
+    for key in order {
+        match end {
+            None if key == a || key == b => {
+                end = Some(if key == a { b } else { a });
+                range.push(key);
+                if a == b {
+                    return range;
+                }
+            }
+            None => {}
+            Some(end) => {
+                let last = key == end;
+                range.push(key);
+                if last {
+                    return range;
+                }
+            }
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order() -> [String; 5] {
+        ["a", "b", "c", "d", "e"].map(String::from)
+    }
+
+    #[test]
+    fn ranges_extend_upwards_from_the_anchor() {
+        let mut selection = Selection::default();
+        selection.toggle(SelectionMode::Multiple, "d", false, order());
+        selection.toggle(SelectionMode::Multiple, "b", true, order());
+        assert_eq!(selection.keys(), vec!["b", "c", "d"]);
+    }
+
+    #[test]
+    fn shift_clicks_keep_the_anchor() {
+        let mut selection = Selection::default();
+        selection.toggle(SelectionMode::Multiple, "b", false, order());
+        selection.toggle(SelectionMode::Multiple, "d", true, order());
+        selection.toggle(SelectionMode::Multiple, "a", true, order());
+        assert_eq!(selection.keys(), vec!["a", "b", "c", "d"]);
+
+        // A plain click moves the anchor.
+        selection.toggle(SelectionMode::Multiple, "e", false, order());
+        selection.toggle(SelectionMode::Multiple, "d", true, order());
+        assert_eq!(selection.keys(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn ranges_need_both_ends_in_the_order() {
+        let mut selection = Selection::default();
+        selection.toggle(SelectionMode::Multiple, "b", false, order());
+        // The anchor was filtered out since.
+        let shown = ["c", "d", "e"].map(String::from);
+        selection.toggle(SelectionMode::Multiple, "e", true, shown);
+        assert_eq!(selection.keys(), vec!["b", "e"]);
+    }
+
+    #[test]
+    fn shift_without_an_anchor_toggles_one_row() {
+        let mut selection = Selection::default();
+        selection.toggle(SelectionMode::Multiple, "c", true, order());
+        assert_eq!(selection.keys(), vec!["c"]);
+
+        selection.clear();
+        selection.toggle(SelectionMode::Multiple, "d", true, order());
+        assert_eq!(selection.keys(), vec!["d"]);
+    }
+
+    #[test]
+    fn single_mode_ignores_shift_and_none_ignores_clicks() {
+        let mut selection = Selection::default();
+        selection.toggle(SelectionMode::Single, "a", false, order());
+        selection.toggle(SelectionMode::Single, "c", true, order());
+        assert_eq!(selection.keys(), vec!["c"]);
+        selection.toggle(SelectionMode::Single, "c", false, order());
+        assert!(selection.is_empty());
+
+        selection.toggle(SelectionMode::None, "a", false, order());
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn check_state_of_no_rows_is_unchecked() {
+        let mut selection = Selection::default();
+        selection.set_all(order(), true);
+        assert_eq!(selection.check_state([]), CheckState::Unchecked);
+        assert_eq!(selection.check_state(order()), CheckState::Checked);
+    }
+}
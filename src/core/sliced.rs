@@ -0,0 +1,296 @@
+use crate::core::column::{TableColumn, search_columns};
+use crate::core::filter::apply_filters;
+use crate::core::fuzzy::fuzzy_score;
+use crate::core::query::SearchSyntax;
+use crate::core::row::TableRow;
+use crate::core::state::TableState;
+use crate::core::view::{compare_rows, matching_rows, sort_keys};
+use std::cmp::Ordering;
+use std::mem;
+
+/// Settings for searching, filtering and sorting local rows in slices, between which the
+/// browser handles input and paints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeSlicing {
+    /// Work done per slice, counted in rows searched and filtered, and in pairs of rows compared
+    /// while sorting (default: `20_000`). Lower it if typing stutters on slow devices.
+    pub budget: usize,
+}
+
+impl Default for TimeSlicing {
+    fn default() -> Self {
+        Self { budget: 20_000 }
+    }
+}
+
+/// [`matching_rows`] followed by [`sort_rows`](crate::core::sort_rows), computed a slice at a
+/// time so that large data does not block the main thread.
+///
+/// Each call to [`step`](Self::step) does at most `budget` units of work and returns `true`
+/// once the rows are in display order. A computation that is no longer wanted, because the
+/// query, the filters or the sort changed in the meantime, is abandoned by simply dropping it.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{SearchSyntax, SlicedView, SortCycle, TableState, compute_view};
+///
+/// let data: Vec<_> = ["Ferris", "Crab", "Ferros", "Fern", "Lobster"]
+///     .iter()
+///     .map(|name| hashmap! { "name" => name.to_string() })
+///     .collect();
+///
+/// let mut state = TableState::new(10);
+/// state.set_query("fer");
+/// state.toggle_sort("name", false, SortCycle::default());
+///
+/// let mut view = SlicedView::new(&state);
+/// let mut slices = 1;
+/// while !view.step(&data, &["name"], 2) {
+///     assert_eq!(view.indices(), None);
+///     slices += 1;
+/// }
+/// assert!(slices > 1);
+/// assert_eq!(view.indices(), Some(&[3, 0, 2][..]));
+/// assert_eq!(compute_view(&data, &["name"], &state).indices, vec![3, 0, 2]);
+///
+/// // Fuzzy matches are ranked by relevance in slices too.
+/// state.search_syntax = SearchSyntax::Fuzzy;
+/// state.sort.clear();
+/// let mut view = SlicedView::new(&state);
+/// while !view.step(&data, &["name"], 1) {}
+/// assert_eq!(view.indices(), Some(&compute_view(&data, &["name"], &state).indices[..]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlicedView {
+    state: TableState,
+    /// Number of rows of the data the computation started on.
+    len: Option<usize>,
+    matched: Vec<usize>,
+    /// Relevance of each matched row, for fuzzy searches.
+    scores: Vec<i32>,
+    stage: Stage,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Stage {
+    /// Searching and filtering, from this row on.
+    Match(usize),
+    /// Ordering the positions in `matched` by relevance.
+    Rank(MergeSort),
+    /// Ordering the matched rows by the sort keys.
+    Sort(MergeSort),
+    Done,
+}
+
+impl SlicedView {
+    /// Starts computing the rows matching the query, the search settings and the filters of
+    /// `state`, in the order of its sort keys.
+    pub fn new(state: &TableState) -> Self {
+        Self {
+            state: TableState {
+                page: 0,
+                cursor: None,
+                ..state.clone()
+            },
+            len: None,
+            matched: Vec::new(),
+            scores: Vec::new(),
+            stage: Stage::Match(0),
+        }
+    }
+
+    /// Returns `true` if the computation is for the search, filters and sort of `state`.
+    pub fn is_for(&self, state: &TableState) -> bool {
+        let own = &self.state;
+        own.query == state.query
+            && own.search_syntax == state.search_syntax
+            && own.search_options == state.search_options
+            && own.search_fields == state.search_fields
+            && own.filters == state.filters
+            && own.sort == state.sort
+    }
+
+    /// Returns `true` once the rows are in display order.
+    pub fn is_done(&self) -> bool {
+        self.stage == Stage::Done
+    }
+
+    /// The indices of the matching rows in display order, once computed.
+    pub fn indices(&self) -> Option<&[usize]> {
+        self.is_done().then_some(&self.matched[..])
+    }
+
+    /// Does up to `budget` units of work on `data` and returns `true` once the rows are in
+    /// display order.
+    ///
+    /// Pass the same `data` and `columns` to every call; if the number of rows changed, the
+    /// computation starts over.
+    pub fn step<T: TableRow, C: TableColumn>(
+        &mut self,
+        data: &[T],
+        columns: &[C],
+        budget: usize,
+    ) -> bool {
+        if self.len != Some(data.len()) {
+            *self = Self::new(&self.state);
+            self.len = Some(data.len());
+        }
+
+        let mut budget = budget.max(1);
+        loop {
+            match &mut self.stage {
+                Stage::Match(start) => {
+                    let start = *start;
+                    let end = start.saturating_add(budget).min(data.len());
+                    self.match_rows(data, columns, start, end);
+                    budget -= end - start;
+                    if end < data.len() {
+                        self.stage = Stage::Match(end);
+                        return false;
+                    }
+                    self.stage = match self.state.search_syntax {
+                        SearchSyntax::Fuzzy => {
+                            Stage::Rank(MergeSort::new((0..self.matched.len()).collect()))
+                        }
+                        _ => self.sort_stage(columns),
+                    };
+                }
+                Stage::Rank(ranking) => {
+                    let scores = &self.scores;
+                    let done = ranking.step(&mut budget, |a, b| scores[b].cmp(&scores[a]));
+                    if !done {
+                        return false;
+                    }
+                    let positions = mem::take(&mut ranking.items);
+                    self.matched = positions.iter().map(|&pos| self.matched[pos]).collect();
+                    self.scores = Vec::new();
+                    self.stage = self.sort_stage(columns);
+                }
+                Stage::Sort(sorting) => {
+                    let keys = sort_keys(columns, &self.state.sort);
+                    let done =
+                        sorting.step(&mut budget, |a, b| compare_rows(&data[a], &data[b], &keys));
+                    if !done {
+                        return false;
+                    }
+                    self.matched = mem::take(&mut sorting.items);
+                    self.stage = Stage::Done;
+                }
+                Stage::Done => return true,
+            }
+        }
+    }
+
+    /// Searches and filters the rows `start..end`, appending the matching ones.
+    fn match_rows<T: TableRow, C: TableColumn>(
+        &mut self,
+        data: &[T],
+        columns: &[C],
+        start: usize,
+        end: usize,
+    ) {
+        let state = &self.state;
+        if state.search_syntax != SearchSyntax::Fuzzy {
+            let found = matching_rows(&data[start..end], columns, state);
+            self.matched
+                .extend(found.into_iter().map(|idx| start + idx));
+            return;
+        }
+
+        // Fuzzy matches keep their score, since they are ranked once all are found
+        let searched = search_columns(columns, &state.search_fields);
+        let scored: Vec<(usize, i32)> = (start..end)
+            .filter_map(|idx| {
+                let score = fuzzy_score(&data[idx], &searched, &state.query, &state.search_options);
+                Some((idx, score?))
+            })
+            .collect();
+        let mut kept: Vec<usize> = scored.iter().map(|&(idx, _)| idx).collect();
+        apply_filters(data, columns, &state.filters, &mut kept);
+
+        // The rows kept are a subsequence of the scored ones
+        let mut kept = kept.into_iter().peekable();
+        for (idx, score) in scored {
+            if kept.next_if_eq(&idx).is_some() {
+                self.matched.push(idx);
+                self.scores.push(score);
+            }
+        }
+    }
+
+    fn sort_stage<C: TableColumn>(&mut self, columns: &[C]) -> Stage {
+        if sort_keys(columns, &self.state.sort).is_empty() {
+            return Stage::Done;
+        }
+        Stage::Sort(MergeSort::new(mem::take(&mut self.matched)))
+    }
+}
+
+/// A stable bottom-up merge sort that can pause after any comparison.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MergeSort {
+    items: Vec<usize>,
+    /// Output of the current pass.
+    merged: Vec<usize>,
+    /// Length of the sorted runs merged pairwise by the current pass.
+    width: usize,
+    /// Start of the left run of the current merge.
+    left: usize,
+    /// Next item of the left run, and of the right run.
+    next: (usize, usize),
+}
+
+impl MergeSort {
+    fn new(items: Vec<usize>) -> Self {
+        let merged = Vec::with_capacity(items.len());
+        let right = items.len().min(1);
+        Self {
+            items,
+            merged,
+            width: 1,
+            left: 0,
+            next: (0, right),
+        }
+    }
+
+    /// Merges until the items are sorted, returning `true`, or `budget` comparisons were made.
+    fn step(
+        &mut self,
+        budget: &mut usize,
+        mut compare: impl FnMut(usize, usize) -> Ordering,
+    ) -> bool {
+        let len = self.items.len();
+        while self.width < len {
+            while self.left < len {
+                let mid = (self.left + self.width).min(len);
+                let end = (self.left + 2 * self.width).min(len);
+                let (i, j) = &mut self.next;
+                while *i < mid && *j < end {
+                    if *budget == 0 {
+                        return false;
+                    }
+                    *budget -= 1;
+                    // Taking from the left run on ties keeps the sort stable
+                    if compare(self.items[*j], self.items[*i]) == Ordering::Less {
+                        self.merged.push(self.items[*j]);
+                        *j += 1;
+                    } else {
+                        self.merged.push(self.items[*i]);
+                        *i += 1;
+                    }
+                }
+                self.merged.extend_from_slice(&self.items[*i..mid]);
+                self.merged.extend_from_slice(&self.items[*j..end]);
+                self.left = end;
+                self.next = (end, (end + self.width).min(len));
+            }
+            mem::swap(&mut self.items, &mut self.merged);
+            self.merged.clear();
+            self.width *= 2;
+            self.left = 0;
+            self.next = (0, self.width.min(len));
+        }
+        true
+    }
+}
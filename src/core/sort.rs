@@ -0,0 +1,144 @@
+use crate::core::row::TableRow;
+use crate::core::value::{CellValue, ColumnKind};
+use std::cmp::Ordering;
+use std::ptr;
+
+/// A custom comparator for a column, used by [`sort_rows`](crate::core::sort_rows) in place of
+/// the default comparison of [`CellValue`]s.
+///
+/// Comparators always describe ascending order; descending sorts reverse their result.
+///
+/// # Examples
+/// ```rust
+/// use std::cmp::Ordering;
+/// use table_rs::core::{CellValue, SortFn};
+///
+/// fn severity(value: &CellValue<'_>) -> u8 {
+///     match value {
+///         CellValue::Text(text) if text == "critical" => 2,
+///         CellValue::Text(text) if text == "high" => 1,
+///         _ => 0,
+///     }
+/// }
+///
+/// let by_severity = SortFn::Values(|a, b| severity(a).cmp(&severity(b)));
+/// let critical = CellValue::Text("critical".into());
+/// let low = CellValue::Text("low".into());
+/// assert_eq!(by_severity.compare_values(&low, &critical), Ordering::Less);
+///
+/// assert_eq!(SortFn::Natural.compare_values(
+///     &CellValue::Text("item2".into()),
+///     &CellValue::Text("item10".into()),
+/// ), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum SortFn {
+    /// Natural ("human") order on the cell text, so `item2` sorts before `item10`.
+    Natural,
+
+    /// Compares the two cell values of the column, parsed according to its kind.
+    Values(fn(&CellValue<'_>, &CellValue<'_>) -> Ordering),
+
+    /// Compares two whole rows, for orders that depend on more than one cell.
+    Rows(fn(&dyn TableRow, &dyn TableRow) -> Ordering),
+}
+
+// Comparators are compared by address, which is all the props diffing of the backends needs.
+impl PartialEq for SortFn {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SortFn::Natural, SortFn::Natural) => true,
+            (SortFn::Values(a), SortFn::Values(b)) => ptr::fn_addr_eq(*a, *b),
+            (SortFn::Rows(a), SortFn::Rows(b)) => ptr::fn_addr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SortFn {}
+
+impl SortFn {
+    /// Compares two rows on column `id` of the given `kind` in ascending order.
+    pub fn compare(
+        &self,
+        a: &dyn TableRow,
+        b: &dyn TableRow,
+        id: &str,
+        kind: ColumnKind,
+    ) -> Ordering {
+        match self {
+            SortFn::Rows(cmp) => cmp(a, b),
+            _ => self.compare_values(&a.value(id, kind), &b.value(id, kind)),
+        }
+    }
+
+    /// Compares two cell values in ascending order.
+    ///
+    /// [`SortFn::Rows`] cannot be applied to bare values and falls back to
+    /// [`CellValue::compare`].
+    pub fn compare_values(&self, a: &CellValue<'_>, b: &CellValue<'_>) -> Ordering {
+        match self {
+            SortFn::Natural => match (a, b) {
+                (CellValue::Text(a), CellValue::Text(b)) => natural_cmp(a, b),
+                _ => a.compare(b),
+            },
+            SortFn::Values(cmp) => cmp(a, b),
+            SortFn::Rows(_) => a.compare(b),
+        }
+    }
+}
+
+/// Compares two strings in natural order: runs of ASCII digits compare by their numeric value,
+/// everything else compares case-insensitively.
+///
+/// Strings that only differ in case or leading zeros fall back to a plain comparison, so the
+/// order stays total.
+///
+/// # Examples
+/// ```rust
+/// use std::cmp::Ordering;
+/// use table_rs::core::natural_cmp;
+///
+/// assert_eq!(natural_cmp("item2", "item10"), Ordering::Less);
+/// assert_eq!(natural_cmp("v1.10.0", "v1.9.3"), Ordering::Greater);
+/// assert_eq!(natural_cmp("Apple", "apple"), Ordering::Less);
+/// ```
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_rest, mut b_rest) = (a, b);
+
+    loop {
+        let (Some(a_char), Some(b_char)) = (a_rest.chars().next(), b_rest.chars().next()) else {
+            return a_rest.len().cmp(&b_rest.len()).then_with(|| a.cmp(b));
+        };
+
+        let ordering = if a_char.is_ascii_digit() && b_char.is_ascii_digit() {
+            let (a_digits, a_tail) = split_digits(a_rest);
+            let (b_digits, b_tail) = split_digits(b_rest);
+            a_rest = a_tail;
+            b_rest = b_tail;
+
+            let a_digits = a_digits.trim_start_matches('0');
+            let b_digits = b_digits.trim_start_matches('0');
+            a_digits
+                .len()
+                .cmp(&b_digits.len())
+                .then_with(|| a_digits.cmp(b_digits))
+        } else {
+            a_rest = &a_rest[a_char.len_utf8()..];
+            b_rest = &b_rest[b_char.len_utf8()..];
+            a_char.to_lowercase().cmp(b_char.to_lowercase())
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Splits `text` into its leading run of ASCII digits and the rest.
+fn split_digits(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
@@ -0,0 +1,479 @@
+use crate::core::column::{ColumnDef, TableColumn};
+use crate::core::row::TableRow;
+use crate::core::state::{PaginationMode, TableState};
+use crate::core::view::{compute_view, paginate_rows};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::{Future, ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// One page of rows returned by a [`DataSource`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct DataPage<T> {
+    /// Rows of the requested page, already searched, filtered and sorted, in display order.
+    pub rows: Vec<T>,
+
+    /// Number of rows matching the request across all pages, used for the pagination controls.
+    pub total_rows: usize,
+
+    /// Continuation token for the page after this one, for backends that cannot jump to an
+    /// offset; `None` on the last page or when paging by offset. The table passes it back as
+    /// [`TableState::cursor`] when it requests the next page.
+    pub next_cursor: Option<String>,
+}
+
+/// The future returned by [`DataSource::fetch`]; it resolves to a page or an error message.
+///
+/// It is not `Send`, since browser fetches run on the single WebAssembly thread.
+pub type DataFuture<T> = Pin<Box<dyn Future<Output = Result<DataPage<T>, String>>>>;
+
+/// Asynchronous provider of table rows, for data too large to hand to the table in memory.
+///
+/// The table calls [`fetch`](DataSource::fetch) whenever the page, the page size, the sort, the
+/// column filters or the (debounced) search query change, and renders the returned rows as they
+/// are. Only the rows of `state.page` are expected, even in the appending
+/// [pagination modes](PaginationMode): the table keeps the pages it already loaded. A typical
+/// implementation turns the [`TableState`] into query parameters of an HTTP request;
+/// [`LocalDataSource`] serves rows from memory, for tests and prototypes.
+pub trait DataSource<T> {
+    /// Fetches the page of rows described by `state`.
+    fn fetch(&self, state: &TableState) -> DataFuture<T>;
+}
+
+/// A shared [`DataSource`], passed to the table's `data_source` prop.
+///
+/// Handles compare equal when they point to the same source, which is what prop diffing needs.
+pub struct DataSourceHandle<T>(Rc<dyn DataSource<T>>);
+
+impl<T> DataSourceHandle<T> {
+    /// Wraps `source` so that it can be passed to a table.
+    pub fn new(source: impl DataSource<T> + 'static) -> Self {
+        Self(Rc::new(source))
+    }
+
+    /// Fetches the page of rows described by `state`.
+    pub fn fetch(&self, state: &TableState) -> DataFuture<T> {
+        self.0.fetch(state)
+    }
+
+    /// Fetches the page described by `state`, retrying failures as allowed by `retry`.
+    ///
+    /// `sleep` waits for the given number of milliseconds between attempts. `is_current` is
+    /// checked after every attempt and every wait; once it returns `false` the request has been
+    /// superseded by a newer one, and `None` is returned without retrying or waiting any further,
+    /// so that a late response never overwrites a newer page.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::cell::Cell;
+    /// use std::future::ready;
+    /// use std::task::{Context, Poll, Waker};
+    /// use table_rs::core::{
+    ///     DataFuture, DataPage, DataSource, DataSourceHandle, RetryPolicy, TableState,
+    /// };
+    ///
+    /// /// Fails twice, then returns an empty page.
+    /// struct Flaky(Cell<u32>);
+    ///
+    /// impl DataSource<String> for Flaky {
+    ///     fn fetch(&self, _state: &TableState) -> DataFuture<String> {
+    ///         self.0.set(self.0.get() + 1);
+    ///         let result = match self.0.get() {
+    ///             1 | 2 => Err("503 Service Unavailable".to_string()),
+    ///             _ => Ok(DataPage::default()),
+    ///         };
+    ///         Box::pin(ready(result))
+    ///     }
+    /// }
+    ///
+    /// let source = DataSourceHandle::new(Flaky(Cell::new(0)));
+    /// let waits = Cell::new(Vec::new());
+    /// let sleep = |ms| {
+    ///     waits.set([waits.take(), vec![ms]].concat());
+    ///     ready(())
+    /// };
+    ///
+    /// let (state, retry) = (TableState::default(), RetryPolicy::default());
+    /// let mut request = Box::pin(source.fetch_with_retry(&state, &retry, sleep, || true));
+    /// let poll = request.as_mut().poll(&mut Context::from_waker(Waker::noop()));
+    /// assert_eq!(poll, Poll::Ready(Some(Ok(DataPage::default()))));
+    /// assert_eq!(waits.take(), vec![500, 1000]);
+    /// ```
+    pub async fn fetch_with_retry<S, F>(
+        &self,
+        state: &TableState,
+        retry: &RetryPolicy,
+        mut sleep: S,
+        is_current: impl Fn() -> bool,
+    ) -> Option<Result<DataPage<T>, String>>
+    where
+        S: FnMut(u32) -> F,
+        F: Future<Output = ()>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = self.fetch(state).await;
+            if !is_current() {
+                return None;
+            }
+
+            match (result, retry.delay_ms(attempt)) {
+                (Err(_), Some(delay)) => {
+                    sleep(delay).await;
+                    if !is_current() {
+                        return None;
+                    }
+                    attempt += 1;
+                }
+                (result, _) => return Some(result),
+            }
+        }
+    }
+}
+
+impl<T> Clone for DataSourceHandle<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T> PartialEq for DataSourceHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> fmt::Debug for DataSourceHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DataSourceHandle").finish_non_exhaustive()
+    }
+}
+
+impl<T> From<Rc<dyn DataSource<T>>> for DataSourceHandle<T> {
+    fn from(source: Rc<dyn DataSource<T>>) -> Self {
+        Self(source)
+    }
+}
+
+/// How often and how fast failed [`DataSource`] fetches are retried.
+///
+/// The wait doubles after every failed attempt, starting at `initial_delay_ms` and capped at
+/// `max_delay_ms`. [`RetryPolicy::NEVER`] turns retries off.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::RetryPolicy;
+///
+/// let retry = RetryPolicy { max_retries: 4, initial_delay_ms: 500, max_delay_ms: 2_000 };
+/// let delays: Vec<_> = (0..5).map(|attempt| retry.delay_ms(attempt)).collect();
+/// assert_eq!(delays, vec![Some(500), Some(1_000), Some(2_000), Some(2_000), None]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// Number of retries after the first failed attempt (default: `3`).
+    pub max_retries: u32,
+
+    /// Wait before the first retry, in milliseconds (default: `500`).
+    pub initial_delay_ms: u32,
+
+    /// Longest wait between two attempts, in milliseconds (default: `8000`).
+    pub max_delay_ms: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay_ms: 500,
+            max_delay_ms: 8_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that gives up after the first failure.
+    pub const NEVER: Self = Self {
+        max_retries: 0,
+        initial_delay_ms: 0,
+        max_delay_ms: 0,
+    };
+
+    /// Wait in milliseconds before retrying after failed attempt number `attempt` (counting
+    /// from `0`), or `None` once the retries are used up.
+    pub fn delay_ms(&self, attempt: u32) -> Option<u32> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        let factor = 1_u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        Some(
+            self.initial_delay_ms
+                .saturating_mul(factor)
+                .min(self.max_delay_ms),
+        )
+    }
+}
+
+/// The pages a table has received from its [`DataSource`].
+///
+/// Keeps the earlier pages for the appending [pagination modes](PaginationMode), and their
+/// continuation tokens for sources that page by cursor. Pages are shared, so cloning the loaded
+/// pages to add one more copies no rows.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{DataPage, LoadedPages, PaginationMode};
+///
+/// let mut loaded = LoadedPages::default();
+/// loaded.insert(0, DataPage { rows: vec!["a", "b"], total_rows: 0, next_cursor: Some("b".into()) });
+/// loaded.insert(1, DataPage { rows: vec!["c", "d"], total_rows: 0, next_cursor: Some("d".into()) });
+///
+/// assert_eq!(loaded.cursor(2), Some("d".to_string()));
+/// assert_eq!(loaded.rows(1, PaginationMode::Pages), vec!["c", "d"]);
+/// assert_eq!(loaded.rows(1, PaginationMode::LoadMore), vec!["a", "b", "c", "d"]);
+/// assert_eq!(loaded.row_count(1, PaginationMode::LoadMore), 4);
+/// assert_eq!(loaded.get(1, PaginationMode::LoadMore, 2), Some(&"c"));
+/// assert_eq!(loaded.get(1, PaginationMode::Pages, 2), None);
+/// // The source did not count the rows, but its cursor promises another page.
+/// assert_eq!(loaded.total_pages(2), 3);
+///
+/// // A fresh first page (say, for a new query) drops what came after it.
+/// loaded.insert(0, DataPage { rows: vec!["z"], total_rows: 1, next_cursor: None });
+/// assert_eq!(loaded.rows(1, PaginationMode::LoadMore), vec!["z"]);
+/// assert_eq!(loaded.total_pages(2), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadedPages<T> {
+    pages: BTreeMap<usize, Rc<DataPage<T>>>,
+}
+
+impl<T> Default for LoadedPages<T> {
+    fn default() -> Self {
+        Self {
+            pages: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Clone> LoadedPages<T> {
+    /// Stores the response for `page`, dropping any later pages, which answered an older request.
+    pub fn insert(&mut self, page: usize, data: DataPage<T>) {
+        self.pages.split_off(&page);
+        self.pages.insert(page, Rc::new(data));
+    }
+
+    /// Returns `true` if the response for `page` has been stored.
+    pub fn contains(&self, page: usize) -> bool {
+        self.pages.contains_key(&page)
+    }
+
+    /// Continuation token to request `page` with: the `next_cursor` of the page before it.
+    pub fn cursor(&self, page: usize) -> Option<String> {
+        let previous = self.pages.get(&page.checked_sub(1)?)?;
+        previous.next_cursor.clone()
+    }
+
+    /// Rows to display for `page`: that page alone, or every loaded page up to it when `mode`
+    /// appends.
+    pub fn rows(&self, page: usize, mode: PaginationMode) -> Vec<T> {
+        self.iter(page, mode).cloned().collect()
+    }
+
+    /// The rows [`rows`](Self::rows) returns for `page` and `mode`, borrowed.
+    pub fn iter(&self, page: usize, mode: PaginationMode) -> impl Iterator<Item = &T> {
+        let first = if mode.appends() { 0 } else { page };
+        self.pages
+            .range(first..=page)
+            .flat_map(|(_, data)| data.rows.iter())
+    }
+
+    /// The row at `position` among those [`rows`](Self::rows) returns for `page` and `mode`,
+    /// found by skipping whole pages rather than walking the rows before it.
+    pub fn get(&self, page: usize, mode: PaginationMode, position: usize) -> Option<&T> {
+        let first = if mode.appends() { 0 } else { page };
+        let mut position = position;
+        for (_, data) in self.pages.range(first..=page) {
+            match data.rows.get(position) {
+                Some(row) => return Some(row),
+                None => position -= data.rows.len(),
+            }
+        }
+        None
+    }
+
+    /// Number of rows [`rows`](Self::rows) returns for `page` and `mode`, without cloning them.
+    pub fn row_count(&self, page: usize, mode: PaginationMode) -> usize {
+        let first = if mode.appends() { 0 } else { page };
+        self.pages
+            .range(first..=page)
+            .map(|(_, data)| data.rows.len())
+            .sum()
+    }
+
+    /// Number of pages to offer: enough for the `total_rows` of the last loaded page, and at
+    /// least one past it while that page has a `next_cursor`.
+    pub fn total_pages(&self, page_size: usize) -> usize {
+        let Some((&last, data)) = self.pages.last_key_value() else {
+            return 1;
+        };
+        let counted = paginate_rows(data.total_rows, 0, page_size).1;
+        counted.max(last + 1 + usize::from(data.next_cursor.is_some()))
+    }
+}
+
+/// A [`DataSource`] serving rows from memory with the same pipeline as a local table.
+///
+/// Useful in tests and while the real backend is not ready; pages resolve immediately.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use std::task::{Context, Poll, Waker};
+/// use table_rs::core::{DataSource, LocalDataSource, TableState};
+///
+/// let data = vec![
+///     hashmap! { "name" => "Ferris".to_string() },
+///     hashmap! { "name" => "Crab".to_string() },
+///     hashmap! { "name" => "Ferros".to_string() },
+/// ];
+/// let source = LocalDataSource::new(data, &["name"]);
+///
+/// let mut state = TableState::new(1);
+/// state.set_query("fer");
+///
+/// let mut page = source.fetch(&state);
+/// let Poll::Ready(Ok(page)) = page.as_mut().poll(&mut Context::from_waker(Waker::noop())) else {
+///     panic!("local pages are ready immediately");
+/// };
+/// assert_eq!(page.rows[0]["name"], "Ferris");
+/// assert_eq!(page.total_rows, 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalDataSource<T> {
+    data: Rc<Vec<T>>,
+    columns: Vec<ColumnDef>,
+}
+
+impl<T> LocalDataSource<T> {
+    /// Serves `data`, searching, filtering and sorting it by `columns`.
+    pub fn new<C: TableColumn>(data: Vec<T>, columns: &[C]) -> Self {
+        let columns = columns
+            .iter()
+            .map(|col| ColumnDef {
+                id: col.id(),
+                kind: col.kind(),
+                sort_fn: col.sort_fn(),
+                first_sort_order: col.first_sort_order(),
+                filter: col.filter(),
+                searchable: col.searchable(),
+                ..Default::default()
+            })
+            .collect();
+
+        Self {
+            data: Rc::new(data),
+            columns,
+        }
+    }
+}
+
+impl<T: TableRow + Clone + 'static> DataSource<T> for LocalDataSource<T> {
+    fn fetch(&self, state: &TableState) -> DataFuture<T> {
+        let view = compute_view(&self.data, &self.columns, state);
+        // The view holds the earlier pages too when appending; only the requested one is sent.
+        let skip = if state.pagination_mode.appends() {
+            view.page * state.page_size.max(1)
+        } else {
+            0
+        };
+        let rows = view.indices[skip..]
+            .iter()
+            .map(|&idx| self.data[idx].clone());
+
+        Box::pin(ready(Ok(DataPage {
+            rows: rows.collect(),
+            total_rows: view.total_rows,
+            next_cursor: None,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(rows: &[&'static str], total_rows: usize) -> DataPage<&'static str> {
+        DataPage {
+            rows: rows.to_vec(),
+            total_rows,
+            next_cursor: None,
+        }
+    }
+
+    fn three_pages() -> LoadedPages<&'static str> {
+        let mut loaded = LoadedPages::default();
+        loaded.insert(0, page(&["a", "b"], 6));
+        loaded.insert(1, page(&["c", "d"], 6));
+        loaded.insert(2, page(&["e", "f"], 6));
+        loaded
+    }
+
+    #[test]
+    fn insert_drops_the_pages_after_it() {
+        let mut loaded = three_pages();
+        loaded.insert(1, page(&["x"], 3));
+        assert!(loaded.contains(0));
+        assert!(!loaded.contains(2));
+        assert_eq!(
+            loaded.rows(2, PaginationMode::LoadMore),
+            vec!["a", "b", "x"]
+        );
+        assert_eq!(loaded.total_pages(2), 2);
+    }
+
+    #[test]
+    fn insert_of_the_last_page_replaces_it_only() {
+        let mut loaded = three_pages();
+        loaded.insert(2, page(&["y"], 5));
+        assert_eq!(
+            loaded.rows(2, PaginationMode::LoadMore),
+            vec!["a", "b", "c", "d", "y"]
+        );
+    }
+
+    #[test]
+    fn appending_rows_skip_pages_not_loaded() {
+        let mut loaded = LoadedPages::default();
+        loaded.insert(0, page(&["a", "b"], 6));
+        loaded.insert(2, page(&["e"], 6));
+        let rows = loaded.rows(2, PaginationMode::LoadMore);
+        assert_eq!(rows, vec!["a", "b", "e"]);
+        assert_eq!(loaded.row_count(2, PaginationMode::LoadMore), 3);
+        for (position, row) in rows.iter().enumerate() {
+            assert_eq!(loaded.get(2, PaginationMode::LoadMore, position), Some(row));
+        }
+        assert_eq!(loaded.get(2, PaginationMode::LoadMore, 3), None);
+        assert!(loaded.rows(1, PaginationMode::Pages).is_empty());
+    }
+
+    #[test]
+    fn get_matches_rows_in_every_mode() {
+        let loaded = three_pages();
+        for mode in [PaginationMode::Pages, PaginationMode::LoadMore] {
+            let rows = loaded.rows(1, mode);
+            for (position, row) in rows.iter().enumerate() {
+                assert_eq!(loaded.get(1, mode, position), Some(row));
+            }
+            assert_eq!(loaded.get(1, mode, rows.len()), None);
+        }
+    }
+
+    #[test]
+    fn empty_pages_offer_one_page_and_no_cursor() {
+        let loaded = LoadedPages::<&str>::default();
+        assert_eq!(loaded.total_pages(10), 1);
+        assert_eq!(loaded.cursor(0), None);
+        assert_eq!(loaded.cursor(1), None);
+        assert!(loaded.rows(0, PaginationMode::LoadMore).is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use crate::core::column::TableColumn;
+use crate::core::filter::FilterValue;
+use crate::core::normalize::SearchOptions;
+use crate::core::query::SearchSyntax;
+use std::collections::BTreeMap;
+
+/// Delay, in milliseconds, between the last keystroke in the search input and the query update.
+pub const SEARCH_DEBOUNCE_MS: u32 = 300;
+
+/// Sort direction for a column: ascending or descending.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SortOrder {
+    /// Ascending order (default).
+    #[default]
+    Asc,
+
+    /// Descending order.
+    Desc,
+}
+
+impl SortOrder {
+    /// Returns the opposite direction.
+    pub fn reversed(self) -> Self {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+
+    /// Value of the `aria-sort` attribute for a header sorted in this direction.
+    pub fn aria_sort(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ascending",
+            SortOrder::Desc => "descending",
+        }
+    }
+}
+
+/// How the rows after the first page are reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum PaginationMode {
+    /// Previous and Next buttons flip between pages, showing one at a time (default).
+    #[default]
+    Pages,
+
+    /// A "Load more" button appends the next page below the rows already shown.
+    LoadMore,
+
+    /// The next page is appended as soon as the end of the table scrolls into view.
+    InfiniteScroll,
+}
+
+impl PaginationMode {
+    /// Returns `true` if later pages are appended to the rows already shown instead of
+    /// replacing them.
+    pub fn appends(self) -> bool {
+        self != PaginationMode::Pages
+    }
+}
+
+/// A column id and the direction to sort it in.
+pub type SortKey = (&'static str, SortOrder);
+
+/// The states a column goes through as its header is clicked repeatedly.
+///
+/// A column starts unsorted, is sorted in the `first` direction on the first click and in the
+/// opposite one on the second. The third click goes back to `first`, or, with `allow_unsorted`,
+/// removes the column from the sort so the original data order comes back.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{SortCycle, SortOrder};
+///
+/// let newest_first = SortCycle::new(SortOrder::Desc, true);
+/// assert_eq!(newest_first.next(None), Some(SortOrder::Desc));
+/// assert_eq!(newest_first.next(Some(SortOrder::Desc)), Some(SortOrder::Asc));
+/// assert_eq!(newest_first.next(Some(SortOrder::Asc)), None);
+///
+/// assert_eq!(SortCycle::default().next(Some(SortOrder::Desc)), Some(SortOrder::Asc));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct SortCycle {
+    /// Direction applied by the first click on an unsorted column.
+    pub first: SortOrder,
+
+    /// Whether the third click unsorts the column instead of starting over.
+    pub allow_unsorted: bool,
+}
+
+impl SortCycle {
+    /// Creates a cycle starting in the `first` direction.
+    pub fn new(first: SortOrder, allow_unsorted: bool) -> Self {
+        Self {
+            first,
+            allow_unsorted,
+        }
+    }
+
+    /// Creates the cycle of column `id`, starting in its
+    /// [`first_sort_order`](TableColumn::first_sort_order) (ascending if it is not in `columns`).
+    pub fn for_column<C: TableColumn>(columns: &[C], id: &str, allow_unsorted: bool) -> Self {
+        let first = columns
+            .iter()
+            .find(|col| col.id() == id)
+            .map_or(SortOrder::Asc, TableColumn::first_sort_order);
+        Self::new(first, allow_unsorted)
+    }
+
+    /// Returns the state following `current`, where `None` means unsorted.
+    pub fn next(self, current: Option<SortOrder>) -> Option<SortOrder> {
+        match current {
+            None => Some(self.first),
+            Some(order) if order == self.first => Some(order.reversed()),
+            Some(_) if self.allow_unsorted => None,
+            Some(_) => Some(self.first),
+        }
+    }
+}
+
+/// Updates an ordered list of sort keys after a click on the header of column `id`.
+///
+/// A plain click makes `id` the only key, moving it to the next state of `cycle`. An additive
+/// click (shift-click) moves `id` to its next state in place if it is already a key, or appends
+/// it as the lowest-priority key otherwise. A column whose next state is unsorted is removed.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{SortCycle, SortOrder, toggle_sort_key};
+///
+/// let cycle = SortCycle::new(SortOrder::Asc, true);
+/// let mut keys = Vec::new();
+/// toggle_sort_key(&mut keys, "department", false, cycle);
+/// toggle_sort_key(&mut keys, "salary", true, cycle);
+/// toggle_sort_key(&mut keys, "salary", true, cycle);
+/// assert_eq!(keys, vec![("department", SortOrder::Asc), ("salary", SortOrder::Desc)]);
+///
+/// toggle_sort_key(&mut keys, "salary", true, cycle);
+/// assert_eq!(keys, vec![("department", SortOrder::Asc)]);
+///
+/// toggle_sort_key(&mut keys, "salary", false, cycle);
+/// assert_eq!(keys, vec![("salary", SortOrder::Asc)]);
+/// ```
+pub fn toggle_sort_key(
+    keys: &mut Vec<SortKey>,
+    id: &'static str,
+    additive: bool,
+    cycle: SortCycle,
+) {
+    let position = keys.iter().position(|(key, _)| *key == id);
+    let next = cycle.next(position.map(|idx| keys[idx].1));
+
+    if additive {
+        match (position, next) {
+            (Some(idx), Some(order)) => keys[idx].1 = order,
+            (Some(idx), None) => {
+                keys.remove(idx);
+            }
+            (None, Some(order)) => keys.push((id, order)),
+            (None, None) => {}
+        }
+    } else {
+        keys.clear();
+        keys.extend(next.map(|order| (id, order)));
+    }
+}
+
+/// Returns the zero-based priority and direction of column `id` in `keys`, if it is sorted.
+pub fn sort_key_position(keys: &[SortKey], id: &str) -> Option<(usize, SortOrder)> {
+    keys.iter()
+        .position(|(key, _)| *key == id)
+        .map(|idx| (idx, keys[idx].1))
+}
+
+/// Everything the engine needs to know about the user's interaction with a table.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{SortCycle, SortOrder, TableState};
+///
+/// let mut state = TableState::new(25);
+/// state.set_query("ferris");
+/// state.toggle_sort("name", false, SortCycle::default());
+/// assert_eq!(state.sort, vec![("name", SortOrder::Asc)]);
+///
+/// state.toggle_sort("name", false, SortCycle::default());
+/// state.toggle_sort("age", true, SortCycle::default());
+/// assert_eq!(state.sort, vec![("name", SortOrder::Desc), ("age", SortOrder::Asc)]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableState {
+    /// Current search query; an empty string matches every row.
+    pub query: String,
+
+    /// How the search query is interpreted.
+    pub search_syntax: SearchSyntax,
+
+    /// How the search query and the cells are normalized before matching.
+    pub search_options: SearchOptions,
+
+    /// Row keys searched in addition to the searchable columns, without being displayed.
+    pub search_fields: Vec<&'static str>,
+
+    /// Columns to sort by, highest priority first; later keys break ties of earlier ones.
+    pub sort: Vec<SortKey>,
+
+    /// Per-column filter inputs, keyed by column id; all of them must match.
+    pub filters: BTreeMap<&'static str, FilterValue>,
+
+    /// Zero-based index of the requested page.
+    pub page: usize,
+
+    /// Number of rows per page; `0` is treated as `1`.
+    pub page_size: usize,
+
+    /// Whether pages are flipped or appended; in the appending modes `page` is the last page
+    /// loaded and the view holds every row up to the end of it.
+    pub pagination_mode: PaginationMode,
+
+    /// Continuation token of the requested page, taken from the
+    /// [`next_cursor`](crate::core::DataPage::next_cursor) of the page before it; `None` for the
+    /// first page and for sources that page by offset.
+    pub cursor: Option<String>,
+}
+
+impl Default for TableState {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl TableState {
+    /// Creates an unsorted, unfiltered state on the first page.
+    pub fn new(page_size: usize) -> Self {
+        Self {
+            query: String::new(),
+            search_syntax: SearchSyntax::Plain,
+            search_options: SearchOptions::default(),
+            search_fields: Vec::new(),
+            sort: Vec::new(),
+            filters: BTreeMap::new(),
+            page: 0,
+            page_size,
+            pagination_mode: PaginationMode::Pages,
+            cursor: None,
+        }
+    }
+
+    /// Replaces the search query and jumps back to the first page.
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.page = 0;
+    }
+
+    /// Replaces the filter input of column `id` and jumps back to the first page.
+    pub fn set_filter(&mut self, id: &'static str, value: FilterValue) {
+        self.filters.insert(id, value);
+        self.page = 0;
+    }
+
+    /// Handles a click on the header of column `id`; see [`toggle_sort_key`].
+    ///
+    /// In the appending pagination modes this also jumps back to the first page, since the rows
+    /// loaded so far no longer line up with the new order.
+    pub fn toggle_sort(&mut self, id: &'static str, additive: bool, cycle: SortCycle) {
+        toggle_sort_key(&mut self.sort, id, additive, cycle);
+        if self.pagination_mode.appends() {
+            self.page = 0;
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+/// How the values of a column are interpreted when sorting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ColumnKind {
+    /// Plain text, compared lexicographically (default).
+    #[default]
+    Text,
+
+    /// Whole numbers such as ids or ages.
+    Integer,
+
+    /// Decimal numbers such as prices or scores.
+    Float,
+
+    /// Booleans written as `true`/`false`, `yes`/`no`, `on`/`off` or `1`/`0`.
+    Bool,
+
+    /// Calendar dates written as `YYYY-MM-DD`.
+    Date,
+
+    /// Timestamps in ISO 8601 (`2024-05-01T13:45:00Z`) or JavaScript `Date.toString()` form.
+    DateTime,
+}
+
+/// A typed cell value, produced by [`TableRow::value`](crate::core::TableRow::value).
+///
+/// Values of different variants still compare consistently: `Null` sorts first, then booleans,
+/// numbers, dates and finally text. Integers and floats compare numerically with each other, and
+/// so do dates and timestamps.
+///
+/// # Examples
+/// ```rust
+/// use std::cmp::Ordering;
+/// use table_rs::core::{CellValue, ColumnKind};
+///
+/// let nine = CellValue::parse("9", ColumnKind::Integer);
+/// let ten = CellValue::parse("10", ColumnKind::Integer);
+/// assert_eq!(nine.compare(&ten), Ordering::Less);
+///
+/// let may = CellValue::parse("2024-05-01", ColumnKind::Date);
+/// let june = CellValue::parse("2024-06-01T08:00:00+02:00", ColumnKind::DateTime);
+/// assert_eq!(may.compare(&june), Ordering::Less);
+///
+/// assert_eq!(CellValue::parse("", ColumnKind::Float), CellValue::Null);
+/// assert_eq!(CellValue::parse("n/a", ColumnKind::Integer), CellValue::Text("n/a".into()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellValue<'a> {
+    /// A missing or empty cell.
+    Null,
+
+    /// Text, or a value that could not be parsed as the column's kind.
+    Text(Cow<'a, str>),
+
+    /// A whole number.
+    Integer(i64),
+
+    /// A decimal number.
+    Float(f64),
+
+    /// A boolean.
+    Bool(bool),
+
+    /// A date, as days since 1970-01-01.
+    Date(i64),
+
+    /// A timestamp, as milliseconds since 1970-01-01T00:00:00Z.
+    DateTime(i64),
+}
+
+impl<'a> CellValue<'a> {
+    /// Parses cell text as a value of the given kind.
+    ///
+    /// Empty (or whitespace-only) text is [`CellValue::Null`]; text that does not parse as `kind`
+    /// is kept as [`CellValue::Text`].
+    pub fn parse(text: impl Into<Cow<'a, str>>, kind: ColumnKind) -> Self {
+        let text = text.into();
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return CellValue::Null;
+        }
+
+        let parsed = match kind {
+            ColumnKind::Text => None,
+            ColumnKind::Integer => trimmed
+                .parse::<i64>()
+                .map(CellValue::Integer)
+                .ok()
+                .or_else(|| trimmed.parse::<f64>().ok().map(CellValue::Float)),
+            ColumnKind::Float => trimmed.parse::<f64>().ok().map(CellValue::Float),
+            ColumnKind::Bool => parse_bool(trimmed).map(CellValue::Bool),
+            ColumnKind::Date | ColumnKind::DateTime => parse_date(trimmed)
+                .map(CellValue::Date)
+                .or_else(|| parse_date_time(trimmed).map(CellValue::DateTime)),
+        };
+
+        parsed.unwrap_or(CellValue::Text(text))
+    }
+
+    /// Compares two values with a total order suitable for sorting.
+    pub fn compare(&self, other: &CellValue<'_>) -> Ordering {
+        use CellValue::*;
+
+        match (self, other) {
+            (Null, Null) => Ordering::Equal,
+            (Text(a), Text(b)) => a.cmp(b),
+            (Bool(a), Bool(b)) => a.cmp(b),
+            (Integer(a), Integer(b)) => a.cmp(b),
+            (Float(a), Float(b)) => a.total_cmp(b),
+            (Integer(a), Float(b)) => (*a as f64).total_cmp(b),
+            (Float(a), Integer(b)) => a.total_cmp(&(*b as f64)),
+            (Date(a), Date(b)) | (DateTime(a), DateTime(b)) => a.cmp(b),
+            (Date(a), DateTime(b)) => a.saturating_mul(MS_PER_DAY).cmp(b),
+            (DateTime(a), Date(b)) => a.cmp(&b.saturating_mul(MS_PER_DAY)),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+
+    /// Position of the variant in the cross-type ordering.
+    fn rank(&self) -> u8 {
+        match self {
+            CellValue::Null => 0,
+            CellValue::Bool(_) => 1,
+            CellValue::Integer(_) | CellValue::Float(_) => 2,
+            CellValue::Date(_) | CellValue::DateTime(_) => 3,
+            CellValue::Text(_) => 4,
+        }
+    }
+}
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// Years accepted in dates, a little beyond those of JavaScript's `Date`, so that the day and
+/// millisecond arithmetic cannot overflow.
+const MAX_YEAR: i64 = 275_000;
+
+fn parse_bool(text: &str) -> Option<bool> {
+    match text.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" | "on" | "1" => Some(true),
+        "false" | "no" | "n" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses `YYYY-MM-DD` into days since the Unix epoch.
+fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    days_from_civil(year, month, day)
+}
+
+/// Parses an ISO 8601 or JavaScript `Date.toString()` timestamp into Unix milliseconds.
+fn parse_date_time(text: &str) -> Option<i64> {
+    parse_iso_date_time(text).or_else(|| parse_js_date_time(text))
+}
+
+/// `2024-05-01T13:45:00`, with optional fraction and `Z`/`±HH:MM` offset; a space may replace `T`.
+fn parse_iso_date_time(text: &str) -> Option<i64> {
+    let (date, rest) = text.split_at_checked(10)?;
+    let days = parse_date(date)?;
+    let rest = rest.strip_prefix(['T', 't', ' '])?;
+
+    let (time, offset_ms) = if let Some(time) = rest.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else if let Some(pos) = rest.rfind(['+', '-']) {
+        (&rest[..pos], parse_offset(&rest[pos..])?)
+    } else {
+        (rest, 0)
+    };
+
+    Some(days * MS_PER_DAY + parse_time(time)? - offset_ms)
+}
+
+/// `Thu Oct 16 2025 12:34:56 GMT+0200 (Central European Summer Time)`.
+fn parse_js_date_time(text: &str) -> Option<i64> {
+    let mut parts = text.split_whitespace();
+    let _weekday = parts.next()?;
+    let month = parse_month(parts.next()?)?;
+    let day = parts.next()?.parse().ok()?;
+    let year = parts.next()?.parse().ok()?;
+    let time = parse_time(parts.next()?)?;
+    let offset_ms = match parts.next() {
+        Some(zone) => parse_offset(zone.strip_prefix("GMT").or(zone.strip_prefix("UTC"))?)?,
+        None => 0,
+    };
+
+    Some(days_from_civil(year, month, day)? * MS_PER_DAY + time - offset_ms)
+}
+
+/// Parses `HH:MM[:SS[.fff]]` into milliseconds since midnight.
+fn parse_time(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, ':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let (seconds, millis) = match parts.next() {
+        Some(seconds) => match seconds.split_once(['.', ',']) {
+            Some((whole, fraction)) => {
+                let fraction = format!("{fraction:0<3}");
+                (whole.parse().ok()?, fraction.get(..3)?.parse().ok()?)
+            }
+            None => (seconds.parse().ok()?, 0),
+        },
+        None => (0, 0),
+    };
+
+    if hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis)
+}
+
+/// Parses `±HH:MM`, `±HHMM` or `±HH` into milliseconds east of UTC; an empty offset is UTC.
+fn parse_offset(text: &str) -> Option<i64> {
+    if text.is_empty() {
+        return Some(0);
+    }
+    let (sign, digits) = match text.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    let digits = digits.replace(':', "");
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i64>().ok()?, 0),
+        4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+        _ => return None,
+    };
+    Some(sign * (hours * 60 + minutes) * 60_000)
+}
+
+fn parse_month(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let prefix = name.get(..3)?.to_ascii_lowercase();
+    MONTHS
+        .iter()
+        .position(|month| *month == prefix)
+        .map(|idx| idx as u32 + 1)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date, rejecting impossible dates and years
+/// beyond [`MAX_YEAR`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    if !(-MAX_YEAR..=MAX_YEAR).contains(&year) {
+        return None;
+    }
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if day == 0 || day > days_in_month {
+        return None;
+    }
+
+    // Howard Hinnant's days_from_civil algorithm.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_years_stay_text() {
+        for text in [
+            "99999999999999-01-01",
+            "9223372036854775807-03-01",
+            "-275001-01-01",
+        ] {
+            assert_eq!(
+                CellValue::parse(text, ColumnKind::Date),
+                CellValue::Text(text.into())
+            );
+        }
+        let js = "Thu Oct 16 9223372036854775807 12:34:56 GMT+0200";
+        assert_eq!(
+            CellValue::parse(js, ColumnKind::DateTime),
+            CellValue::Text(js.into())
+        );
+    }
+
+    #[test]
+    fn extreme_dates_compare_without_overflow() {
+        let far = CellValue::parse("275000-12-31", ColumnKind::Date);
+        let now = CellValue::parse("2024-05-01T00:00:00Z", ColumnKind::DateTime);
+        assert_eq!(far.compare(&now), Ordering::Greater);
+        assert_eq!(now.compare(&far), Ordering::Less);
+
+        let huge = CellValue::Date(i64::MAX);
+        assert_eq!(
+            huge.compare(&CellValue::DateTime(i64::MAX - 1)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            CellValue::DateTime(0).compare(&CellValue::Date(i64::MIN)),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn dates_compare_with_timestamps_of_the_same_day() {
+        let day = CellValue::parse("2024-05-01", ColumnKind::Date);
+        let midnight = CellValue::parse("2024-05-01T00:00:00Z", ColumnKind::DateTime);
+        let noon = CellValue::parse("2024-05-01 12:00:00+02:00", ColumnKind::DateTime);
+        assert_eq!(day.compare(&midnight), Ordering::Equal);
+        assert_eq!(day.compare(&noon), Ordering::Less);
+        assert_eq!(
+            CellValue::parse("2023-02-29", ColumnKind::Date),
+            CellValue::Text("2023-02-29".into())
+        );
+    }
+}
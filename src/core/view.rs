@@ -0,0 +1,479 @@
+use crate::core::column::{TableColumn, search_columns};
+use crate::core::filter::apply_filters;
+use crate::core::fuzzy::fuzzy_score;
+use crate::core::normalize::SearchOptions;
+use crate::core::query::{Query, SearchSyntax};
+use crate::core::row::TableRow;
+use crate::core::sort::SortFn;
+use crate::core::state::{SortKey, SortOrder, TableState};
+use crate::core::value::ColumnKind;
+use std::cmp::Ordering;
+
+/// The rows a table should display for a given [`TableState`], plus page metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct TableView {
+    /// Indices into the data of the rows on the current page, in display order. In the appending
+    /// [pagination modes](crate::core::PaginationMode) these are the rows of every page up to
+    /// the current one.
+    pub indices: Vec<usize>,
+
+    /// Number of rows matching the query, across all pages.
+    pub total_rows: usize,
+
+    /// Number of pages; always at least `1`, even when nothing matches.
+    pub total_pages: usize,
+
+    /// Current page, clamped to `0..total_pages`.
+    pub page: usize,
+}
+
+/// Runs the whole pipeline (search, column filters, sort, paginate) and returns the rows to render.
+///
+/// This is [`matching_rows`], then [`sort_rows`], then [`page_view`]. Backends that keep the
+/// output of each stage only rerun the stages whose inputs changed, so that flipping pages
+/// neither searches nor sorts again.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{PaginationMode, SortCycle, TableState, compute_view};
+///
+/// let data = vec![
+///     hashmap! { "name" => "Ferris".to_string() },
+///     hashmap! { "name" => "Crab".to_string() },
+///     hashmap! { "name" => "Ferros".to_string() },
+/// ];
+///
+/// let mut state = TableState::new(1);
+/// state.set_query("fer");
+/// state.toggle_sort("name", false, SortCycle::default());
+///
+/// let view = compute_view(&data, &["name"], &state);
+/// assert_eq!(view.indices, vec![0]);
+/// assert_eq!(view.total_rows, 2);
+/// assert_eq!(view.total_pages, 2);
+///
+/// state.pagination_mode = PaginationMode::LoadMore;
+/// state.page = 1;
+/// assert_eq!(compute_view(&data, &["name"], &state).indices, vec![0, 2]);
+/// ```
+pub fn compute_view<T: TableRow, C: TableColumn>(
+    data: &[T],
+    columns: &[C],
+    state: &TableState,
+) -> TableView {
+    let mut indices = matching_rows(data, columns, state);
+    sort_rows(data, columns, &mut indices, &state.sort);
+    page_view(&indices, state)
+}
+
+/// Returns the indices of the rows matching the search query and the column filters of `state`,
+/// in data order (or by relevance for fuzzy search).
+///
+/// Only the query, the search settings and the filters of `state` are read.
+pub fn matching_rows<T: TableRow, C: TableColumn>(
+    data: &[T],
+    columns: &[C],
+    state: &TableState,
+) -> Vec<usize> {
+    let searched = search_columns(columns, &state.search_fields);
+    let mut indices = search_rows(
+        data,
+        &searched,
+        &state.query,
+        state.search_syntax,
+        &state.search_options,
+    );
+    apply_filters(data, columns, &state.filters, &mut indices);
+    indices
+}
+
+/// Returns the page of `indices` that `state` asks for, with the page metadata.
+///
+/// `indices` are the matching rows in display order, as left by [`matching_rows`] and
+/// [`sort_rows`]; only the paging settings of `state` are read.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{PaginationMode, TableState, page_view};
+///
+/// let sorted = vec![4, 2, 0, 3, 1];
+/// let mut state = TableState::new(2);
+/// state.page = 1;
+/// assert_eq!(page_view(&sorted, &state).indices, vec![0, 3]);
+///
+/// state.pagination_mode = PaginationMode::LoadMore;
+/// let view = page_view(&sorted, &state);
+/// assert_eq!(view.indices, vec![4, 2, 0, 3]);
+/// assert_eq!((view.total_rows, view.total_pages, view.page), (5, 3, 1));
+/// ```
+pub fn page_view(indices: &[usize], state: &TableState) -> TableView {
+    let total_rows = indices.len();
+    let (page, total_pages, mut range) = paginate_rows(total_rows, state.page, state.page_size);
+    if state.pagination_mode.appends() {
+        range.start = 0;
+    }
+
+    TableView {
+        indices: indices[range].to_vec(),
+        total_rows,
+        total_pages,
+        page,
+    }
+}
+
+/// Returns the indices of the rows matching the search input in `columns`, read according to
+/// `syntax` and compared as normalized by `options`.
+///
+/// Pass the output of [`search_columns`] to honour [`TableColumn::searchable`] and hidden
+/// search fields, as [`compute_view`] does.
+///
+/// With [`SearchSyntax::Plain`] this is [`filter_rows`]; with [`SearchSyntax::Structured`] the
+/// input is parsed as a [`Query`]. With [`SearchSyntax::Fuzzy`] the rows come back best match
+/// first, so that they are shown by relevance until [`sort_rows`] orders them by a column (rows
+/// the sort ties still keep their relevance order).
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{SearchOptions, SearchSyntax, search_rows};
+///
+/// let data = vec![
+///     hashmap! { "status" => "open".to_string(), "priority" => "1".to_string() },
+///     hashmap! { "status" => "open".to_string(), "priority" => "3".to_string() },
+///     hashmap! { "status" => "closed".to_string(), "priority" => "5".to_string() },
+/// ];
+/// let columns = ["status", "priority"];
+/// let search = |query, syntax| search_rows(&data, &columns, query, syntax, &SearchOptions::default());
+///
+/// let query = "status:open priority:>2";
+/// assert_eq!(search(query, SearchSyntax::Structured), vec![1]);
+/// assert!(search(query, SearchSyntax::Plain).is_empty());
+///
+/// // Swapped letters still find both open tickets.
+/// assert_eq!(search("opne", SearchSyntax::Fuzzy), vec![0, 1]);
+/// ```
+pub fn search_rows<T: TableRow, C: TableColumn>(
+    data: &[T],
+    columns: &[C],
+    query: &str,
+    syntax: SearchSyntax,
+    options: &SearchOptions,
+) -> Vec<usize> {
+    match syntax {
+        SearchSyntax::Plain => filter_rows(data, columns, query, options),
+        SearchSyntax::Structured => {
+            let query = Query::parse(query, options);
+            data.iter()
+                .enumerate()
+                .filter(|(_, row)| query.matches(*row, columns))
+                .map(|(idx, _)| idx)
+                .collect()
+        }
+        SearchSyntax::Fuzzy => {
+            let mut scored: Vec<_> = data
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, row)| Some((idx, fuzzy_score(row, columns, query, options)?)))
+                .collect();
+            scored.sort_by(|(_, a), (_, b)| b.cmp(a));
+            scored.into_iter().map(|(idx, _)| idx).collect()
+        }
+    }
+}
+
+/// Returns the indices of the rows where any column contains `query`, with both normalized by
+/// `options` (by default ignoring case and accents).
+///
+/// An empty query keeps every row.
+pub fn filter_rows<T: TableRow, C: TableColumn>(
+    data: &[T],
+    columns: &[C],
+    query: &str,
+    options: &SearchOptions,
+) -> Vec<usize> {
+    if query.is_empty() {
+        return (0..data.len()).collect();
+    }
+
+    let query = options.normalize(query);
+    data.iter()
+        .enumerate()
+        .filter(|(_, row)| {
+            columns.iter().any(|col| {
+                row.cell(col.id())
+                    .map(|v| options.normalize(&v).contains(&query))
+                    .unwrap_or(false)
+            })
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Sorts `indices` by the sort keys in priority order, each comparing the typed values of its
+/// column, as given by its [`ColumnKind`], or using its [`SortFn`] when it has one.
+///
+/// Later keys only break ties of earlier ones, and the sort is stable, so rows that tie on every
+/// key keep their relative order. Keys naming a column that is not in `columns` are ignored.
+/// Missing and empty cells sort first in ascending order.
+///
+/// [`ColumnKind`]: crate::core::ColumnKind
+/// [`SortFn`]: crate::core::SortFn
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{ColumnKind, SortOrder, TableColumn, sort_rows};
+///
+/// struct Age;
+///
+/// impl TableColumn for Age {
+///     fn id(&self) -> &'static str {
+///         "age"
+///     }
+///
+///     fn kind(&self) -> ColumnKind {
+///         ColumnKind::Integer
+///     }
+/// }
+///
+/// let data = vec![
+///     hashmap! { "age" => "10".to_string() },
+///     hashmap! { "age" => "9".to_string() },
+///     hashmap! { "age" => "".to_string() },
+/// ];
+///
+/// let mut indices = vec![0, 1, 2];
+/// sort_rows(&data, &[Age], &mut indices, &[("age", SortOrder::Asc)]);
+/// assert_eq!(indices, vec![2, 1, 0]);
+/// ```
+pub fn sort_rows<T: TableRow, C: TableColumn>(
+    data: &[T],
+    columns: &[C],
+    indices: &mut [usize],
+    sort: &[SortKey],
+) {
+    let keys = sort_keys(columns, sort);
+    if keys.is_empty() {
+        return;
+    }
+
+    indices.sort_by(|&a, &b| compare_rows(&data[a], &data[b], &keys));
+}
+
+/// A sort key resolved against its column: id, direction, kind and comparator.
+pub(crate) type ColumnSortKey = (&'static str, SortOrder, ColumnKind, Option<SortFn>);
+
+/// Resolves `sort` against `columns`, dropping the keys naming no column.
+pub(crate) fn sort_keys<C: TableColumn>(columns: &[C], sort: &[SortKey]) -> Vec<ColumnSortKey> {
+    sort.iter()
+        .filter_map(|&(col_id, order)| {
+            let column = columns.iter().find(|col| col.id() == col_id)?;
+            Some((col_id, order, column.kind(), column.sort_fn()))
+        })
+        .collect()
+}
+
+/// Compares two rows by `keys` in priority order, as [`sort_rows`] does.
+pub(crate) fn compare_rows<T: TableRow>(a: &T, b: &T, keys: &[ColumnSortKey]) -> Ordering {
+    keys.iter()
+        .map(|&(col_id, order, kind, sort_fn)| {
+            let ordering = match sort_fn {
+                Some(sort_fn) => sort_fn.compare(a, b, col_id, kind),
+                None => a.value(col_id, kind).compare(&b.value(col_id, kind)),
+            };
+            match order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Splits `total_rows` into pages and returns `(page, total_pages, range)`.
+///
+/// `page` is clamped to the last page and `range` is the slice of the filtered rows to show.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::paginate_rows;
+///
+/// assert_eq!(paginate_rows(25, 1, 10), (1, 3, 10..20));
+/// assert_eq!(paginate_rows(25, 7, 10), (2, 3, 20..25));
+/// assert_eq!(paginate_rows(0, 0, 10), (0, 1, 0..0));
+/// ```
+pub fn paginate_rows(
+    total_rows: usize,
+    page: usize,
+    page_size: usize,
+) -> (usize, usize, std::ops::Range<usize>) {
+    // Ensure page_size is at least 1 to prevent division by zero
+    let page_size = page_size.max(1);
+    // Ensure at least 1 page to avoid confusing 'Page 1 of 0' message when empty
+    let total_pages = total_rows.div_ceil(page_size).max(1);
+    // Clamp current page to valid range to prevent showing empty results
+    let page = page.min(total_pages - 1);
+    let start = (page * page_size).min(total_rows);
+    let end = (start + page_size).min(total_rows);
+
+    (page, total_pages, start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::column::ColumnDef;
+    use crate::core::state::PaginationMode;
+    use std::collections::HashMap;
+
+    type Row = HashMap<&'static str, String>;
+
+    fn rows(cells: &[&[(&'static str, &str)]]) -> Vec<Row> {
+        cells
+            .iter()
+            .map(|row| row.iter().map(|&(id, v)| (id, v.to_string())).collect())
+            .collect()
+    }
+
+    fn columns() -> [ColumnDef; 2] {
+        [
+            ColumnDef {
+                id: "name",
+                ..Default::default()
+            },
+            ColumnDef {
+                id: "age",
+                kind: ColumnKind::Integer,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn paginate_rows_treats_a_zero_page_size_as_one() {
+        assert_eq!(paginate_rows(3, 1, 0), (1, 3, 1..2));
+        assert_eq!(paginate_rows(0, 4, 0), (0, 1, 0..0));
+    }
+
+    #[test]
+    fn paginate_rows_fills_the_last_page_exactly() {
+        assert_eq!(paginate_rows(20, 1, 10), (1, 2, 10..20));
+        assert_eq!(paginate_rows(20, 2, 10), (1, 2, 10..20));
+        assert_eq!(paginate_rows(21, 2, 10), (2, 3, 20..21));
+    }
+
+    #[test]
+    fn paginate_rows_clamps_huge_pages_without_overflow() {
+        assert_eq!(paginate_rows(5, usize::MAX, 2), (2, 3, 4..5));
+        assert_eq!(paginate_rows(5, 0, usize::MAX), (0, 1, 0..5));
+    }
+
+    #[test]
+    fn page_view_clamps_a_page_past_the_end() {
+        let mut state = TableState::new(2);
+        state.page = 9;
+        let view = page_view(&[5, 4, 3], &state);
+        assert_eq!(view.indices, vec![3]);
+        assert_eq!((view.total_rows, view.total_pages, view.page), (3, 2, 1));
+
+        state.pagination_mode = PaginationMode::LoadMore;
+        assert_eq!(page_view(&[5, 4, 3], &state).indices, vec![5, 4, 3]);
+    }
+
+    #[test]
+    fn page_view_of_no_rows_is_one_empty_page() {
+        let mut state = TableState::new(10);
+        state.page = 3;
+        assert_eq!(
+            page_view(&[], &state),
+            TableView {
+                indices: Vec::new(),
+                total_rows: 0,
+                total_pages: 1,
+                page: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn compute_view_clamps_the_page_to_the_matching_rows() {
+        let data = rows(&[
+            &[("name", "Ferris"), ("age", "9")],
+            &[("name", "Crab"), ("age", "12")],
+            &[("name", "Ferros"), ("age", "10")],
+        ]);
+        let mut state = TableState::new(1);
+        state.page = 2;
+        assert_eq!(compute_view(&data, &columns(), &state).indices, vec![2]);
+
+        state.set_query("crab");
+        state.page = 2;
+        let view = compute_view(&data, &columns(), &state);
+        assert_eq!(view.indices, vec![1]);
+        assert_eq!((view.total_pages, view.page), (1, 0));
+    }
+
+    #[test]
+    fn sort_rows_keeps_the_data_order_of_ties() {
+        let data = rows(&[
+            &[("name", "b"), ("age", "1")],
+            &[("name", "a"), ("age", "2")],
+            &[("name", "c"), ("age", "1")],
+            &[("name", "d"), ("age", "2")],
+        ]);
+        let mut indices = vec![0, 1, 2, 3];
+        sort_rows(&data, &columns(), &mut indices, &[("age", SortOrder::Desc)]);
+        assert_eq!(indices, vec![1, 3, 0, 2]);
+
+        let mut indices = vec![3, 2, 1, 0];
+        sort_rows(&data, &columns(), &mut indices, &[("age", SortOrder::Asc)]);
+        assert_eq!(indices, vec![2, 0, 3, 1]);
+    }
+
+    #[test]
+    fn sort_rows_breaks_ties_with_later_keys() {
+        let data = rows(&[
+            &[("name", "b"), ("age", "1")],
+            &[("name", "a"), ("age", "2")],
+            &[("name", "a"), ("age", "1")],
+        ]);
+        let mut indices = vec![0, 1, 2];
+        let sort = [("name", SortOrder::Asc), ("age", SortOrder::Desc)];
+        sort_rows(&data, &columns(), &mut indices, &sort);
+        assert_eq!(indices, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn sort_rows_puts_missing_and_empty_cells_first_ascending() {
+        let data = rows(&[
+            &[("name", "a"), ("age", "3")],
+            &[("name", "b")],
+            &[("name", "c"), ("age", "")],
+            &[("name", "d"), ("age", "1")],
+        ]);
+        let mut indices = vec![0, 1, 2, 3];
+        sort_rows(&data, &columns(), &mut indices, &[("age", SortOrder::Asc)]);
+        assert_eq!(indices, vec![1, 2, 3, 0]);
+
+        sort_rows(&data, &columns(), &mut indices, &[("age", SortOrder::Desc)]);
+        assert_eq!(indices, vec![0, 3, 1, 2]);
+    }
+
+    #[test]
+    fn sort_rows_ignores_keys_naming_no_column() {
+        let data = rows(&[&[("name", "b")], &[("name", "a")]]);
+        let mut indices = vec![0, 1];
+        sort_rows(
+            &data,
+            &columns(),
+            &mut indices,
+            &[("email", SortOrder::Asc)],
+        );
+        assert_eq!(indices, vec![0, 1]);
+
+        let sort = [("email", SortOrder::Asc), ("name", SortOrder::Asc)];
+        sort_rows(&data, &columns(), &mut indices, &sort);
+        assert_eq!(indices, vec![1, 0]);
+    }
+}
@@ -0,0 +1,463 @@
+use crate::core::column::TableColumn;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// How tall the rows of a virtualized table are, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowHeight {
+    /// Every row has this height; nothing is measured.
+    Fixed(f64),
+
+    /// Rows are measured once rendered; rows that have not been rendered yet are assumed to be
+    /// `estimate` pixels tall.
+    Measured {
+        /// Height assumed for rows that have not been measured.
+        estimate: f64,
+    },
+}
+
+impl Default for RowHeight {
+    fn default() -> Self {
+        RowHeight::Fixed(36.0)
+    }
+}
+
+/// Settings for rendering only the rows inside the scroll viewport of a table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualScroll {
+    /// Height of the rows (default: fixed, `36` pixels).
+    pub row_height: RowHeight,
+
+    /// Height of the scrolling area around the table, in pixels (default: `480`).
+    pub viewport_height: f64,
+
+    /// Rows rendered above and below the viewport, so that fast scrolling does not reveal
+    /// blank space (default: `8`).
+    pub overscan: usize,
+}
+
+impl Default for VirtualScroll {
+    fn default() -> Self {
+        Self {
+            row_height: RowHeight::default(),
+            viewport_height: 480.0,
+            overscan: 8,
+        }
+    }
+}
+
+/// The rows of a virtualized table to render, and the height of the rows around them.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct VirtualWindow {
+    /// Positions of the rows to render.
+    pub range: Range<usize>,
+
+    /// Height of the rows before `range`, filled by a spacer row.
+    pub before: f64,
+
+    /// Height of the rows after `range`, filled by a spacer row.
+    pub after: f64,
+}
+
+/// Row heights of a virtualized table, used to map a scroll position to the rows to render.
+///
+/// Measured heights are kept in a Fenwick tree of their differences to the estimate, so offsets
+/// stay cheap to compute with millions of rows.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{RowHeight, RowHeights};
+///
+/// let fixed = RowHeights::new(RowHeight::Fixed(20.0));
+/// let window = fixed.window(1_000_000, 2_000.0, 100.0, 2);
+/// assert_eq!(window.range, 98..108);
+/// assert_eq!(window.before, 1_960.0);
+/// assert_eq!(window.after, 20_000_000.0 - 2_160.0);
+///
+/// let mut measured = RowHeights::new(RowHeight::Measured { estimate: 20.0 });
+/// assert!(measured.measure(0, 60.0));
+/// assert!(!measured.measure(0, 60.2));
+/// assert_eq!(measured.offset(2), 80.0);
+/// assert_eq!(measured.window(100, 70.0, 30.0, 0).range, 1..4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct RowHeights {
+    row_height: RowHeight,
+    measured: BTreeMap<usize, f64>,
+    /// Fenwick tree over the measured height minus the estimate of each row.
+    deltas: Vec<f64>,
+}
+
+impl RowHeights {
+    /// Creates heights for rows of `row_height`, with nothing measured yet.
+    pub fn new(row_height: RowHeight) -> Self {
+        Self {
+            row_height,
+            ..Default::default()
+        }
+    }
+
+    /// The kind of row height these heights were created for.
+    pub fn row_height(&self) -> RowHeight {
+        self.row_height
+    }
+
+    fn estimate(&self) -> f64 {
+        match self.row_height {
+            RowHeight::Fixed(height) | RowHeight::Measured { estimate: height } => height.max(1.0),
+        }
+    }
+
+    /// Records the rendered height of the row at position `row`.
+    ///
+    /// Returns `true` if the layout changed by half a pixel or more, in which case the window
+    /// should be recomputed. Fixed heights ignore measurements.
+    pub fn measure(&mut self, row: usize, height: f64) -> bool {
+        if matches!(self.row_height, RowHeight::Fixed(_)) || height.is_nan() || height <= 0.0 {
+            return false;
+        }
+        let previous = self.measured.get(&row).copied().unwrap_or(self.estimate());
+        if (height - previous).abs() < 0.5 {
+            return false;
+        }
+
+        if row >= self.deltas.len() {
+            self.grow(row + 1);
+        }
+        self.measured.insert(row, height);
+        let mut idx = row + 1;
+        while idx <= self.deltas.len() {
+            self.deltas[idx - 1] += height - previous;
+            idx += idx & idx.wrapping_neg();
+        }
+        true
+    }
+
+    /// Rebuilds the tree with room for at least `len` rows.
+    fn grow(&mut self, len: usize) {
+        let estimate = self.estimate();
+        self.deltas = vec![0.0; len.next_power_of_two()];
+        for (&row, &height) in &self.measured {
+            let mut idx = row + 1;
+            while idx <= self.deltas.len() {
+                self.deltas[idx - 1] += height - estimate;
+                idx += idx & idx.wrapping_neg();
+            }
+        }
+    }
+
+    /// Distance in pixels from the top of the first row to the top of the row at position `row`.
+    pub fn offset(&self, row: usize) -> f64 {
+        let mut adjustment = 0.0;
+        let mut idx = row.min(self.deltas.len());
+        while idx > 0 {
+            adjustment += self.deltas[idx - 1];
+            idx -= idx & idx.wrapping_neg();
+        }
+        row as f64 * self.estimate() + adjustment
+    }
+
+    /// Position of the row under the pixel `y`, or `count` past the last row.
+    fn row_at(&self, count: usize, y: f64) -> usize {
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.offset(mid + 1) <= y {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Returns the rows of a list of `count` rows to render when scrolled to `scroll_top` in a
+    /// viewport `viewport_height` pixels tall, with `overscan` extra rows on both sides.
+    pub fn window(
+        &self,
+        count: usize,
+        scroll_top: f64,
+        viewport_height: f64,
+        overscan: usize,
+    ) -> VirtualWindow {
+        let scroll_top = scroll_top.max(0.0);
+        let first = self.row_at(count, scroll_top);
+        let last = self.row_at(count, scroll_top + viewport_height.max(0.0));
+        let start = first.saturating_sub(overscan).min(count);
+        let end = last.saturating_add(overscan + 1).min(count);
+
+        VirtualWindow {
+            range: start..end,
+            before: self.offset(start),
+            after: self.offset(count) - self.offset(end),
+        }
+    }
+}
+
+/// Settings for rendering only the columns inside the horizontal viewport of a table.
+///
+/// Columns are laid out at their [`min_width`](TableColumn::min_width), or at `column_width`
+/// when it is `0`. [Pinned](TableColumn::pinned) columns are always rendered, before the others,
+/// and stick to the left edge.
+///
+/// # Examples
+/// ```rust
+/// use table_rs::core::{ColumnSlot, TableColumn, VirtualColumns};
+///
+/// struct Col(&'static str, bool);
+///
+/// impl TableColumn for Col {
+///     fn id(&self) -> &'static str {
+///         self.0
+///     }
+///
+///     fn pinned(&self) -> bool {
+///         self.1
+///     }
+/// }
+///
+/// let mut columns: Vec<Col> = (0..300).map(|_| Col("value", false)).collect();
+/// columns[5] = Col("name", true);
+///
+/// let settings = VirtualColumns { viewport_width: 500.0, column_width: 100.0, overscan: 1 };
+/// let window = settings.window(&columns, 1_000.0);
+/// assert_eq!(window.total_width, 30_000.0);
+/// assert_eq!(
+///     window.slots,
+///     vec![
+///         ColumnSlot::Column { index: 5, width: Some(100.0), left: Some(0.0) },
+///         ColumnSlot::Spacer(900.0),
+///         ColumnSlot::Column { index: 10, width: Some(100.0), left: None },
+///         ColumnSlot::Column { index: 11, width: Some(100.0), left: None },
+///         ColumnSlot::Column { index: 12, width: Some(100.0), left: None },
+///         ColumnSlot::Column { index: 13, width: Some(100.0), left: None },
+///         ColumnSlot::Column { index: 14, width: Some(100.0), left: None },
+///         ColumnSlot::Column { index: 15, width: Some(100.0), left: None },
+///         ColumnSlot::Spacer(28_400.0),
+///     ],
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualColumns {
+    /// Width of the scrolling area around the table, in pixels (default: `960`).
+    pub viewport_width: f64,
+
+    /// Width of the columns without a `min_width`, in pixels (default: `150`).
+    pub column_width: f64,
+
+    /// Columns rendered left and right of the viewport (default: `2`).
+    pub overscan: usize,
+}
+
+impl Default for VirtualColumns {
+    fn default() -> Self {
+        Self {
+            viewport_width: 960.0,
+            column_width: 150.0,
+            overscan: 2,
+        }
+    }
+}
+
+/// A cell to render in each row of a table whose columns may be virtualized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnSlot {
+    /// The column at `index`, `width` pixels wide when virtualized, and stuck `left` pixels from
+    /// the left edge when pinned.
+    Column {
+        /// Position of the column in the column definitions.
+        index: usize,
+        /// Width of the cell, if set.
+        width: Option<f64>,
+        /// Offset of a pinned cell from the left edge of the scrolling area.
+        left: Option<f64>,
+    },
+
+    /// An empty cell as wide as the columns it stands in for.
+    Spacer(f64),
+}
+
+impl ColumnSlot {
+    /// One slot per column, left to the browser's layout, for tables without column
+    /// virtualization.
+    pub fn all(count: usize) -> Vec<ColumnSlot> {
+        (0..count)
+            .map(|index| ColumnSlot::Column {
+                index,
+                width: None,
+                left: None,
+            })
+            .collect()
+    }
+
+    /// Inline style sizing the cell, and keeping it in place when pinned.
+    pub fn style(&self) -> String {
+        match *self {
+            ColumnSlot::Column {
+                width: Some(width),
+                left: Some(left),
+                ..
+            } => format!("width: {width}px; position: sticky; left: {left}px; z-index: 1;"),
+            ColumnSlot::Column {
+                width: Some(width), ..
+            } => format!("width: {width}px;"),
+            ColumnSlot::Column { .. } => String::new(),
+            ColumnSlot::Spacer(width) => format!("width: {width}px; padding: 0; border: 0;"),
+        }
+    }
+}
+
+/// The cells to render in each row of a table with virtualized columns.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ColumnWindow {
+    /// Cells of each row, in order: the pinned columns, then the columns in view between two
+    /// spacers.
+    pub slots: Vec<ColumnSlot>,
+
+    /// Width of all the columns together, in pixels.
+    pub total_width: f64,
+}
+
+impl VirtualColumns {
+    /// Width `column` is laid out at.
+    pub fn width<C: TableColumn>(&self, column: &C) -> f64 {
+        match column.min_width() {
+            0 => self.column_width.max(1.0),
+            width => f64::from(width),
+        }
+    }
+
+    /// Returns the cells to render when the table is scrolled `scroll_left` pixels sideways.
+    pub fn window<C: TableColumn>(&self, columns: &[C], scroll_left: f64) -> ColumnWindow {
+        let mut slots = Vec::new();
+        let mut pinned_width = 0.0;
+        let mut scrolling = Vec::new();
+        let mut offsets = vec![0.0];
+        for (index, column) in columns.iter().enumerate() {
+            let width = self.width(column);
+            if column.pinned() {
+                slots.push(ColumnSlot::Column {
+                    index,
+                    width: Some(width),
+                    left: Some(pinned_width),
+                });
+                pinned_width += width;
+            } else {
+                scrolling.push((index, width));
+                offsets.push(offsets[offsets.len() - 1] + width);
+            }
+        }
+
+        // Pinned columns cover the left of the viewport, so fewer scrolling columns show
+        let start = scroll_left.max(0.0);
+        let end = start + (self.viewport_width - pinned_width).max(0.0);
+        let first = offsets[1..].partition_point(|&right| right <= start);
+        let last = offsets[..scrolling.len()].partition_point(|&left| left < end);
+        let range_start = first.saturating_sub(self.overscan).min(scrolling.len());
+        let range_end = last
+            .saturating_add(self.overscan)
+            .clamp(range_start, scrolling.len());
+
+        let total = offsets[scrolling.len()];
+        if offsets[range_start] > 0.0 {
+            slots.push(ColumnSlot::Spacer(offsets[range_start]));
+        }
+        slots.extend(
+            scrolling[range_start..range_end]
+                .iter()
+                .map(|&(index, width)| ColumnSlot::Column {
+                    index,
+                    width: Some(width),
+                    left: None,
+                }),
+        );
+        if total > offsets[range_end] {
+            slots.push(ColumnSlot::Spacer(total - offsets[range_end]));
+        }
+
+        ColumnWindow {
+            slots,
+            total_width: pinned_width + total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEASURED: RowHeight = RowHeight::Measured { estimate: 20.0 };
+
+    /// Offsets summed row by row, to check the tree against.
+    fn naive_offset(heights: &BTreeMap<usize, f64>, row: usize) -> f64 {
+        (0..row)
+            .map(|row| heights.get(&row).copied().unwrap_or(20.0))
+            .sum()
+    }
+
+    #[test]
+    fn offsets_match_the_measured_heights_as_the_tree_grows() {
+        let mut heights = RowHeights::new(MEASURED);
+        let mut expected = BTreeMap::new();
+        for (row, height) in [
+            (3, 50.0),
+            (0, 10.0),
+            (17, 31.0),
+            (3, 40.0),
+            (200, 25.0),
+            (64, 5.0),
+        ] {
+            assert!(heights.measure(row, height));
+            expected.insert(row, height);
+            for row in [0, 1, 3, 4, 16, 18, 64, 65, 200, 201, 1_000] {
+                assert_eq!(
+                    heights.offset(row),
+                    naive_offset(&expected, row),
+                    "row {row}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn measuring_the_estimate_again_restores_the_offsets() {
+        let mut heights = RowHeights::new(MEASURED);
+        assert!(heights.measure(2, 100.0));
+        assert!(heights.measure(2, 20.0));
+        assert_eq!(heights.offset(10), 200.0);
+    }
+
+    #[test]
+    fn invalid_and_fixed_measurements_are_ignored() {
+        let mut measured = RowHeights::new(MEASURED);
+        assert!(!measured.measure(0, f64::NAN));
+        assert!(!measured.measure(0, 0.0));
+        assert!(!measured.measure(0, -5.0));
+        assert!(!measured.measure(0, 20.4));
+        assert_eq!(measured.offset(1), 20.0);
+
+        let mut fixed = RowHeights::new(RowHeight::Fixed(20.0));
+        assert!(!fixed.measure(0, 80.0));
+        assert_eq!(fixed.offset(1), 20.0);
+    }
+
+    #[test]
+    fn windows_of_no_rows_are_empty() {
+        let heights = RowHeights::new(MEASURED);
+        assert_eq!(heights.window(0, 500.0, 100.0, 3), VirtualWindow::default());
+    }
+
+    #[test]
+    fn windows_scrolled_past_the_end_keep_the_last_rows() {
+        let mut heights = RowHeights::new(MEASURED);
+        heights.measure(9, 100.0);
+        let window = heights.window(10, 10_000.0, 100.0, 2);
+        assert_eq!(window.range, 8..10);
+        assert_eq!(window.before, 160.0);
+        assert_eq!(window.after, 0.0);
+
+        let window = heights.window(10, -50.0, 50.0, 0);
+        assert_eq!(window.range, 0..3);
+        assert_eq!(window.before + heights.offset(3) + window.after, 280.0);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::core::column::{ColumnDef, TableColumn};
+use crate::core::filter::{ColumnFilter, FilterValue};
+use crate::core::normalize::SearchOptions;
+use crate::core::query::SearchSyntax;
+use crate::core::row::TableRow;
+use crate::core::sort::SortFn;
+use crate::core::state::{SortOrder, TableState};
+use crate::core::value::ColumnKind;
+use crate::core::view::{matching_rows, sort_rows};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// A copy of the cells a table searches, filters and sorts by, which can be sent to a Web Worker
+/// and ordered there with the same pipeline as on the main thread.
+///
+/// # Examples
+/// ```rust
+/// use maplit::hashmap;
+/// use table_rs::core::{
+///     ColumnDef, ColumnKind, SortCycle, TableState, ViewData, WorkerRequest, compute_view,
+/// };
+///
+/// let data = vec![
+///     hashmap! { "name" => "Ferris".to_string(), "age" => "9".to_string() },
+///     hashmap! { "name" => "Crab".to_string(), "age" => "12".to_string() },
+///     hashmap! { "name" => "Ferros".to_string(), "age" => "10".to_string() },
+/// ];
+/// let columns = [
+///     ColumnDef { id: "name", ..Default::default() },
+///     ColumnDef { id: "age", kind: ColumnKind::Integer, ..Default::default() },
+/// ];
+///
+/// let mut state = TableState::new(10);
+/// state.set_query("fer");
+/// state.toggle_sort("age", false, SortCycle::default());
+///
+/// // What the worker receives is what the table would have computed itself.
+/// let load = WorkerRequest::Load(ViewData::new(&data, &columns, &[])).encode();
+/// let Some(WorkerRequest::Load(copy)) = WorkerRequest::decode(&load) else {
+///     panic!("a load request");
+/// };
+/// let view = WorkerRequest::View { id: 7, state: state.clone() }.encode();
+/// let Some(WorkerRequest::View { id: 7, state: received }) = WorkerRequest::decode(&view) else {
+///     panic!("a view request");
+/// };
+/// assert_eq!(copy.sorted_rows(&received), vec![0, 2]);
+/// assert_eq!(compute_view(&data, &columns, &state).indices, vec![0, 2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewData {
+    columns: Vec<ColumnDef>,
+    rows: Vec<ViewRow>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ViewRow {
+    ids: Rc<[&'static str]>,
+    cells: Box<[Option<String>]>,
+}
+
+impl TableRow for ViewRow {
+    fn cell(&self, id: &str) -> Option<Cow<'_, str>> {
+        let position = self.ids.iter().position(|&key| key == id)?;
+        self.cells[position].as_deref().map(Cow::Borrowed)
+    }
+}
+
+impl ViewData {
+    /// Copies the cells of `columns` and of the hidden `search_fields` out of `data`.
+    pub fn new<T: TableRow, C: TableColumn>(
+        data: &[T],
+        columns: &[C],
+        search_fields: &[&'static str],
+    ) -> Self {
+        let columns: Vec<ColumnDef> = columns
+            .iter()
+            .map(|col| ColumnDef {
+                id: col.id(),
+                kind: col.kind(),
+                sort_fn: col.sort_fn(),
+                filter: col.filter(),
+                searchable: col.searchable(),
+                ..Default::default()
+            })
+            .collect();
+        let mut ids: Vec<&'static str> = columns.iter().map(|col| col.id).collect();
+        for &id in search_fields {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        let ids: Rc<[&'static str]> = ids.into();
+        let rows = data
+            .iter()
+            .map(|row| ViewRow {
+                ids: Rc::clone(&ids),
+                cells: ids
+                    .iter()
+                    .map(|id| row.cell(id).map(Cow::into_owned))
+                    .collect(),
+            })
+            .collect();
+        Self { columns, rows }
+    }
+
+    /// Returns `true` if `columns` can be sorted away from the main thread: custom
+    /// [`SortFn::Values`] and [`SortFn::Rows`] comparators are code, which cannot be sent.
+    pub fn supports<C: TableColumn>(columns: &[C]) -> bool {
+        columns
+            .iter()
+            .all(|col| matches!(col.sort_fn(), None | Some(SortFn::Natural)))
+    }
+
+    /// Number of rows copied.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if no row was copied.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the indices of the rows matching `state`, in display order: [`matching_rows`]
+    /// followed by [`sort_rows`], ready to be paged through.
+    pub fn sorted_rows(&self, state: &TableState) -> Vec<usize> {
+        let mut indices = matching_rows(&self.rows, &self.columns, state);
+        sort_rows(&self.rows, &self.columns, &mut indices, &state.sort);
+        indices
+    }
+}
+
+/// A message from the table to its Web Worker, encoded as text so that it crosses to the worker
+/// as a single string.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkerRequest {
+    /// Replaces the rows the worker orders.
+    Load(ViewData),
+
+    /// Asks for [`ViewData::sorted_rows`]; the reply carries the same `id`.
+    View {
+        /// Number identifying the request.
+        id: u64,
+
+        /// Search, filters and sort to apply; the paging fields are ignored.
+        state: TableState,
+    },
+}
+
+const KINDS: [ColumnKind; 6] = [
+    ColumnKind::Text,
+    ColumnKind::Integer,
+    ColumnKind::Float,
+    ColumnKind::Bool,
+    ColumnKind::Date,
+    ColumnKind::DateTime,
+];
+const SYNTAXES: [SearchSyntax; 3] = [
+    SearchSyntax::Plain,
+    SearchSyntax::Structured,
+    SearchSyntax::Fuzzy,
+];
+
+impl WorkerRequest {
+    /// Encodes the request; every string is written with its length, so cells may hold any text.
+    pub fn encode(&self) -> String {
+        let mut out = String::new();
+        match self {
+            WorkerRequest::Load(data) => {
+                put(&mut out, "load");
+                put(&mut out, data.columns.len());
+                for col in &data.columns {
+                    put(&mut out, col.id);
+                    put(&mut out, position(&KINDS, col.kind));
+                    put(&mut out, filter_code(col.filter));
+                    put(&mut out, u8::from(col.searchable));
+                    put(&mut out, u8::from(col.sort_fn == Some(SortFn::Natural)));
+                }
+                let ids = data
+                    .rows
+                    .first()
+                    .map(|row| &row.ids[..])
+                    .unwrap_or_default();
+                put(&mut out, ids.len());
+                for id in ids {
+                    put(&mut out, id);
+                }
+                put(&mut out, data.rows.len());
+                for row in &data.rows {
+                    for cell in &row.cells {
+                        match cell {
+                            Some(cell) => put(&mut out, cell),
+                            None => out.push('~'),
+                        }
+                    }
+                }
+            }
+            WorkerRequest::View { id, state } => {
+                put(&mut out, "view");
+                put(&mut out, id);
+                put(&mut out, &state.query);
+                put(&mut out, position(&SYNTAXES, state.search_syntax));
+                let options = state.search_options;
+                for flag in [
+                    options.ignore_case,
+                    options.ignore_diacritics,
+                    options.ignore_punctuation,
+                ] {
+                    put(&mut out, u8::from(flag));
+                }
+                put(&mut out, state.search_fields.len());
+                for field in &state.search_fields {
+                    put(&mut out, field);
+                }
+                put(&mut out, state.filters.len());
+                for (id, value) in &state.filters {
+                    put(&mut out, id);
+                    match value {
+                        FilterValue::Text(text) => {
+                            put(&mut out, "text");
+                            put(&mut out, text);
+                        }
+                        FilterValue::Range { min, max } => {
+                            put(&mut out, "range");
+                            put(&mut out, min);
+                            put(&mut out, max);
+                        }
+                    }
+                }
+                put(&mut out, state.sort.len());
+                for (id, order) in &state.sort {
+                    put(&mut out, id);
+                    put(&mut out, order.aria_sort());
+                }
+            }
+        }
+        out
+    }
+
+    /// Decodes a request written by [`encode`](Self::encode), or returns `None` if `text` is
+    /// not one.
+    pub fn decode(text: &str) -> Option<Self> {
+        let mut reader = Reader(text);
+        let request = match reader.text()? {
+            "load" => {
+                let mut columns = Vec::new();
+                for _ in 0..reader.number::<usize>()? {
+                    columns.push(ColumnDef {
+                        id: intern(reader.text()?),
+                        kind: *KINDS.get(reader.number::<usize>()?)?,
+                        filter: filter_from_code(reader.text()?)?,
+                        searchable: reader.flag()?,
+                        sort_fn: reader.flag()?.then_some(SortFn::Natural),
+                        ..Default::default()
+                    });
+                }
+                let mut ids = Vec::new();
+                for _ in 0..reader.number::<usize>()? {
+                    ids.push(intern(reader.text()?));
+                }
+                let ids: Rc<[&'static str]> = ids.into();
+                let mut rows = Vec::new();
+                for _ in 0..reader.number::<usize>()? {
+                    let cells = (0..ids.len())
+                        .map(|_| Some(reader.cell()?.map(str::to_string)))
+                        .collect::<Option<_>>()?;
+                    rows.push(ViewRow {
+                        ids: Rc::clone(&ids),
+                        cells,
+                    });
+                }
+                WorkerRequest::Load(ViewData { columns, rows })
+            }
+            "view" => {
+                let id = reader.number()?;
+                let mut state = TableState {
+                    query: reader.text()?.to_string(),
+                    search_syntax: *SYNTAXES.get(reader.number::<usize>()?)?,
+                    search_options: SearchOptions {
+                        ignore_case: reader.flag()?,
+                        ignore_diacritics: reader.flag()?,
+                        ignore_punctuation: reader.flag()?,
+                    },
+                    ..Default::default()
+                };
+                for _ in 0..reader.number::<usize>()? {
+                    state.search_fields.push(intern(reader.text()?));
+                }
+                for _ in 0..reader.number::<usize>()? {
+                    let id = intern(reader.text()?);
+                    let value = match reader.text()? {
+                        "text" => FilterValue::Text(reader.text()?.to_string()),
+                        "range" => FilterValue::Range {
+                            min: reader.text()?.to_string(),
+                            max: reader.text()?.to_string(),
+                        },
+                        _ => return None,
+                    };
+                    state.filters.insert(id, value);
+                }
+                for _ in 0..reader.number::<usize>()? {
+                    let id = intern(reader.text()?);
+                    let order = match reader.text()? {
+                        "ascending" => SortOrder::Asc,
+                        "descending" => SortOrder::Desc,
+                        _ => return None,
+                    };
+                    state.sort.push((id, order));
+                }
+                WorkerRequest::View { id, state }
+            }
+            _ => return None,
+        };
+        reader.0.is_empty().then_some(request)
+    }
+}
+
+fn put(out: &mut String, value: impl fmt::Display) {
+    let text = value.to_string();
+    let _ = write!(out, "{}:{text}", text.len());
+}
+
+fn position<T: PartialEq>(values: &[T], value: T) -> usize {
+    values.iter().position(|v| *v == value).unwrap_or_default()
+}
+
+fn filter_code(filter: Option<ColumnFilter>) -> &'static str {
+    match filter {
+        None => "none",
+        Some(ColumnFilter::Text) => "text",
+        Some(ColumnFilter::Equals) => "equals",
+        Some(ColumnFilter::Select(_)) => "select",
+        Some(ColumnFilter::NumberRange) => "number",
+        Some(ColumnFilter::DateRange) => "date",
+    }
+}
+
+/// The options of a select filter are only needed to render it, so they are not sent.
+fn filter_from_code(code: &str) -> Option<Option<ColumnFilter>> {
+    Some(match code {
+        "none" => None,
+        "text" => Some(ColumnFilter::Text),
+        "equals" => Some(ColumnFilter::Equals),
+        "select" => Some(ColumnFilter::Select(&[])),
+        "number" => Some(ColumnFilter::NumberRange),
+        "date" => Some(ColumnFilter::DateRange),
+        _ => return None,
+    })
+}
+
+/// Column ids are `&'static str` throughout the engine; the worker keeps one copy of each id it
+/// receives for as long as it runs.
+fn intern(id: &str) -> &'static str {
+    thread_local! {
+        static IDS: RefCell<HashSet<&'static str>> = RefCell::default();
+    }
+    IDS.with_borrow_mut(|ids| {
+        if let Some(&id) = ids.get(id) {
+            return id;
+        }
+        let id: &'static str = Box::leak(id.into());
+        ids.insert(id);
+        id
+    })
+}
+
+/// Reads the length-prefixed strings written by [`put`].
+struct Reader<'a>(&'a str);
+
+impl<'a> Reader<'a> {
+    fn text(&mut self) -> Option<&'a str> {
+        let (len, rest) = self.0.split_once(':')?;
+        let len = len.parse().ok()?;
+        let text = rest.get(..len)?;
+        self.0 = &rest[len..];
+        Some(text)
+    }
+
+    fn cell(&mut self) -> Option<Option<&'a str>> {
+        match self.0.strip_prefix('~') {
+            Some(rest) => {
+                self.0 = rest;
+                Some(None)
+            }
+            None => self.text().map(Some),
+        }
+    }
+
+    fn number<N: FromStr>(&mut self) -> Option<N> {
+        self.text()?.parse().ok()
+    }
+
+    fn flag(&mut self) -> Option<bool> {
+        Some(self.number::<u8>()? != 0)
+    }
+}
@@ -1,8 +1,13 @@
+use crate::core::{SearchOptions, TableRow, fuzzy_highlight};
 use crate::dioxus::types::Column;
+use crate::dioxus::types::PaginationMode;
 use crate::dioxus::types::TableClasses;
 use crate::dioxus::types::TableTexts;
+use crate::dioxus::types::VirtualWindow;
+use crate::dioxus::types::{ColumnSlot, ColumnWindow};
+use crate::dioxus::types::{Selection, SelectionMode};
 use dioxus::prelude::*;
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 
 /// A table body component that renders rows of data, along with loading and empty states.
 ///
@@ -11,15 +16,39 @@ use std::collections::HashMap;
 ///
 /// # Props
 /// - `columns`: A `Vec<Column>` defining which fields to render in each table row. Each column corresponds to a key in the row data.
-/// - `rows`: A `Vec<HashMap<&'static str, String>>` representing the data for each row, where keys match column IDs.
+/// - `rows`: A `ReadSignal<Vec<T>>` (or a plain `Vec<T>`) of rows implementing `TableRow` (e.g. `HashMap<&'static str, String>`), whose cells are looked up by column ID; when virtualized, only the rows of `virtual_window`.
 /// - `loading`: A `bool` flag that, when true, displays a loading message instead of data rows.
+/// - `error`: An optional error message; when set, an error row with a retry button replaces the rows (default: `None`).
+/// - `on_retry`: An `EventHandler<()>` called when the retry button is clicked (default: no-op).
+/// - `pagination_mode`: A `PaginationMode`; the appending modes keep the rows visible while more load (default: `Pages`).
+/// - `has_more`: A `bool` telling whether more rows can be loaded after the ones shown (default: `false`).
+/// - `on_load_more`: An `EventHandler<()>` called when the sentinel row of `InfiniteScroll` becomes visible (default: no-op).
 /// - `classes`: A `TableClasses` struct for customizing the CSS class names of the body, rows, and cells.
 /// - `texts`: A `TableTexts` struct that provides custom text for the loading and empty states.
+/// - `highlight`: A fuzzy search query whose matched characters are wrapped in `<mark>`, unless the column has a custom `cell` renderer (default: empty).
+/// - `search_options`: The `SearchOptions` used to match `highlight` (default: `SearchOptions::default()`).
+/// - `virtual_window`: An optional `VirtualWindow` whose range places `rows` among all the rows, with spacer rows of the given heights above and below them (default: `None`).
+/// - `on_measure`: An optional `EventHandler<(usize, f64)>` called with the position and height of each row once it is mounted (default: `None`).
+/// - `column_window`: An optional `ColumnWindow` restricting each row to the cells of the pinned columns and those in view, between spacer cells (default: `None`).
+/// - `selection`: A `SelectionMode`; unless `None`, each row starts with a checkbox cell (default: `None`).
+/// - `row_keys`: A `Vec<String>` holding the key of each row of `rows`, used as the key of its `<tr>`; rows without one are keyed by position (default: empty).
+/// - `selected`: The `Selection` of rows, which get `aria-selected="true"` and the `selected_row` class (default: none selected).
+/// - `on_select`: An `EventHandler<(String, bool)>` called by the checkbox of a row with its key and whether shift was held (default: no-op).
+/// - `expandable`: A `bool` starting each row with a button that shows or hides its detail panel, with `aria-expanded` telling which (default: `false`).
+/// - `expanded`: A `BTreeSet<String>` of the keys of the expanded rows (default: empty).
+/// - `on_expand`: An `EventHandler<String>` called by the expand button of a row with its key (default: no-op).
+/// - `render_detail`: An optional `Callback<T, Element>` rendering the detail panel of an expanded row, shown in a full-width row beneath it (default: `None`).
 ///
 /// # Behavior
 /// - If `loading` is `true`, a single row with a loading message is shown spanning all columns.
+/// - If `error` is set and not loading, an error row with a retry button is displayed.
 /// - If `rows` is empty and not loading, an empty message row is displayed.
-/// - Otherwise, each data row is rendered in a `<tr>`, with one `<td>` per column.
+/// - Otherwise, each data row is rendered in a `<tr>`, with one `<td>` per column and its position in `data-row`.
+/// - When rows can be selected, each row starts with a checkbox cell, and the status rows span it too.
+/// - Each expanded row is followed by a row holding its detail panel.
+/// - When virtualized, spacer rows above and below the rendered rows keep the scrollbar in proportion.
+/// - In the appending modes, the loading or error row follows the rows instead of replacing them, and
+///   with `InfiniteScroll` a sentinel row after them calls `on_load_more` when it scrolls into view.
 ///
 /// # Returns
 /// A Dioxus `Element` representing the `<tbody>` of a table, with dynamic row content.
@@ -59,68 +88,247 @@ use std::collections::HashMap;
 /// # See Also
 /// - [MDN `<tbody>` Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/tbody)
 #[component]
-pub fn TableBody(
+pub fn TableBody<T: TableRow + Clone + PartialEq + 'static>(
     columns: Vec<Column>,
-    rows: Vec<HashMap<&'static str, String>>,
+    rows: ReadSignal<Vec<T>>,
     loading: bool,
+    #[props(default)] error: Option<String>,
+    #[props(default)] on_retry: EventHandler<()>,
+    #[props(default)] pagination_mode: PaginationMode,
+    #[props(default)] has_more: bool,
+    #[props(default)] on_load_more: EventHandler<()>,
     classes: TableClasses,
     texts: TableTexts,
+    #[props(default)] highlight: String,
+    #[props(default)] search_options: SearchOptions,
+    #[props(default)] virtual_window: Option<VirtualWindow>,
+    #[props(default)] on_measure: Option<EventHandler<(usize, f64)>>,
+    #[props(default)] column_window: Option<ColumnWindow>,
+    #[props(default)] selection: SelectionMode,
+    #[props(default)] row_keys: Vec<String>,
+    #[props(default)] selected: Selection,
+    #[props(default)] on_select: EventHandler<(String, bool)>,
+    #[props(default)] expandable: bool,
+    #[props(default)] expanded: BTreeSet<String>,
+    #[props(default)] on_expand: EventHandler<String>,
+    #[props(default)] render_detail: Option<Callback<T, Element>>,
 ) -> Element {
-    let content = if loading {
+    let slots = column_window.map_or_else(|| ColumnSlot::all(columns.len()), |window| window.slots);
+    let colspan = slots.len() + usize::from(expandable) + usize::from(selection.is_enabled());
+    let rows = rows.read();
+    // In the appending modes the rows stay put while the next page loads or fails below them
+    let appending = pagination_mode.appends() && !rows.is_empty();
+    let status = if loading {
         rsx! {
             tr { class: "{classes.loading_row}",
                 td {
-                    colspan: "{columns.len()}",
+                    colspan: "{colspan}",
                     "{texts.loading}"
                 }
             }
         }
+    } else if error.is_some() {
+        rsx! {
+            tr { class: "{classes.error_row}", role: "alert",
+                td {
+                    colspan: "{colspan}",
+                    "{texts.error}"
+                    button {
+                        class: "{classes.retry_button}",
+                        r#type: "button",
+                        onclick: move |_| on_retry.call(()),
+                        "{texts.retry}"
+                    }
+                }
+            }
+        }
     } else if rows.is_empty() {
         rsx! {
             tr { class: "{classes.empty_row}",
                 td {
-                    colspan: "{columns.len()}",
+                    colspan: "{colspan}",
                     "{texts.empty}"
                 }
             }
         }
     } else {
+        rsx! {}
+    };
+    let show_rows = appending || (!loading && error.is_none() && !rows.is_empty());
+    // Keyed by the row count, so that the sentinel is observed afresh after each page and keeps
+    // loading while it is still in view
+    let sentinel = (appending
+        && pagination_mode == PaginationMode::InfiniteScroll
+        && has_more
+        && !loading
+        && error.is_none())
+    .then_some(rows.len());
+
+    let first = virtual_window
+        .as_ref()
+        .map_or(0, |window| window.range.start);
+    let spacer = |height: f64| {
+        if show_rows && height > 0.0 {
+            rsx! {
+                tr {
+                    class: "{classes.spacer_row}",
+                    aria_hidden: "true",
+                    style: "height: {height}px;",
+                    td { colspan: "{colspan}", style: "padding: 0; border: 0;" }
+                }
+            }
+        } else {
+            rsx! {}
+        }
+    };
+    let before = spacer(virtual_window.as_ref().map_or(0.0, |window| window.before));
+    let after = spacer(virtual_window.as_ref().map_or(0.0, |window| window.after));
+
+    let slot_columns: Vec<(ColumnSlot, Option<&Column>)> = slots
+        .iter()
+        .map(|slot| match *slot {
+            ColumnSlot::Column { index, .. } => (*slot, columns.get(index)),
+            ColumnSlot::Spacer(_) => (*slot, None),
+        })
+        .collect();
+
+    let keyed_rows = rows.iter().enumerate().map(|(idx, row)| {
+        let position = first + idx;
+        let key = row_keys
+            .get(idx)
+            .cloned()
+            .unwrap_or_else(|| position.to_string());
+        let checked = selection.is_enabled() && selected.contains(&key);
+        let is_expanded = expandable && expanded.contains(&key);
+        let detail = render_detail
+            .filter(|_| is_expanded)
+            .map(|render| render.call(row.clone()));
+        (position, row, key, checked, is_expanded, detail)
+    });
+
+    let content = if show_rows {
         rsx! {
-            for (idx , row) in rows.iter().enumerate() {
-                tr { key: "{idx}", class: "{classes.row}", role: "row",
-                    for col in columns.iter() {
-                        {
-                            #[cfg(debug_assertions)]
-                            if !row.contains_key(col.id) {
-                                web_sys::console::warn_1(&format!("Missing column '{}' in row data", col.id).into());
+            for (position , row , key , checked , is_expanded , detail) in keyed_rows {
+                tr {
+                    key: "{key}",
+                    class: if checked { "{classes.row} {classes.selected_row}" } else { "{classes.row}" },
+                    role: "row",
+                    "data-row": "{position}",
+                    aria_selected: if selection.is_enabled() { "{checked}" },
+                    onmounted: move |evt: MountedEvent| async move {
+                        let Some(on_measure) = on_measure else {
+                            return;
+                        };
+                        if let Ok(rect) = evt.get_client_rect().await {
+                            on_measure.call((position, rect.height()));
+                        }
+                    },
+                    if expandable {
+                        td { class: "{classes.expand_cell}", role: "cell",
+                            button {
+                                class: "{classes.expand_button}",
+                                r#type: "button",
+                                aria_expanded: "{is_expanded}",
+                                aria_label: "{texts.expand_row}",
+                                onclick: {
+                                    let key = key.clone();
+                                    move |_| on_expand.call(key.clone())
+                                },
+                                if is_expanded { "▾" } else { "▸" }
+                            }
+                        }
+                    }
+                    if selection.is_enabled() {
+                        td { class: "{classes.select_cell}", role: "cell",
+                            input {
+                                r#type: "checkbox",
+                                checked: checked,
+                                aria_label: "{texts.select_row}",
+                                onclick: move |evt: MouseEvent| {
+                                    on_select.call((key.clone(), evt.modifiers().contains(Modifiers::SHIFT)))
+                                },
                             }
                         }
-                        td { class: "{classes.body_cell}", role: "cell",
-                            BodyCell {
-                                column: col.clone(),
-                                content: row.get(col.id).unwrap_or(&String::new()),
+                    }
+                    for (slot , column) in slot_columns.iter() {
+                        if let Some(col) = column {
+                            {
+                                #[cfg(debug_assertions)]
+                                if row.cell(col.id).is_none() {
+                                    web_sys::console::warn_1(&format!("Missing column '{}' in row data", col.id).into());
+                                }
                             }
+                            td { class: "{classes.body_cell}", role: "cell", style: "{slot.style()}",
+                                BodyCell {
+                                    column: (*col).clone(),
+                                    content: row.cell(col.id).unwrap_or_default().into_owned(),
+                                    highlight: if col.searchable { highlight.clone() } else { String::new() },
+                                    search_options: search_options,
+                                    mark_class: classes.search_highlight,
+                                }
+                            }
+                        } else {
+                            td { aria_hidden: "true", style: "{slot.style()}" }
                         }
                     }
                 }
+                if let Some(detail) = detail {
+                    tr { class: "{classes.detail_row}", role: "row",
+                        td { colspan: "{colspan}", role: "cell", {detail} }
+                    }
+                }
             }
         }
+    } else {
+        rsx! {}
     };
 
     rsx! {
         tbody { class: "{classes.tbody}",
+            {before}
             {content}
+            {after}
+            {status}
+            for count in sentinel {
+                tr {
+                    key: "sentinel-{count}",
+                    class: "{classes.sentinel_row}",
+                    aria_hidden: "true",
+                    onvisible: move |evt: VisibleEvent| {
+                        if evt.is_intersecting().unwrap_or(false) {
+                            on_load_more.call(());
+                        }
+                    },
+                    td { colspan: "{colspan}" }
+                }
+            }
         }
     }
 }
 
 #[component]
-fn BodyCell(column: Column, content: String) -> Element {
+fn BodyCell(
+    column: Column,
+    content: String,
+    highlight: String,
+    search_options: SearchOptions,
+    mark_class: &'static str,
+) -> Element {
     if let Some(cb) = column.cell {
         cb(content)
-    } else {
+    } else if highlight.trim().is_empty() {
         rsx! {
             "{content}"
         }
+    } else {
+        rsx! {
+            for (segment , matched) in fuzzy_highlight(&content, &highlight, &search_options) {
+                if matched {
+                    mark { class: "{mark_class}", "{segment}" }
+                } else {
+                    "{segment}"
+                }
+            }
+        }
     }
 }
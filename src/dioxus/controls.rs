@@ -1,3 +1,4 @@
+use crate::dioxus::types::PaginationMode;
 use crate::dioxus::types::TableClasses;
 use crate::dioxus::types::TableTexts;
 use dioxus::prelude::*;
@@ -8,6 +9,8 @@ pub fn PaginationControls(
     total_pages: usize,
     classes: TableClasses,
     texts: TableTexts,
+    #[props(default)] pagination_mode: PaginationMode,
+    #[props(default)] on_load_more: Option<EventHandler<()>>,
 ) -> Element {
     let on_prev = move |_| {
         if page() > 0 {
@@ -21,6 +24,29 @@ pub fn PaginationControls(
         }
     };
 
+    match pagination_mode {
+        PaginationMode::Pages => {}
+        PaginationMode::LoadMore => {
+            let on_load_more = move |_| match on_load_more {
+                Some(handler) => handler.call(()),
+                None if page() + 1 < total_pages => page.set(page() + 1),
+                None => {}
+            };
+            return rsx! {
+                div { class: classes.pagination,
+                    button {
+                        class: classes.load_more_button,
+                        onclick: on_load_more,
+                        disabled: page() + 1 >= total_pages,
+                        "{texts.load_more}"
+                    }
+                }
+            };
+        }
+        // The sentinel row at the end of the body loads the next page
+        PaginationMode::InfiniteScroll => return rsx! {},
+    }
+
     // Pre-compute page indicator to avoid multiple string allocations
     let page_indicator_text = texts
         .page_indicator
@@ -1,7 +1,14 @@
+use crate::core::sort_key_position;
 use crate::dioxus::types::Column;
-use crate::dioxus::types::SortOrder;
+use crate::dioxus::types::SortKey;
 use crate::dioxus::types::TableClasses;
+use crate::dioxus::types::{CheckState, SelectionMode};
+use crate::dioxus::types::{ColumnFilter, FilterValue, TableTexts};
+use crate::dioxus::types::{ColumnSlot, ColumnWindow};
 use dioxus::prelude::*;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use web_sys::wasm_bindgen::JsCast;
 
 /// A table header component that renders sortable column headers for use within the `Table` component.
 ///
@@ -10,14 +17,26 @@ use dioxus::prelude::*;
 ///
 /// # Props
 /// - `columns`: A `Vec<Column>` defining the columns to display in the header. Each `Column` may be sortable and have optional styles or class overrides.
-/// - `sort_column`: A `Signal<Option<&'static str>>` indicating which column (if any) is currently being sorted.
-/// - `sort_order`: A `Signal<SortOrder>` indicating the current sort direction (`Asc` or `Desc`).
-/// - `on_sort_column`: An `EventHandler<&'static str>` triggered when a sortable header cell is clicked. The column ID is passed as the event payload.
+/// - `sort`: A `Signal<Vec<SortKey>>` of the sorted columns and their directions, highest priority first.
+/// - `on_sort_column`: An `EventHandler<(&'static str, bool)>` triggered when a sortable header cell is clicked. The column ID and whether shift was held (to add a secondary sort key) are passed as the event payload.
+/// - `filters`: A `Signal<BTreeMap<&'static str, FilterValue>>` of the current filter inputs by column id.
+/// - `on_filter`: An `EventHandler<(&'static str, FilterValue)>` triggered when a filter input changes.
 /// - `classes`: A `TableClasses` struct allowing custom class names for `<thead>`, `<tr>`, and `<th>` elements.
+/// - `texts`: A `TableTexts` struct providing the filter row labels (default: `TableTexts::default()`).
+/// - `sticky`: A `bool` keeping the header at the top of the scrolling area while rows scroll under it (default: `false`).
+/// - `column_window`: An optional `ColumnWindow` restricting the header and filter cells to the pinned columns and those in view, between spacer cells (default: `None`).
+/// - `selection`: A `SelectionMode`; unless `None`, a cell is added before the headers, holding a "select all" checkbox in `Multiple` mode (default: `None`).
+/// - `select_all`: The `CheckState` of the "select all" checkbox, shown as indeterminate when only some rows are selected (default: `Unchecked`).
+/// - `on_select_all`: An `EventHandler<bool>` triggered by the "select all" checkbox, with whether to select the rows it covers (default: no-op).
+/// - `expandable`: A `bool` adding a first cell with an "expand all" button, whose `aria-expanded` tells whether every row of the page is expanded (default: `false`).
+/// - `all_expanded`: A `bool` telling whether every row of the page is expanded (default: `false`).
+/// - `on_expand_all`: An `EventHandler<bool>` triggered by the "expand all" button, with whether to expand the rows of the page (default: no-op).
 ///
 /// # Behavior
-/// - Sortable columns show proper `aria-sort` attributes for accessibility (`ascending`, `descending`, or `none`).
-/// - Clicking a sortable column emits an event to update sort state.
+/// - The primary sort column shows the proper `aria-sort` attribute for accessibility (`ascending` or `descending`); every other column has `none`, as ARIA recommends a single sorted header.
+/// - Clicking a sortable column emits an event to update sort state; shift-clicking adds or toggles a secondary sort key.
+/// - When sorting by several columns, each sorted header shows its priority in a badge.
+/// - Columns with a `filter` get a matching input (text box, drop-down, or min/max pair) in a filter row under the headers.
 /// - Each column can override default styles and classes via `Column::style` and `Column::class`.
 ///
 /// # Returns
@@ -27,6 +46,7 @@ use dioxus::prelude::*;
 /// ```rust
 /// use dioxus::prelude::*;
 /// use maplit::hashmap;
+/// use std::collections::BTreeMap;
 /// use table_rs::dioxus::table::Table;
 /// use table_rs::dioxus::types::{Column, TableClasses, SortOrder};
 /// use table_rs::dioxus::header::TableHeader;
@@ -38,15 +58,16 @@ use dioxus::prelude::*;
 ///         Column { id: "email", header: "Email", sortable: false, ..Default::default() },
 ///     ];
 ///
-///     let sort_column = use_signal(|| Some("name"));
-///     let sort_order = use_signal(|| SortOrder::Asc);
+///     let sort = use_signal(|| vec![("name", SortOrder::Asc)]);
+///     let filters = use_signal(BTreeMap::new);
 ///
 ///     rsx! {
 ///         TableHeader {
 ///             columns: columns,
-///             sort_column: sort_column,
-///             sort_order: sort_order,
-///             on_sort_column: move |col_id| println!("Sort column changed: {}", col_id),
+///             sort: sort,
+///             on_sort_column: move |(col_id, additive)| println!("Sort column changed: {} (shift: {})", col_id, additive),
+///             filters: filters,
+///             on_filter: move |(col_id, value)| println!("Filter changed: {} = {:?}", col_id, value),
 ///             classes: TableClasses::default(),
 ///         }
 ///     }
@@ -58,29 +79,69 @@ use dioxus::prelude::*;
 #[component]
 pub fn TableHeader(
     columns: Vec<Column>,
-    sort_column: Signal<Option<&'static str>>,
-    sort_order: Signal<SortOrder>,
-    on_sort_column: EventHandler<&'static str>,
+    sort: Signal<Vec<SortKey>>,
+    on_sort_column: EventHandler<(&'static str, bool)>,
+    filters: Signal<BTreeMap<&'static str, FilterValue>>,
+    on_filter: EventHandler<(&'static str, FilterValue)>,
     classes: TableClasses,
+    #[props(default)] texts: TableTexts,
+    #[props(default)] sticky: bool,
+    #[props(default)] column_window: Option<ColumnWindow>,
+    #[props(default)] selection: SelectionMode,
+    #[props(default)] select_all: CheckState,
+    #[props(default)] on_select_all: EventHandler<bool>,
+    #[props(default)] expandable: bool,
+    #[props(default)] all_expanded: bool,
+    #[props(default)] on_expand_all: EventHandler<bool>,
 ) -> Element {
-    let header_cells = columns.iter().map(|col| {
+    // The indeterminate state of a checkbox is a DOM property without an attribute, so it is set
+    // on the mounted element
+    let mut select_all_input = use_signal(|| None::<Rc<MountedData>>);
+    use_effect(use_reactive!(|select_all| {
+        let input = select_all_input.read();
+        if let Some(input) = input
+            .as_ref()
+            .and_then(|mounted| mounted.downcast::<web_sys::Element>())
+            .and_then(|element| element.dyn_ref::<web_sys::HtmlInputElement>())
+        {
+            input.set_indeterminate(select_all == CheckState::Indeterminate);
+        }
+    }));
+
+    let style = if sticky {
+        "position: sticky; top: 0; z-index: 2;"
+    } else {
+        ""
+    };
+    let slots = column_window.map_or_else(|| ColumnSlot::all(columns.len()), |window| window.slots);
+    let sort = sort();
+    let multi_sort = sort.len() > 1;
+    let has_filters = columns.iter().any(|col| col.filter.is_some());
+    let filters = filters();
+
+    let header_cells = slots.iter().enumerate().map(|(n, slot)| {
+        let ColumnSlot::Column { index, .. } = *slot else {
+            return rsx! {
+                th { key: "spacer-{n}", aria_hidden: "true", style: "{slot.style()}" }
+            };
+        };
+        let col = &columns[index];
         let col_id = col.id;
-        let is_sorted = sort_column() == Some(col_id);
-        let aria_sort = if is_sorted {
-            match sort_order() {
-                SortOrder::Asc => "ascending",
-                SortOrder::Desc => "descending",
-            }
-        } else {
-            "none"
+        let position = sort_key_position(&sort, col_id);
+        let aria_sort = match position {
+            Some((0, order)) => order.aria_sort(),
+            _ => "none",
         };
+        let priority = position.filter(|_| multi_sort).map(|(idx, _)| idx + 1);
 
         let class = format!("{} {}", classes.header_cell, col.class.unwrap_or_default());
-        let style = col.style.unwrap_or_default();
+        let style = format!("{} {}", col.style.unwrap_or_default(), slot.style());
         let header = col.header;
 
         let onclick = if col.sortable {
-            Callback::new(move |_| on_sort_column.call(col_id))
+            Callback::new(move |evt: MouseEvent| {
+                on_sort_column.call((col_id, evt.modifiers().contains(Modifiers::SHIFT)))
+            })
         } else {
             Callback::new(|_| {})
         };
@@ -90,19 +151,178 @@ pub fn TableHeader(
                 key: "{col_id}",
                 role: "columnheader",
                 class: "{class}",
-                style: "{style}",
+                style: "{style.trim()}",
                 aria_sort: "{aria_sort}",
                 onclick: onclick,
                 "{header}"
+                if let Some(priority) = priority {
+                    span {
+                        class: "{classes.sort_badge}",
+                        aria_label: "sort priority {priority}",
+                        "{priority}"
+                    }
+                }
+            }
+        }
+    });
+
+    let filter_cells = slots.iter().enumerate().map(|(n, slot)| {
+        let ColumnSlot::Column { index, .. } = *slot else {
+            return rsx! {
+                td { key: "spacer-{n}", aria_hidden: "true", style: "{slot.style()}" }
+            };
+        };
+        let col = &columns[index];
+        let widget = filter_widget(col, filters.get(col.id), on_filter, &classes, &texts);
+        rsx! {
+            td {
+                key: "{col.id}",
+                class: "{classes.filter_cell}",
+                style: "{slot.style()}",
+                {widget}
             }
         }
     });
 
+    let checked = select_all == CheckState::Checked;
+    let select_all_cell = match selection {
+        SelectionMode::None => rsx! {},
+        SelectionMode::Single => rsx! {
+            th { class: "{classes.select_cell}", role: "columnheader" }
+        },
+        SelectionMode::Multiple => rsx! {
+            th { class: "{classes.select_cell}", role: "columnheader",
+                input {
+                    r#type: "checkbox",
+                    checked: checked,
+                    aria_checked: "{select_all.aria_checked()}",
+                    aria_label: "{texts.select_all}",
+                    onmounted: move |evt: MountedEvent| select_all_input.set(Some(evt.data())),
+                    onclick: move |_| on_select_all.call(!checked),
+                }
+            }
+        },
+    };
+
+    let expand_all_cell = if expandable {
+        rsx! {
+            th { class: "{classes.expand_cell}", role: "columnheader",
+                button {
+                    class: "{classes.expand_button}",
+                    r#type: "button",
+                    aria_expanded: "{all_expanded}",
+                    aria_label: "{texts.expand_all}",
+                    onclick: move |_| on_expand_all.call(!all_expanded),
+                    if all_expanded { "▾" } else { "▸" }
+                }
+            }
+        }
+    } else {
+        rsx! {}
+    };
+    let leading_cells = usize::from(expandable) + usize::from(selection.is_enabled());
+
     rsx! {
-        thead { class: "{classes.thead}",
+        thead { class: "{classes.thead}", style: "{style}",
             tr { class: "{classes.row}", role: "row",
+                {expand_all_cell}
+                {select_all_cell}
                 {header_cells}
             }
+            if has_filters {
+                tr { class: "{classes.filter_row}", role: "row",
+                    for n in 0..leading_cells {
+                        td { key: "leading-{n}", class: "{classes.filter_cell}" }
+                    }
+                    {filter_cells}
+                }
+            }
+        }
+    }
+}
+
+/// Renders the input widget(s) matching the column's `ColumnFilter`, or nothing without one.
+fn filter_widget(
+    col: &Column,
+    value: Option<&FilterValue>,
+    on_filter: EventHandler<(&'static str, FilterValue)>,
+    classes: &TableClasses,
+    texts: &TableTexts,
+) -> Element {
+    let id = col.id;
+    let label = format!("Filter {}", col.header);
+    let value = value.cloned().unwrap_or_default();
+    let input_class = classes.filter_input;
+
+    match col.filter {
+        None => rsx! {},
+        Some(ColumnFilter::Text | ColumnFilter::Equals) => {
+            let text = value.text().to_string();
+            rsx! {
+                input {
+                    class: "{input_class}",
+                    r#type: "text",
+                    value: "{text}",
+                    aria_label: "{label}",
+                    oninput: move |evt| on_filter.call((id, FilterValue::Text(evt.value()))),
+                }
+            }
+        }
+        Some(ColumnFilter::Select(options)) => {
+            let selected = value.text().to_string();
+            rsx! {
+                select {
+                    class: "{input_class}",
+                    aria_label: "{label}",
+                    onchange: move |evt| on_filter.call((id, FilterValue::Text(evt.value()))),
+                    option { value: "", selected: selected.is_empty(), "{texts.filter_all}" }
+                    for option in options.iter() {
+                        option {
+                            key: "{option}",
+                            value: "{option}",
+                            selected: selected == *option,
+                            "{option}"
+                        }
+                    }
+                }
+            }
+        }
+        Some(filter @ (ColumnFilter::NumberRange | ColumnFilter::DateRange)) => {
+            let input_type = if filter == ColumnFilter::NumberRange {
+                "number"
+            } else {
+                "date"
+            };
+            let (min, max) = value.range();
+            let (min, max) = (min.to_string(), max.to_string());
+            let (min_text, max_text) = (min.clone(), max.clone());
+            let min_label = format!("{label} ({})", texts.filter_min);
+            let max_label = format!("{label} ({})", texts.filter_max);
+
+            rsx! {
+                input {
+                    class: "{input_class}",
+                    r#type: "{input_type}",
+                    value: "{min_text}",
+                    placeholder: "{texts.filter_min}",
+                    aria_label: "{min_label}",
+                    oninput: move |evt| {
+                        let range = FilterValue::Range { min: evt.value(), max: max.clone() };
+                        on_filter.call((id, range))
+                    },
+                }
+                input {
+                    class: "{input_class}",
+                    r#type: "{input_type}",
+                    value: "{max_text}",
+                    placeholder: "{texts.filter_max}",
+                    aria_label: "{max_label}",
+                    oninput: move |evt| {
+                        let range = FilterValue::Range { min: min.clone(), max: evt.value() };
+                        on_filter.call((id, range))
+                    },
+                }
+            }
         }
     }
 }
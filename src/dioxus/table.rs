@@ -1,14 +1,29 @@
 use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
 
+#[cfg(target_family = "wasm")]
+use dioxus::core::Task;
+#[cfg(target_family = "wasm")]
+use gloo_timers::future::TimeoutFuture;
 #[cfg(target_family = "wasm")]
 use web_sys::UrlSearchParams;
 #[cfg(target_family = "wasm")]
 use web_sys::wasm_bindgen::JsValue;
 
+#[cfg(target_family = "wasm")]
+use crate::core::SEARCH_DEBOUNCE_MS;
+use crate::core::{
+    CheckState, LoadedPages, RowHeight, RowHeights, SearchIndex, Selection, SelectionMode,
+    SlicedView, SortCycle, TableRow, TableState, ViewData, matching_rows, page_view, row_keys,
+    search_columns, sort_rows, toggle_sort_key,
+};
 use crate::dioxus::body::TableBody;
 use crate::dioxus::controls::PaginationControls;
 use crate::dioxus::header::TableHeader;
-use crate::dioxus::types::SortOrder;
+use crate::dioxus::types::FilterValue;
+use crate::dioxus::types::SearchSyntax;
 use crate::dioxus::types::TableProps;
 
 /// A fully featured table component with sorting, pagination, and search functionality in Dioxus.
@@ -19,19 +34,50 @@ use crate::dioxus::types::TableProps;
 ///
 /// # Props
 /// `TableProps` defines the configuration for this component:
-/// - `data`: A `Vec<HashMap<&'static str, String>>` representing row data.
+/// - `data`: A `Vec<T>` of rows implementing `TableRow`, such as `HashMap<&'static str, String>`.
+/// - `data_source`: An optional `DataSourceHandle<T>` fetching each page asynchronously instead of using `data`; the source then does the searching, filtering, sorting and paging (default: `None`).
+/// - `retry`: A `RetryPolicy` for failed `data_source` fetches, retried with exponential backoff before an error row with a retry button is shown (default: `RetryPolicy::default()`).
 /// - `columns`: A `Vec<Column>` describing each column's ID, header text, and behavior.
 /// - `page_size`: Number of rows to display per page (default: `10`).
 /// - `loading`: When `true`, displays a loading indicator (default: `false`).
 /// - `paginate`: Enables pagination controls (default: `false`).
+/// - `pagination_mode`: A `PaginationMode`: `Pages` flips pages, `LoadMore` appends the next page from a button and `InfiniteScroll` when the end of the table scrolls into view (default: `Pages`).
+/// - `virtualize`: An optional `VirtualScroll` rendering only the rows in view inside a scrolling area of fixed height, for fixed or measured row heights (default: `None`).
+/// - `virtualize_columns`: An optional `VirtualColumns` rendering only the pinned columns and those in view, for tables with hundreds of columns (default: `None`).
 /// - `search`: Enables a search input for client-side filtering (default: `false`).
+/// - `search_syntax`: `SearchSyntax::Structured` reads the search input as a query such as `status:open priority:>2 -assignee:bot`, `SearchSyntax::Fuzzy` tolerates typos, ranks rows by relevance and highlights the matches (default: `SearchSyntax::Plain`).
+/// - `search_options`: A `SearchOptions` controlling case folding, accent stripping and punctuation handling during search (default: ignore case and accents).
+/// - `search_fields`: A `Vec<&'static str>` of row keys searched without being displayed, such as tags or ids (default: none).
+/// - `search_index`: An optional `SearchIndexKind` normalizing the searched cells once per change of `data` (optionally with a trigram index) for fast plain searches of large data (default: `None`).
+/// - `worker`: An optional `ViewWorker` searching, filtering and sorting `data` in a Web Worker, so that large data sets do not block input (default: `None`).
+/// - `time_slicing`: An optional `TimeSlicing` searching, filtering and sorting `data` a slice at a time between frames, for large data sets without a worker (default: `None`).
+/// - `default_sort`: A `Vec<SortKey>` applied on first render, e.g. `vec![("date", SortOrder::Desc)]` (default: unsorted).
+/// - `allow_unsorted`: When `true`, a third header click unsorts the column and restores the data order (default: `false`).
+/// - `row_key`: An optional `RowKey` (a column id or a function of the row) keying each `<tr>` and identifying rows in the selection, so that DOM state follows rows as they are sorted and paged (default: `None`, keying rows by index).
+/// - `selection`: A `SelectionMode`; `Single` or `Multiple` add a checkbox column, with shift-click selecting ranges and a "select all" checkbox in `Multiple` mode (default: `None`).
+/// - `select_all_filtered`: When `true`, "select all" covers every row matching the search and filters rather than the current page (default: `false`).
+/// - `on_selection_change`: An `EventHandler<Vec<String>>` called with the keys of the selected rows whenever the selection changes (default: no-op).
+/// - `expandable`: When `true`, a column of buttons shows or hides the detail panel of each row, with an "expand all" button in its header (default: `false`).
+/// - `render_detail`: An optional `Callback<T, Element>` rendering the detail panel of an expanded row, in a full-width row beneath it (default: `None`).
+/// - `expanded`: An optional `Vec<String>` of the keys of the expanded rows, for a parent controlling the expansion; without it the table keeps track of it (default: `None`).
+/// - `on_expand_change`: An `EventHandler<Vec<String>>` called with the keys of the expanded rows whenever a row is expanded or collapsed (default: no-op).
 /// - `texts`: Customizable text labels for UI strings (default: `TableTexts::default()`).
 /// - `classes`: Customizable CSS class names for each table part (default: `TableClasses::default()`).
 ///
 /// # Features
-/// - **Search**: Filters rows client-side using a text input; the query is persisted in the URL via `?search=`.
-/// - **Sorting**: Clickable headers allow sorting columns ascending or descending.
+/// - **Search**: Filters rows client-side using a debounced text input; the query is persisted in the URL via `?search=` and may use `field:value` syntax.
+/// - **Sorting**: Clickable headers allow sorting columns ascending or descending; shift-click adds a secondary sort column.
+/// - **Column Filters**: Columns with a `filter` get a text box, drop-down, or min/max inputs in a filter row; all filters are combined with AND.
+/// - **Server-side Data**: With a `data_source`, pages are fetched whenever the page, sort, filters or debounced query change, showing the loading row meanwhile.
+/// - **Request Lifecycle**: Superseded requests are cancelled, and failed ones are retried with backoff.
 /// - **Pagination**: Navigate between pages using prev/next buttons, with an indicator showing current page.
+/// - **Load More and Infinite Scroll**: Append pages for feeds, with cursor-based continuation for data sources.
+/// - **Virtualization**: Only the rows in view are rendered, between spacer rows and under a sticky header.
+/// - **Column Virtualization**: Only the pinned columns and the columns in view are rendered, between spacer cells.
+/// - **Web Worker Offload**: Searching, filtering and sorting run in a worker, showing the loading row meanwhile.
+/// - **Time Slicing**: Without a worker, searching, filtering and sorting run in slices between which input is handled.
+/// - **Row Selection**: Selected rows are kept across pages, sorting and filtering, with a tri-state "select all" checkbox.
+/// - **Expandable Rows**: A custom detail panel under each expanded row, controlled or uncontrolled, with an "expand all" toggle.
 /// - **Custom Classes**: All elements are styled via `TableClasses` for full customization.
 /// - **Text Overrides**: All UI strings (e.g., empty state, loading, buttons) can be customized using `TableTexts`.
 ///
@@ -71,22 +117,54 @@ use crate::dioxus::types::TableProps;
 /// # See Also
 /// - [MDN `<table>` Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/table)
 #[component]
-pub fn Table(props: TableProps) -> Element {
+pub fn Table<T: TableRow + Clone + PartialEq + 'static>(props: TableProps<T>) -> Element {
     let TableProps {
         data,
+        data_source,
+        retry,
         columns,
         page_size,
         loading,
         paginate,
+        pagination_mode,
+        virtualize,
+        virtualize_columns,
         search,
+        search_syntax,
+        search_options,
+        search_fields,
+        search_index,
+        worker,
+        time_slicing,
+        default_sort,
+        allow_unsorted,
+        row_key,
+        selection,
+        select_all_filtered,
+        on_selection_change,
+        expandable,
+        render_detail,
+        expanded,
+        on_expand_change,
         texts,
         classes,
     } = props;
 
     let mut page = use_signal(|| 0_usize);
-    let mut sort_column = use_signal(|| None::<&'static str>);
-    let mut sort_order = use_signal(SortOrder::default);
+    let mut sort = use_signal(|| default_sort);
+    let mut filters = use_signal(BTreeMap::<&'static str, FilterValue>::new);
     let mut search_query = use_signal(String::new);
+    let mut search_input = use_signal(String::new);
+    let mut loaded = use_signal(LoadedPages::<T>::default);
+    let mut remote_error = use_signal(|| None::<String>);
+    let scroll_top = use_hook(|| Rc::new(Cell::new(0.0_f64)));
+    let scroll_left = use_hook(|| Rc::new(Cell::new(0.0_f64)));
+    let heights = use_hook(|| Rc::new(RefCell::new(None::<(TableState, RowHeights)>)));
+    let mut relayout = use_signal(|| 0_u64);
+    let mut selected = use_signal(Selection::default);
+    let mut expanded_state = use_signal(BTreeSet::<String>::new);
+    #[cfg(target_family = "wasm")]
+    let mut debounced_search = use_signal(|| None::<Task>);
 
     // Reset page to 0 when search query changes to prevent invalid page states
     use_effect(use_reactive!(|search_query| {
@@ -101,6 +179,7 @@ pub fn Table(props: TableProps) -> Element {
             .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
             .and_then(|params| params.get("search"))
         {
+            search_input.set(search_val.clone());
             search_query.set(search_val);
         }
     });
@@ -122,65 +201,404 @@ pub fn Table(props: TableProps) -> Element {
         });
     };
 
-    // Work with indices instead of cloning data to reduce memory allocations
-    let mut filtered_indices: Vec<usize> = if !search_query().is_empty() {
-        data.iter()
-            .enumerate()
-            .filter(|(_, row)| {
-                columns.iter().any(|col| {
-                    row.get(col.id)
-                        .map(|v| v.to_lowercase().contains(&search_query().to_lowercase()))
-                        .unwrap_or(false)
-                })
-            })
-            .map(|(idx, _)| idx)
-            .collect()
-    } else {
-        (0..data.len()).collect()
-    };
-
-    if let Some(col_id) = sort_column()
-        && let Some(col) = columns.iter().find(|c| c.id == col_id)
-    {
-        let val = "".to_string();
-        filtered_indices.sort_by(|&a, &b| {
-            let a_val = data[a].get(col.id).unwrap_or(&val);
-            let b_val = data[b].get(col.id).unwrap_or(&val);
-            match sort_order() {
-                SortOrder::Asc => a_val.cmp(b_val),
-                SortOrder::Desc => b_val.cmp(a_val),
+    let state = TableState {
+        query: search_query(),
+        search_syntax,
+        search_options,
+        search_fields: search_fields.clone(),
+        sort: sort(),
+        filters: filters(),
+        page: page(),
+        page_size,
+        pagination_mode,
+        cursor: loaded.read().cursor(page()),
+    };
+    let highlight = match search_syntax {
+        SearchSyntax::Fuzzy => state.query.clone(),
+        _ => String::new(),
+    };
+
+    // Fetch the page from the data source whenever the request changes. The resource drops the
+    // future of a superseded request, so its response and pending retries never land.
+    let is_remote = data_source.is_some();
+    let request = state.clone();
+    let mut remote = use_resource(use_reactive!(|(data_source, request, retry)| async move {
+        let Some(data_source) = data_source else {
+            return;
+        };
+        let sleep = |ms| async move {
+            #[cfg(target_family = "wasm")]
+            TimeoutFuture::new(ms).await;
+            #[cfg(not(target_family = "wasm"))]
+            let _ = ms;
+        };
+        match data_source
+            .fetch_with_retry(&request, &retry, sleep, || true)
+            .await
+        {
+            Some(Ok(data)) => {
+                loaded.write().insert(request.page, data);
+                remote_error.set(None);
+            }
+            Some(Err(err)) => {
+                web_sys::console::error_1(&format!("Failed to fetch table rows: {err}").into());
+                remote_error.set(Some(err));
             }
+            None => {}
+        }
+    }));
+
+    // Row heights are measured per position, so start over whenever the rows are reordered or
+    // replaced; appending pages keeps the positions of the rows already shown.
+    if let Some(virtualize) = virtualize {
+        let layout = TableState {
+            page: if pagination_mode.appends() {
+                0
+            } else {
+                state.page
+            },
+            cursor: None,
+            ..state.clone()
+        };
+        let mut heights = heights.borrow_mut();
+        let stale = heights.as_ref().is_none_or(|(key, heights)| {
+            *key != layout || heights.row_height() != virtualize.row_height
         });
+        if stale {
+            *heights = Some((layout, RowHeights::new(virtualize.row_height)));
+        }
     }
+    // Subscribe to new measurements and scroll positions, which move the rows
+    let _ = relayout();
+    let window_of = |count: usize| {
+        let virtualize = virtualize?;
+        let heights = heights.borrow();
+        let (_, heights) = heights.as_ref()?;
+        Some(heights.window(
+            count,
+            scroll_top.get(),
+            virtualize.viewport_height,
+            virtualize.overscan,
+        ))
+    };
+
+    // The worker gets a copy of the rows once per version of them, counted by the memo; the
+    // resource then asks it for their order, and drops the answer to a superseded request
+    let offload = worker.filter(|_| !is_remote && ViewData::supports(&columns));
+    let offloaded = offload.is_some();
+    let load_worker = offload.clone();
+    let load_columns = columns.clone();
+    let load_fields = search_fields.clone();
+    let mut loads = 0_u64;
+    let worker_version = use_memo(use_reactive!(|(load_worker, load_columns, load_fields)| {
+        let data = data.read();
+        if let Some(worker) = &load_worker {
+            worker.load(ViewData::new(&data, &load_columns, &load_fields));
+        }
+        loads += 1;
+        loads
+    }));
+    let view_request = TableState {
+        page: 0,
+        cursor: None,
+        ..state.clone()
+    };
+    let slice_request = view_request.clone();
+    let current_request = view_request.clone();
+    let worker_rows = use_resource(use_reactive!(|(offload, view_request)| async move {
+        let version = worker_version();
+        let worker = offload?;
+        Some((version, worker.sorted_rows(&view_request).await))
+    }));
+
+    // Without a worker, the rows can be searched, filtered and sorted a slice at a time, with
+    // the browser handling input in between. The resource abandons the computation when the
+    // rows, search, filters or sort change, and its answer names what it was computed for.
+    let slicing = time_slicing.filter(|_| !is_remote && !offloaded);
+    let slice_columns = columns.clone();
+    let sliced_rows = use_resource(use_reactive!(|(slicing, slice_columns, slice_request)| {
+        async move {
+            let time_slicing = slicing?;
+            let mut view = SlicedView::new(&slice_request);
+            while !view.step(&data.read(), &slice_columns, time_slicing.budget) {
+                #[cfg(target_family = "wasm")]
+                TimeoutFuture::new(0).await;
+            }
+            let indices = view.indices()?.to_vec();
+            Some((slice_request, data.read().len(), indices))
+        }
+    }));
+    let slicing = slicing.is_some();
+
+    // The index follows `data` from one version of it to the next, so only the rows appended or
+    // changed since the last one are normalized; the memo counts the versions indexed
+    let index = use_hook(|| Rc::new(RefCell::new(None::<SearchIndex>)));
+    let index_columns = columns.clone();
+    let index_fields = search_fields.clone();
+    let indexed = index.clone();
+    let mut versions = 0_u64;
+    let index_version = use_memo(use_reactive!(|(
+        index_columns,
+        index_fields,
+        search_options,
+        search_index,
+        is_remote,
+        offloaded,
+        slicing,
+    )| {
+        let mut index = indexed.borrow_mut();
+        let Some(kind) = search_index.filter(|_| !is_remote && !offloaded && !slicing) else {
+            *index = None;
+            return 0;
+        };
+        let searched = search_columns(&index_columns, &index_fields);
+        let reusable = index
+            .as_ref()
+            .is_some_and(|index| index.kind() == kind && index.covers(&searched, &search_options));
+        if !reusable {
+            *index = Some(SearchIndex::new(&searched, search_options, kind));
+        }
+        if let Some(index) = index.as_mut() {
+            index.sync(&data.read());
+        }
+        versions += 1;
+        versions
+    }));
+
+    // Each stage reruns only when its own inputs change, so that flipping pages neither searches
+    // nor sorts again
+    let filter_columns = columns.clone();
+    let matching = use_memo(use_reactive!(|(
+        filter_columns,
+        search_syntax,
+        search_options,
+        search_fields,
+        is_remote,
+        offloaded,
+        slicing,
+    )| {
+        if is_remote || offloaded || slicing {
+            return Vec::new();
+        }
+        let _ = index_version();
+        let state = TableState {
+            query: search_query(),
+            search_syntax,
+            search_options,
+            search_fields,
+            filters: filters(),
+            ..Default::default()
+        };
+        match index.borrow().as_ref() {
+            Some(index) => index.matching_rows(&data.read(), &filter_columns, &state),
+            None => matching_rows(&data.read(), &filter_columns, &state),
+        }
+    }));
+    let sorted_columns = columns.clone();
+    let sorted = use_memo(use_reactive!(|sorted_columns| {
+        let mut indices = matching();
+        sort_rows(&data.read(), &sorted_columns, &mut indices, &sort.read());
+        indices
+    }));
+
+    // Positions in the loaded rows, or indices into `data`, of the rows on the page (`shown`)
+    // and of those to render, and in display order of all the rows for range selection
+    let row_count;
+    let mut computing_slices = false;
+    let mut order = Vec::new();
+    let (shown, visible, current_page, total_pages, virtual_window) = if is_remote {
+        let loaded = loaded.read();
+        row_count = loaded.row_count(state.page, pagination_mode);
+        let window = window_of(row_count);
+        let range = window
+            .as_ref()
+            .map_or(0..row_count, |window| window.range.clone());
+        let shown = (0..row_count).collect::<Vec<_>>();
+        if selection == SelectionMode::Multiple {
+            order = shown.clone();
+        }
+        (
+            shown,
+            range.collect::<Vec<_>>(),
+            state.page,
+            loaded.total_pages(page_size),
+            window,
+        )
+    } else {
+        let sorted = sorted.read();
+        let worker_rows = worker_rows.read();
+        let sliced_rows = sliced_rows.read();
+        // Until the worker or the last slice has answered for these rows, the loading row stands
+        // in for them
+        let sliced = match &*sliced_rows {
+            Some(Some((request, len, indices)))
+                if *request == current_request && *len == data.read().len() =>
+            {
+                Some(&indices[..])
+            }
+            _ => None,
+        };
+        computing_slices = slicing && sliced.is_none();
+        let indices: &[usize] = match &*worker_rows {
+            _ if slicing => sliced.unwrap_or_default(),
+            _ if !offloaded => &sorted,
+            Some(Some((version, indices))) if *version == worker_version() => indices,
+            _ => &[],
+        };
+        if selection == SelectionMode::Multiple {
+            order = indices.to_vec();
+        }
+        let view = page_view(indices, &state);
+        row_count = view.indices.len();
+        let window = window_of(row_count);
+        let indices = match &window {
+            Some(window) => view.indices[window.range.clone()].to_vec(),
+            None => view.indices.clone(),
+        };
+        (view.indices, indices, view.page, view.total_pages, window)
+    };
+
+    // Rows are keyed by `row_key`, or else local rows by their index in `data` and remote ones by
+    // their position among the results
+    let key_offset = if is_remote && !pagination_mode.appends() {
+        current_page * page_size
+    } else {
+        0
+    };
+    let keys_of = move |indices: &[usize]| {
+        let data = data.read();
+        let loaded = loaded.read();
+        let remote_rows: Vec<&T> = if is_remote {
+            loaded.iter(current_page, pagination_mode).collect()
+        } else {
+            Vec::new()
+        };
+        let row = |idx: usize| {
+            if is_remote {
+                remote_rows.get(idx).copied()
+            } else {
+                data.get(idx)
+            }
+        };
+        row_keys(row_key, indices, row, key_offset)
+    };
+    let row_keys = keys_of(&visible);
+    let page_keys = if expandable {
+        keys_of(&shown)
+    } else {
+        Vec::new()
+    };
+    let scope = if select_all_filtered {
+        order.clone()
+    } else {
+        shown
+    };
+    let select_all = match selection {
+        SelectionMode::Multiple => selected.read().check_state(keys_of(&scope)),
+        _ => CheckState::Unchecked,
+    };
+    let on_select = move |(key, extend): (String, bool)| {
+        let order = keys_of(&order);
+        selected.write().toggle(selection, &key, extend, order);
+        on_selection_change.call(selected.read().keys());
+    };
+    let on_select_all = move |checked: bool| {
+        let scope = keys_of(&scope);
+        selected.write().set_all(scope, checked);
+        on_selection_change.call(selected.read().keys());
+    };
 
-    // Ensure page_size is at least 1 to prevent division by zero
-    let page_size_safe = page_size.max(1);
-    // Ensure at least 1 page to avoid confusing 'Page 1 of 0' message when empty
-    let total_pages =
-        ((filtered_indices.len() as f64 / page_size_safe as f64).ceil() as usize).max(1);
-
-    // Clamp current page to valid range to prevent showing empty results
-    let current_page = page().min(total_pages.saturating_sub(1));
-    let start = current_page * page_size_safe;
-    let end = ((current_page + 1) * page_size_safe).min(filtered_indices.len());
-    let page_rows: Vec<_> = filtered_indices[start..end]
-        .iter()
-        .map(|&idx| data[idx].clone())
-        .collect();
-    let page_rows = &page_rows[..];
-
-    let on_sort_column = move |id: &'static str| {
-        if Some(id) == sort_column() {
-            sort_order.set(match sort_order() {
-                SortOrder::Asc => SortOrder::Desc,
-                SortOrder::Desc => SortOrder::Asc,
-            });
+    // A parent passing `expanded` controls the expansion; otherwise the table keeps track of it
+    let expanded_rows = match &expanded {
+        Some(keys) => keys.iter().cloned().collect(),
+        None => expanded_state(),
+    };
+    let all_expanded =
+        !page_keys.is_empty() && page_keys.iter().all(|key| expanded_rows.contains(key));
+    let mut set_expanded = move |next: BTreeSet<String>| {
+        on_expand_change.call(next.iter().cloned().collect());
+        expanded_state.set(next);
+    };
+    let current = expanded_rows.clone();
+    let on_expand = move |key: String| {
+        let mut next = current.clone();
+        if !next.remove(&key) {
+            next.insert(key);
+        }
+        set_expanded(next);
+    };
+    let current = expanded_rows.clone();
+    let on_expand_all = move |expand: bool| {
+        let mut next = current.clone();
+        for key in &page_keys {
+            if expand {
+                next.insert(key.clone());
+            } else {
+                next.remove(key);
+            }
+        }
+        set_expanded(next);
+    };
+    // The rows are cloned once when they scroll or page into view and shared from then on
+    let page_rows = use_memo(use_reactive!(|(visible, is_remote, pagination_mode)| {
+        if is_remote {
+            let loaded = loaded.read();
+            visible
+                .iter()
+                .filter_map(|&pos| loaded.get(page(), pagination_mode, pos).cloned())
+                .collect()
         } else {
-            sort_column.set(Some(id));
-            sort_order.set(SortOrder::Asc);
+            let data = data.read();
+            visible
+                .iter()
+                .map(|&idx| data[idx].clone())
+                .collect::<Vec<_>>()
+        }
+    }));
+    let on_measure = match virtualize.map(|virtualize| virtualize.row_height) {
+        Some(RowHeight::Measured { .. }) => {
+            let heights = heights.clone();
+            Some(EventHandler::new(move |(row, height): (usize, f64)| {
+                let changed = heights
+                    .borrow_mut()
+                    .as_mut()
+                    .is_some_and(|(_, heights)| heights.measure(row, height));
+                if changed {
+                    relayout += 1;
+                }
+            }))
+        }
+        _ => None,
+    };
+    let loading = loading
+        || *remote.state().read() == UseResourceState::Pending
+        || (offloaded && *worker_rows.state().read() == UseResourceState::Pending)
+        || computing_slices;
+    let has_more = current_page + 1 < total_pages;
+
+    // Appends the next page, once the current one has arrived so that its cursor is known
+    let ready = has_more && !loading && (!is_remote || loaded.read().contains(current_page));
+    let on_load_more = move |()| {
+        if ready {
+            page.set(current_page + 1);
+        }
+    };
+
+    let sort_columns = columns.clone();
+    let on_sort_column = move |(id, additive): (&'static str, bool)| {
+        let cycle = SortCycle::for_column(&sort_columns, id, allow_unsorted);
+        toggle_sort_key(&mut sort.write(), id, additive, cycle);
+        // Appended pages no longer line up with the new order, so start over
+        if pagination_mode.appends() {
+            page.set(0);
         }
     };
 
+    let on_filter = move |(id, value): (&'static str, FilterValue)| {
+        filters.write().insert(id, value);
+        page.set(0);
+    };
+
     let pagination_controls = if paginate {
         rsx! {
             PaginationControls {
@@ -188,12 +606,117 @@ pub fn Table(props: TableProps) -> Element {
                 total_pages: total_pages,
                 classes: classes.clone(),
                 texts: texts.clone(),
+                pagination_mode: pagination_mode,
+                on_load_more: on_load_more,
             }
         }
     } else {
         rsx! {}
     };
 
+    let column_window = virtualize_columns
+        .map(|virtualize_columns| virtualize_columns.window(&columns, scroll_left.get()));
+
+    // Scrolling within the rendered rows and columns needs no render; only a different window does
+    let rendered = virtual_window.as_ref().map(|window| window.range.clone());
+    let rendered_columns = column_window.clone();
+    let scroll_columns = columns.clone();
+    let on_scroll = move |evt: ScrollEvent| {
+        let top = evt.scroll_top();
+        let left = evt.scroll_left();
+        let range = virtualize.and_then(|virtualize| {
+            let heights = heights.borrow();
+            let (_, heights) = heights.as_ref()?;
+            let window = heights.window(
+                row_count,
+                top,
+                virtualize.viewport_height,
+                virtualize.overscan,
+            );
+            Some(window.range)
+        });
+        let column_window = virtualize_columns
+            .map(|virtualize_columns| virtualize_columns.window(&scroll_columns, left));
+        scroll_top.set(top);
+        scroll_left.set(left);
+        if range != rendered || column_window != rendered_columns {
+            relayout += 1;
+        }
+    };
+
+    let mut scroll_style = String::new();
+    if let Some(virtualize) = virtualize {
+        scroll_style += &format!(
+            "height: {}px; overflow-y: auto;",
+            virtualize.viewport_height
+        );
+    }
+    if let Some(virtualize_columns) = virtualize_columns {
+        scroll_style += &format!(
+            " max-width: {}px; overflow-x: auto;",
+            virtualize_columns.viewport_width
+        );
+    }
+    // A fixed layout keeps the columns from resizing as different rows scroll into view, and
+    // sizes them from the header cells so that the body lines up under the header
+    let mut table_style = String::new();
+    if virtualize.is_some() || virtualize_columns.is_some() {
+        table_style += "table-layout: fixed;";
+    }
+    if let Some(column_window) = &column_window {
+        table_style += &format!(" width: {}px;", column_window.total_width);
+    }
+
+    let table = rsx! {
+        table {
+            class: "{classes.table}",
+            style: if !table_style.is_empty() { "{table_style.trim()}" },
+            aria_busy: "{loading}",
+            TableHeader {
+                columns: columns.clone(),
+                sort: sort,
+                on_sort_column: on_sort_column,
+                filters: filters,
+                on_filter: on_filter,
+                classes: classes.clone(),
+                texts: texts.clone(),
+                sticky: virtualize.is_some(),
+                column_window: column_window.clone(),
+                selection: selection,
+                select_all: select_all,
+                on_select_all: on_select_all,
+                expandable: expandable,
+                all_expanded: all_expanded,
+                on_expand_all: on_expand_all,
+            }
+            TableBody {
+                columns: columns.clone(),
+                rows: page_rows,
+                loading: loading,
+                error: remote_error(),
+                on_retry: move |()| remote.restart(),
+                pagination_mode: pagination_mode,
+                has_more: has_more,
+                on_load_more: on_load_more,
+                classes: classes.clone(),
+                texts: texts.clone(),
+                highlight: highlight,
+                search_options: search_options,
+                virtual_window: virtual_window,
+                on_measure: on_measure,
+                column_window: column_window,
+                selection: selection,
+                row_keys: row_keys,
+                selected: selected(),
+                on_select: on_select,
+                expandable: expandable,
+                expanded: expanded_rows,
+                on_expand: on_expand,
+                render_detail: render_detail,
+            }
+        }
+    };
+
     rsx! {
         div {
             class: "{classes.container}",
@@ -201,33 +724,43 @@ pub fn Table(props: TableProps) -> Element {
                 input {
                     class: "{classes.search_input}",
                     r#type: "text",
-                    value: "{search_query()}",
+                    value: "{search_input()}",
                     placeholder: "{texts.search_placeholder}",
                     oninput: move |e| {
                         let val = e.value();
-                        search_query.set(val.clone());
-                        page.set(0);
+                        search_input.set(val.clone());
+
                         #[cfg(target_family = "wasm")]
-                        update_search_param(&val);
+                        {
+                            // Cancel previous timeout to prevent multiple URL updates
+                            if let Some(prev) = debounced_search.take() {
+                                prev.cancel();
+                            }
+                            debounced_search.set(Some(spawn(async move {
+                                TimeoutFuture::new(SEARCH_DEBOUNCE_MS).await;
+                                update_search_param(&val);
+                                search_query.set(val);
+                                page.set(0);
+                            })));
+                        }
+
+                        #[cfg(not(target_family = "wasm"))]
+                        {
+                            search_query.set(val);
+                            page.set(0);
+                        }
                     }
                 }
             }
-            table {
-                class: "{classes.table}",
-                TableHeader {
-                    columns: columns.clone(),
-                    sort_column: sort_column,
-                    sort_order: sort_order,
-                    on_sort_column: on_sort_column,
-                    classes: classes.clone(),
-                }
-                TableBody {
-                    columns: columns.clone(),
-                    rows: page_rows.to_vec(),
-                    loading: loading,
-                    classes: classes.clone(),
-                    texts: texts.clone(),
+            if !scroll_style.is_empty() {
+                div {
+                    class: "{classes.scroll_container}",
+                    style: "{scroll_style.trim()}",
+                    onscroll: on_scroll,
+                    {table}
                 }
+            } else {
+                {table}
             }
             {pagination_controls}
         }
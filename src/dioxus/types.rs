@@ -1,8 +1,18 @@
 use dioxus::prelude::*;
 use std::collections::HashMap;
 
+use crate::core::{ColumnDef, SortFn, TableColumn, TableRow};
+
+pub use crate::core::{
+    CheckState, ColumnFilter, ColumnKind, ColumnSlot, ColumnWindow, DataFuture, DataPage,
+    DataSource, DataSourceHandle, FilterValue, PaginationMode, RetryPolicy, RowHeight, RowKey,
+    SearchIndexKind, SearchOptions, SearchSyntax, Selection, SelectionMode, SortKey, SortOrder,
+    TimeSlicing, VirtualColumns, VirtualScroll, VirtualWindow,
+};
+pub use crate::worker::ViewWorker;
+
 /// Represents a column definition for the table.
-#[derive(PartialEq, Props, Clone, Default)]
+#[derive(PartialEq, Props, Clone)]
 pub struct Column {
     /// Unique identifier for the column.
     pub id: &'static str,
@@ -14,6 +24,26 @@ pub struct Column {
     #[props(default)]
     pub sortable: bool,
 
+    /// How values are parsed and compared when sorting (defaults to text).
+    #[props(default)]
+    pub kind: ColumnKind,
+
+    /// Custom comparator for sorting, e.g. `SortFn::Natural` or a severity order.
+    #[props(default)]
+    pub sort_fn: Option<SortFn>,
+
+    /// Direction of the first click on the header, e.g. `SortOrder::Desc` for newest-first dates.
+    #[props(default)]
+    pub first_sort_order: SortOrder,
+
+    /// Filter widget shown for this column in the filter row, if any.
+    #[props(default)]
+    pub filter: Option<ColumnFilter>,
+
+    /// Whether the search input looks at this column; turn off for columns such as avatars.
+    #[props(default = true)]
+    pub searchable: bool,
+
     /// Provide custom element generator (defaults to plain String -> String).
     #[props(default)]
     pub cell: Option<Callback<String, Element>>,
@@ -22,6 +52,10 @@ pub struct Column {
     #[props(default = 100)]
     pub min_width: u32,
 
+    /// Whether the column always renders, stuck to the left edge, when the columns are virtualized.
+    #[props(default = false)]
+    pub pinned: bool,
+
     /// Optional inline styles for the column header.
     #[props(default)]
     pub style: Option<&'static str>,
@@ -31,6 +65,76 @@ pub struct Column {
     pub class: Option<&'static str>,
 }
 
+impl TableColumn for Column {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn kind(&self) -> ColumnKind {
+        self.kind
+    }
+
+    fn sort_fn(&self) -> Option<SortFn> {
+        self.sort_fn
+    }
+
+    fn first_sort_order(&self) -> SortOrder {
+        self.first_sort_order
+    }
+
+    fn filter(&self) -> Option<ColumnFilter> {
+        self.filter
+    }
+
+    fn searchable(&self) -> bool {
+        self.searchable
+    }
+
+    fn min_width(&self) -> u32 {
+        self.min_width
+    }
+
+    fn pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Self {
+            id: "",
+            header: "",
+            sortable: false,
+            kind: ColumnKind::Text,
+            sort_fn: None,
+            first_sort_order: SortOrder::Asc,
+            filter: None,
+            searchable: true,
+            cell: None,
+            min_width: 0,
+            pinned: false,
+            style: None,
+            class: None,
+        }
+    }
+}
+
+impl From<ColumnDef> for Column {
+    fn from(def: ColumnDef) -> Self {
+        Self {
+            id: def.id,
+            header: def.header,
+            sortable: def.sortable,
+            kind: def.kind,
+            sort_fn: def.sort_fn,
+            first_sort_order: def.first_sort_order,
+            filter: def.filter,
+            searchable: def.searchable,
+            ..Default::default()
+        }
+    }
+}
+
 /// Text labels for table UI elements.
 #[derive(PartialEq, Props, Clone)]
 pub struct TableTexts {
@@ -42,6 +146,14 @@ pub struct TableTexts {
     #[props(default = "No results found")]
     pub empty: &'static str,
 
+    /// Text shown when the data source failed to load the rows.
+    #[props(default = "Failed to load data.")]
+    pub error: &'static str,
+
+    /// Label of the button retrying a failed load.
+    #[props(default = "Retry")]
+    pub retry: &'static str,
+
     /// Placeholder text for the search input.
     #[props(default = "Search...")]
     pub search_placeholder: &'static str,
@@ -57,6 +169,38 @@ pub struct TableTexts {
     /// Page indicator text with placeholders `{current}` and `{total}`.
     #[props(default = "Page {current} of {total}")]
     pub page_indicator: &'static str,
+
+    /// Label for the button appending the next page.
+    #[props(default = "Load more")]
+    pub load_more: &'static str,
+
+    /// Label of the drop-down option that clears a select filter.
+    #[props(default = "All")]
+    pub filter_all: &'static str,
+
+    /// Placeholder and label suffix for the lower bound of a range filter.
+    #[props(default = "Min")]
+    pub filter_min: &'static str,
+
+    /// Placeholder and label suffix for the upper bound of a range filter.
+    #[props(default = "Max")]
+    pub filter_max: &'static str,
+
+    /// Label of the checkbox selecting a row.
+    #[props(default = "Select row")]
+    pub select_row: &'static str,
+
+    /// Label of the checkbox selecting every row.
+    #[props(default = "Select all rows")]
+    pub select_all: &'static str,
+
+    /// Label of the button showing the detail panel of a row.
+    #[props(default = "Show details")]
+    pub expand_row: &'static str,
+
+    /// Label of the button showing the detail panels of every row.
+    #[props(default = "Show all details")]
+    pub expand_all: &'static str,
 }
 
 impl Default for TableTexts {
@@ -64,10 +208,20 @@ impl Default for TableTexts {
         Self {
             loading: "Loading...",
             empty: "No results found",
+            error: "Failed to load data.",
+            retry: "Retry",
             search_placeholder: "Search...",
             previous_button: "Previous",
             next_button: "Next",
             page_indicator: "Page {current} of {total}",
+            load_more: "Load more",
+            filter_all: "All",
+            filter_min: "Min",
+            filter_max: "Max",
+            select_row: "Select row",
+            select_all: "Select all rows",
+            expand_row: "Show details",
+            expand_all: "Show all details",
         }
     }
 }
@@ -93,6 +247,9 @@ pub struct TableClasses {
     /// Class for the search input field.
     pub search_input: &'static str,
 
+    /// Class for the `<mark>` around characters matched by a fuzzy search.
+    pub search_highlight: &'static str,
+
     /// Class for header cells (`<th>`).
     pub header_cell: &'static str,
 
@@ -108,8 +265,53 @@ pub struct TableClasses {
     /// Class for the row shown when no data is found.
     pub empty_row: &'static str,
 
+    /// Class for the row shown when the data source failed.
+    pub error_row: &'static str,
+
+    /// Class for the retry button in the error row.
+    pub retry_button: &'static str,
+
     /// Class for pagination buttons.
     pub pagination_button: &'static str,
+
+    /// Class for the "Load more" button.
+    pub load_more_button: &'static str,
+
+    /// Class for the empty row after the last one that loads more rows when scrolled into view.
+    pub sentinel_row: &'static str,
+
+    /// Class for the scrolling area wrapping a virtualized table.
+    pub scroll_container: &'static str,
+
+    /// Class for the empty rows that take the place of the rows scrolled out of a virtualized table.
+    pub spacer_row: &'static str,
+
+    /// Class for the sort priority badge shown when sorting by several columns.
+    pub sort_badge: &'static str,
+
+    /// Class for the filter row under the header row.
+    pub filter_row: &'static str,
+
+    /// Class for the cells of the filter row.
+    pub filter_cell: &'static str,
+
+    /// Class for the filter inputs and drop-downs.
+    pub filter_input: &'static str,
+
+    /// Class added to the selected rows.
+    pub selected_row: &'static str,
+
+    /// Class for the cells holding the selection checkboxes.
+    pub select_cell: &'static str,
+
+    /// Class for the cells holding the expand buttons.
+    pub expand_cell: &'static str,
+
+    /// Class for the buttons showing and hiding the detail panel of a row.
+    pub expand_button: &'static str,
+
+    /// Class for the rows holding the detail panel of an expanded row.
+    pub detail_row: &'static str,
 }
 
 impl Default for TableClasses {
@@ -121,22 +323,56 @@ impl Default for TableClasses {
             tbody: "tbody",
             pagination: "pagination-controls",
             search_input: "search-input",
+            search_highlight: "search-highlight",
             header_cell: "th",
             body_cell: "td",
             row: "tr",
             loading_row: "loading-row",
             empty_row: "empty-row",
+            error_row: "error-row",
+            retry_button: "retry-button",
             pagination_button: "pagination-button",
+            load_more_button: "load-more-button",
+            sentinel_row: "sentinel-row",
+            scroll_container: "table-scroll",
+            spacer_row: "spacer-row",
+            sort_badge: "sort-priority",
+            filter_row: "filter-row",
+            filter_cell: "filter-cell",
+            filter_input: "filter-input",
+            selected_row: "selected-row",
+            select_cell: "select-cell",
+            expand_cell: "expand-cell",
+            expand_button: "expand-button",
+            detail_row: "detail-row",
         }
     }
 }
 
 /// Main props for the table component.
+///
+/// Rows can be any type implementing [`TableRow`]; the default keeps the key-value map form.
 #[derive(PartialEq, Props, Clone)]
-pub struct TableProps {
-    /// Data rows, where each row is a key-value map.
+pub struct TableProps<T = HashMap<&'static str, String>>
+where
+    T: TableRow + Clone + PartialEq + 'static,
+{
+    /// Data rows, either key-value maps or any type implementing `TableRow`.
+    ///
+    /// A plain `Vec<T>` converts into the signal, which the table reads without cloning the rows
+    /// on every render.
+    #[props(default = ReadSignal::new(Signal::new(Vec::new())))]
+    pub data: ReadSignal<Vec<T>>,
+
+    /// Remote source of rows that replaces `data`, for data sets too large to load at once.
+    ///
+    /// Searching, filtering, sorting and paging are then left to the source.
+    #[props(default)]
+    pub data_source: Option<DataSourceHandle<T>>,
+
+    /// How failed `data_source` fetches are retried before the error row is shown.
     #[props(default)]
-    pub data: Vec<HashMap<&'static str, String>>,
+    pub retry: RetryPolicy,
 
     /// Definitions of columns to display.
     #[props(default)]
@@ -154,10 +390,96 @@ pub struct TableProps {
     #[props(default = false)]
     pub paginate: bool,
 
+    /// Whether pages are flipped with Previous/Next, or appended with a "Load more" button or by
+    /// scrolling to the end of the table.
+    #[props(default)]
+    pub pagination_mode: PaginationMode,
+
+    /// Renders only the rows scrolled into view, inside a scrolling area with a sticky header.
+    ///
+    /// Paging still applies, so pass a `page_size` covering every row to scroll them all.
+    #[props(default)]
+    pub virtualize: Option<VirtualScroll>,
+
+    /// Renders only the columns scrolled into view, plus the pinned ones, inside a scrolling area.
+    #[props(default)]
+    pub virtualize_columns: Option<VirtualColumns>,
+
     /// Enables the search input field.
     #[props(default = false)]
     pub search: bool,
 
+    /// How the search input is read: plain substring text, or a structured `field:value` query.
+    #[props(default)]
+    pub search_syntax: SearchSyntax,
+
+    /// How the search input and the cells are normalized (case, accents, punctuation) before matching.
+    #[props(default)]
+    pub search_options: SearchOptions,
+
+    /// Row keys searched in addition to the searchable columns, such as tags or ids kept off screen.
+    #[props(default)]
+    pub search_fields: Vec<&'static str>,
+
+    /// Normalizes the searched cells once into a `SearchIndex` when `data` changes, indexing
+    /// appended rows on their own, so that plain searches of large data stay fast.
+    #[props(default)]
+    pub search_index: Option<SearchIndexKind>,
+
+    /// Searches, filters and sorts `data` in a Web Worker instead of on the main thread, showing
+    /// the loading row while it works. Columns with custom `Values` or `Rows` comparators are
+    /// ordered on the main thread as before.
+    #[props(default)]
+    pub worker: Option<ViewWorker>,
+
+    /// Searches, filters and sorts `data` a slice at a time between frames, showing the loading
+    /// row until done, so that typing stays responsive on large data without a worker.
+    #[props(default)]
+    pub time_slicing: Option<TimeSlicing>,
+
+    /// Sort applied when the table first renders, highest priority first.
+    #[props(default)]
+    pub default_sort: Vec<SortKey>,
+
+    /// Whether a third click on a sorted header unsorts it, restoring the original data order.
+    #[props(default = false)]
+    pub allow_unsorted: bool,
+
+    /// How rows are told apart, keying their `<tr>` and identifying them in the selection;
+    /// by default their index in `data`, or their position among the results of `data_source`.
+    #[props(default)]
+    pub row_key: Option<RowKey>,
+
+    /// Whether rows can be selected, adding a checkbox column.
+    #[props(default)]
+    pub selection: SelectionMode,
+
+    /// Whether the "select all" checkbox covers every row matching the search and the filters,
+    /// on all pages, rather than the rows of the current page.
+    #[props(default = false)]
+    pub select_all_filtered: bool,
+
+    /// Called with the keys of the selected rows whenever the selection changes.
+    #[props(default)]
+    pub on_selection_change: EventHandler<Vec<String>>,
+
+    /// Whether rows can be expanded, adding a column of buttons showing their detail panel.
+    #[props(default = false)]
+    pub expandable: bool,
+
+    /// Renders the detail panel of an expanded row, shown in a full-width row beneath it.
+    #[props(default)]
+    pub render_detail: Option<Callback<T, Element>>,
+
+    /// Keys of the expanded rows, when the parent controls the expansion; otherwise the table
+    /// keeps track of it.
+    #[props(default)]
+    pub expanded: Option<Vec<String>>,
+
+    /// Called with the keys of the expanded rows whenever a row is expanded or collapsed.
+    #[props(default)]
+    pub on_expand_change: EventHandler<Vec<String>>,
+
     /// Texts for various table UI messages.
     #[props(default)]
     pub texts: TableTexts,
@@ -166,13 +488,3 @@ pub struct TableProps {
     #[props(default)]
     pub classes: TableClasses,
 }
-
-/// Sort direction (ascending or descending).
-#[derive(PartialEq, Clone, Copy, Default)]
-pub enum SortOrder {
-    /// Ascending (default).
-    #[default]
-    Asc,
-    /// Descending.
-    Desc,
-}
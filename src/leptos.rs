@@ -1 +1,7 @@
+#![doc = include_str!("../LEPTOS.md")]
 
+pub mod body;
+pub mod controls;
+pub mod header;
+pub mod table;
+pub mod types;
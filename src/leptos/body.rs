@@ -0,0 +1,419 @@
+use crate::core::{SearchOptions, TableRow, fuzzy_highlight};
+use crate::leptos::types::Column;
+use crate::leptos::types::PaginationMode;
+use crate::leptos::types::TableClasses;
+use crate::leptos::types::TableTexts;
+use crate::leptos::types::VirtualWindow;
+use crate::leptos::types::{ColumnSlot, ColumnWindow};
+use crate::leptos::types::{Selection, SelectionMode};
+use leptos::callback::{Callable, Callback};
+use leptos::html::Tr;
+use leptos::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use web_sys::js_sys::Array;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+/// A table body component that renders rows of data, along with loading and empty states.
+///
+/// This component is responsible for rendering the `<tbody>` section of a table in a Leptos application.
+/// It reactively displays row data, a loading message, or an empty state message based on the provided signals.
+///
+/// # Props
+/// - `columns`: A `Vec<Column>` defining which fields to render in each table row. Each column corresponds to a key in the row data.
+/// - `rows`: A `Signal<Vec<T>>` of rows implementing `TableRow` (e.g. `HashMap<&'static str, String>`), whose cells are looked up by column ID; when virtualized, only the rows of `virtual_window`.
+/// - `loading`: A `Signal<bool>` that, when true, displays a loading message instead of data rows.
+/// - `classes`: A `TableClasses` struct for customizing the CSS class names of the body, rows, and cells.
+/// - `texts`: A `TableTexts` struct that provides custom text for the loading and empty states.
+/// - `highlight`: A `Signal<String>` fuzzy search query whose matched characters are wrapped in `<mark>`, unless the column has a custom `cell` renderer (default: empty).
+/// - `search_options`: The `SearchOptions` used to match `highlight` (default: `SearchOptions::default()`).
+/// - `pagination_mode`: A `PaginationMode`; the appending modes keep the rows visible while more load (default: `Pages`).
+/// - `has_more`: A `Signal<bool>` telling whether more rows can be loaded after the ones shown (default: `false`).
+/// - `on_load_more`: An optional `Callback<()>` run when the sentinel row of `InfiniteScroll` scrolls into view.
+/// - `virtual_window`: A `Signal<Option<VirtualWindow>>` whose range places `rows` among all the rows, with spacer rows of the given heights above and below them (default: `None`).
+/// - `on_measure`: An optional `Callback<(usize, f64)>` run with the position and height of each row once it is mounted.
+/// - `column_window`: A `Signal<Option<ColumnWindow>>` restricting each row to the cells of the pinned columns and those in view, between spacer cells (default: `None`).
+/// - `selection`: A `SelectionMode`; unless `None`, each row starts with a checkbox cell (default: `None`).
+/// - `row_keys`: A `Signal<Vec<String>>` holding the key of each row of `rows`, which keys its `<tr>`; rows without one are keyed by position (default: empty).
+/// - `selected`: A `Signal<Selection>` of the selected rows, which get `aria-selected="true"` and the `selected_row` class (default: none selected).
+/// - `on_select`: An optional `Callback<(String, bool)>` run by the checkbox of a row with its key and whether shift was held.
+/// - `expandable`: A `bool` starting each row with a button that shows or hides its detail panel, with `aria-expanded` telling which (default: `false`).
+/// - `expanded`: A `Signal<BTreeSet<String>>` of the keys of the expanded rows (default: empty).
+/// - `on_expand`: An optional `Callback<String>` run by the expand button of a row with its key.
+/// - `render_detail`: An optional `Callback<T, AnyView>` rendering the detail panel of an expanded row, shown in a full-width row beneath it.
+///
+/// # Behavior
+/// - If `loading` is `true`, a single row with a loading message is shown spanning all columns.
+/// - If `rows` is empty and not loading, an empty message row is displayed.
+/// - Otherwise, each data row is rendered in a `<tr>`, with one `<td>` per column and its position in `data-row`.
+/// - Rows are rendered with `<For>` by key, so each `<tr>` stays in the DOM as rows move, and only its cells update with the data.
+/// - When rows can be selected, each row starts with a checkbox cell, and the status rows span it too.
+/// - Each expanded row is followed by a row holding its detail panel.
+/// - When virtualized, spacer rows above and below the rendered rows keep the scrollbar in proportion.
+/// - In the appending modes, the loading row follows the rows instead of replacing them, and with
+///   `InfiniteScroll` a sentinel row after them runs `on_load_more` when it scrolls into view.
+///
+/// # Returns
+/// A Leptos view representing the `<tbody>` of a table, with reactive row content.
+///
+/// # Example
+/// ```rust
+/// use leptos::prelude::*;
+/// use maplit::hashmap;
+/// use table_rs::leptos::body::TableBody;
+/// use table_rs::leptos::types::{Column, TableClasses, TableTexts};
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let rows = vec![
+///         hashmap! { "name" => "Ferris".to_string(), "email" => "ferris@opensass.org".to_string() },
+///         hashmap! { "name" => "Rustacean".to_string(), "email" => "rust@opensass.org".to_string() },
+///     ];
+///
+///     let columns = vec![
+///         Column { id: "name", header: "Name", ..Default::default() },
+///         Column { id: "email", header: "Email", ..Default::default() },
+///     ];
+///
+///     view! {
+///         <TableBody
+///             columns=columns
+///             rows=rows
+///             loading=false
+///             classes=TableClasses::default()
+///             texts=TableTexts::default()
+///         />
+///     }
+/// }
+/// ```
+///
+/// # See Also
+/// - [MDN `<tbody>` Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/tbody)
+#[component]
+pub fn TableBody<T>(
+    columns: Vec<Column>,
+    #[prop(into)] rows: Signal<Vec<T>>,
+    #[prop(optional, into)] loading: Signal<bool>,
+    #[prop(optional)] classes: TableClasses,
+    #[prop(optional)] texts: TableTexts,
+    #[prop(optional, into)] highlight: Signal<String>,
+    #[prop(optional)] search_options: SearchOptions,
+    #[prop(optional)] pagination_mode: PaginationMode,
+    #[prop(optional, into)] has_more: Signal<bool>,
+    #[prop(optional)] on_load_more: Option<Callback<()>>,
+    #[prop(optional, into)] virtual_window: Signal<Option<VirtualWindow>>,
+    #[prop(default = None)] on_measure: Option<Callback<(usize, f64)>>,
+    #[prop(optional, into)] column_window: Signal<Option<ColumnWindow>>,
+    #[prop(optional)] selection: SelectionMode,
+    #[prop(optional, into)] row_keys: Signal<Vec<String>>,
+    #[prop(optional, into)] selected: Signal<Selection>,
+    #[prop(optional)] on_select: Option<Callback<(String, bool)>>,
+    #[prop(optional)] expandable: bool,
+    #[prop(optional, into)] expanded: Signal<BTreeSet<String>>,
+    #[prop(optional)] on_expand: Option<Callback<String>>,
+    #[prop(default = None)] render_detail: Option<Callback<T, AnyView>>,
+) -> impl IntoView
+where
+    T: TableRow + Clone + Send + Sync + 'static,
+{
+    let column_count = columns.len();
+    let columns = StoredValue::new(columns);
+    let slots = Memo::new(move |_| {
+        column_window.with(|window| match window {
+            Some(window) => window.slots.clone(),
+            None => ColumnSlot::all(column_count),
+        })
+    });
+    let colspan = Memo::new(move |_| {
+        let leading = usize::from(expandable) + usize::from(selection.is_enabled());
+        (slots.with(Vec::len) + leading).to_string()
+    });
+    // In the appending modes the rows stay put while the next page loads below them
+    let appending = move || pagination_mode.appends() && !rows.with(Vec::is_empty);
+    let show_rows = move || appending() || !(loading.get() || rows.with(Vec::is_empty));
+    let first = Memo::new(move |_| {
+        virtual_window.with(|window| window.as_ref().map_or(0, |window| window.range.start))
+    });
+
+    // Rows are keyed by `row_keys`, or else by position, so that each `<tr>` follows its row as
+    // rows are sorted and paged; a row looks its data up by key, wherever it moved
+    let keys = Memo::new(move |_| {
+        let first = first.get();
+        let len = rows.with(Vec::len);
+        row_keys.with(|keys| {
+            (0..len)
+                .map(|idx| {
+                    keys.get(idx)
+                        .cloned()
+                        .unwrap_or_else(|| (first + idx).to_string())
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let positions = Memo::new(move |_| {
+        keys.with(|keys| {
+            keys.iter()
+                .enumerate()
+                .map(|(idx, key)| (key.clone(), idx))
+                .collect::<HashMap<_, _>>()
+        })
+    });
+
+    let status = move || {
+        if loading.get() {
+            view! {
+                <tr class=classes.loading_row>
+                    <td colspan=colspan>{texts.loading}</td>
+                </tr>
+            }
+            .into_any()
+        } else if rows.with(Vec::is_empty) {
+            view! {
+                <tr class=classes.empty_row>
+                    <td colspan=colspan>{texts.empty}</td>
+                </tr>
+            }
+            .into_any()
+        } else {
+            ().into_any()
+        }
+    };
+    let sentinel = move || {
+        on_load_more
+            .filter(|_| {
+                appending()
+                    && pagination_mode == PaginationMode::InfiniteScroll
+                    && has_more.get()
+                    && !loading.get()
+            })
+            .map(|on_visible| {
+                view! {
+                    <LoadMoreSentinel colspan=colspan.get() class=classes.sentinel_row on_visible=on_visible />
+                }
+            })
+    };
+
+    let spacer = move |height: f64| {
+        (show_rows() && height > 0.0).then(|| {
+            view! {
+                <tr class=classes.spacer_row aria-hidden="true" style=format!("height: {height}px;")>
+                    <td colspan=colspan style="padding: 0; border: 0;"></td>
+                </tr>
+            }
+        })
+    };
+    let before = move || {
+        spacer(virtual_window.with(|window| window.as_ref().map_or(0.0, |window| window.before)))
+    };
+    let after = move || {
+        spacer(virtual_window.with(|window| window.as_ref().map_or(0.0, |window| window.after)))
+    };
+
+    let body_row = move |key: String| {
+        let index = Memo::new({
+            let key = key.clone();
+            move |_| positions.with(|positions| positions.get(&key).copied())
+        });
+        let position = move || first.get() + index.get().unwrap_or_default();
+        // Only the rows whose state changed update when the selection does
+        let checked = {
+            let key = key.clone();
+            move || selection.is_enabled() && selected.with(|selected| selected.contains(&key))
+        };
+        let is_expanded = {
+            let key = key.clone();
+            move || expandable && expanded.with(|expanded| expanded.contains(&key))
+        };
+        let expand_cell = expandable.then(|| {
+            let key = key.clone();
+            let chevron = is_expanded.clone();
+            let aria_expanded = is_expanded.clone();
+            view! {
+                <td class=classes.expand_cell role="cell">
+                    <button
+                        class=classes.expand_button
+                        type="button"
+                        aria-expanded=move || aria_expanded().to_string()
+                        aria-label=texts.expand_row
+                        on:click=move |_| {
+                            if let Some(on_expand) = on_expand {
+                                on_expand.run(key.clone());
+                            }
+                        }
+                    >
+                        {move || if chevron() { "▾" } else { "▸" }}
+                    </button>
+                </td>
+            }
+        });
+        let select_cell = selection.is_enabled().then(|| {
+            view! {
+                <td class=classes.select_cell role="cell">
+                    <input
+                        type="checkbox"
+                        prop:checked=checked.clone()
+                        aria-label=texts.select_row
+                        on:click=move |ev| {
+                            if let Some(on_select) = on_select {
+                                on_select.run((key.clone(), ev.shift_key()));
+                            }
+                        }
+                    />
+                </td>
+            }
+        });
+        let row_class = {
+            let checked = checked.clone();
+            move || {
+                if checked() {
+                    format!("{} {}", classes.row, classes.selected_row)
+                } else {
+                    classes.row.to_string()
+                }
+            }
+        };
+        let aria_selected = move || selection.is_enabled().then(|| checked().to_string());
+        let node = NodeRef::<Tr>::new();
+        if let Some(on_measure) = on_measure {
+            // Heights are kept by position, so the row is measured again wherever it moves
+            Effect::new(move |_| {
+                let position = position();
+                if let Some(row) = node.get() {
+                    on_measure.run((position, row.get_bounding_client_rect().height()));
+                }
+            });
+        }
+        let cells = move || {
+            let highlight = highlight.get();
+            rows.with(|rows| {
+                let row = rows.get(index.get()?)?;
+                let cells = slots.with(|slots| {
+                    columns.with_value(|columns| {
+                        slots
+                            .iter()
+                            .map(|slot| {
+                                let ColumnSlot::Column { index, .. } = *slot else {
+                                    return view! { <td aria-hidden="true" style=slot.style()></td> }.into_any();
+                                };
+                                let col = &columns[index];
+                                let content = row.cell(col.id).unwrap_or_default().into_owned();
+                                let cell = match col.cell {
+                                    Some(cb) => cb.run(content),
+                                    None if !col.searchable || highlight.trim().is_empty() => content.into_any(),
+                                    None => fuzzy_highlight(&content, &highlight, &search_options)
+                                        .into_iter()
+                                        .map(|(segment, matched)| {
+                                            let segment = segment.to_string();
+                                            if matched {
+                                                view! { <mark class=classes.search_highlight>{segment}</mark> }
+                                                    .into_any()
+                                            } else {
+                                                segment.into_any()
+                                            }
+                                        })
+                                        .collect_view()
+                                        .into_any(),
+                                };
+                                view! {
+                                    <td class=classes.body_cell role="cell" style=slot.style()>
+                                        {cell}
+                                    </td>
+                                }
+                                .into_any()
+                            })
+                            .collect_view()
+                    })
+                });
+                Some(cells)
+            })
+        };
+        let detail = render_detail.map(|render| {
+            move || {
+                if !is_expanded() {
+                    return None;
+                }
+                let row = rows.with(|rows| rows.get(index.get()?).cloned())?;
+                Some(view! {
+                    <tr class=classes.detail_row role="row">
+                        <td colspan=colspan role="cell">{render.run(row)}</td>
+                    </tr>
+                })
+            }
+        });
+
+        view! {
+            <tr
+                node_ref=node
+                class=row_class
+                role="row"
+                data-row=position
+                aria-selected=aria_selected
+            >
+                {expand_cell}
+                {select_cell}
+                {cells}
+            </tr>
+            {detail}
+        }
+    };
+
+    view! {
+        <tbody class=classes.tbody>
+            {before}
+            <For
+                each=move || if show_rows() { keys.get() } else { Vec::new() }
+                key=|key| key.clone()
+                children=body_row
+            />
+            {after}
+            {status}
+            {sentinel}
+        </tbody>
+    }
+}
+
+/// An empty row that runs `on_visible` whenever it scrolls into view, using an
+/// `IntersectionObserver`.
+///
+/// It is rebuilt with the rows, and a new observer reports it right away if it is still
+/// visible, so short pages keep loading until the viewport is filled.
+#[component]
+fn LoadMoreSentinel(
+    colspan: String,
+    class: &'static str,
+    on_visible: Callback<()>,
+) -> impl IntoView {
+    let node = NodeRef::<Tr>::new();
+    let observer = StoredValue::new_local(None::<(IntersectionObserver, Closure<dyn Fn(Array)>)>);
+
+    node.on_load(move |row| {
+        let callback = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+            let visible = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<IntersectionObserverEntry>()
+                    .is_intersecting()
+            });
+            if visible {
+                on_visible.run(());
+            }
+        });
+        if let Ok(intersection) = IntersectionObserver::new(callback.as_ref().unchecked_ref()) {
+            intersection.observe(&row);
+            observer.set_value(Some((intersection, callback)));
+        }
+    });
+    on_cleanup(move || {
+        observer.try_update_value(|observer| {
+            if let Some((intersection, _)) = observer.take() {
+                intersection.disconnect();
+            }
+        });
+    });
+
+    view! {
+        <tr node_ref=node class=class aria-hidden="true">
+            <td colspan=colspan></td>
+        </tr>
+    }
+}
@@ -0,0 +1,73 @@
+use crate::leptos::types::PaginationMode;
+use crate::leptos::types::TableClasses;
+use crate::leptos::types::TableTexts;
+use leptos::prelude::*;
+
+#[component]
+pub fn PaginationControls(
+    page: RwSignal<usize>,
+    #[prop(into)] total_pages: Signal<usize>,
+    #[prop(optional)] classes: TableClasses,
+    #[prop(optional)] texts: TableTexts,
+    #[prop(optional)] pagination_mode: PaginationMode,
+) -> impl IntoView {
+    let on_prev = move |_| {
+        if page.get() > 0 {
+            page.update(|p| *p -= 1);
+        }
+    };
+
+    let on_next = move |_| {
+        if page.get() + 1 < total_pages.get() {
+            page.update(|p| *p += 1);
+        }
+    };
+
+    match pagination_mode {
+        PaginationMode::Pages => {}
+        PaginationMode::LoadMore => {
+            return view! {
+                <div class=classes.pagination>
+                    <button
+                        class=classes.load_more_button
+                        on:click=on_next
+                        disabled=move || page.get() + 1 >= total_pages.get()
+                    >
+                        {texts.load_more}
+                    </button>
+                </div>
+            }
+            .into_any();
+        }
+        // The sentinel row at the end of the body loads the next page
+        PaginationMode::InfiniteScroll => return ().into_any(),
+    }
+
+    let page_indicator_text = move || {
+        texts
+            .page_indicator
+            .replace("{current}", &(page.get() + 1).to_string())
+            .replace("{total}", &total_pages.get().to_string())
+    };
+
+    view! {
+        <div class=classes.pagination>
+            <button
+                class=classes.pagination_button
+                on:click=on_prev
+                disabled=move || page.get() == 0
+            >
+                {texts.previous_button}
+            </button>
+            <span>{page_indicator_text}</span>
+            <button
+                class=classes.pagination_button
+                on:click=on_next
+                disabled=move || page.get() + 1 >= total_pages.get()
+            >
+                {texts.next_button}
+            </button>
+        </div>
+    }
+    .into_any()
+}
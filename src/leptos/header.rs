@@ -0,0 +1,342 @@
+use crate::core::sort_key_position;
+use crate::leptos::types::Column;
+use crate::leptos::types::SortKey;
+use crate::leptos::types::TableClasses;
+use crate::leptos::types::{CheckState, SelectionMode};
+use crate::leptos::types::{ColumnFilter, FilterValue, TableTexts};
+use crate::leptos::types::{ColumnSlot, ColumnWindow};
+use leptos::callback::{Callable, Callback};
+use leptos::prelude::*;
+use std::collections::BTreeMap;
+
+/// A table header component that renders sortable column headers for use within the `Table` component.
+///
+/// This component produces the `<thead>` section of a table using the provided column definitions,
+/// handling rendering, sorting indicators (`aria-sort`), and user interaction to trigger sort changes.
+///
+/// # Props
+/// - `columns`: A `Vec<Column>` defining the columns to display in the header. Each `Column` may be sortable and have optional styles or class overrides.
+/// - `sort`: A `Signal<Vec<SortKey>>` of the sorted columns and their directions, highest priority first.
+/// - `on_sort_column`: A `Callback<(&'static str, bool)>` triggered when a sortable header cell is clicked. The column ID and whether shift was held (to add a secondary sort key) are passed as the event payload.
+/// - `filters`: An optional `Signal<BTreeMap<&'static str, FilterValue>>` of the current filter inputs by column id.
+/// - `on_filter`: An optional `Callback<(&'static str, FilterValue)>` triggered when a filter input changes.
+/// - `classes`: A `TableClasses` struct allowing custom class names for `<thead>`, `<tr>`, and `<th>` elements.
+/// - `texts`: A `TableTexts` struct providing the filter row labels.
+/// - `sticky`: A `bool` keeping the header at the top of the scrolling area while rows scroll under it (default: `false`).
+/// - `column_window`: A `Signal<Option<ColumnWindow>>` restricting the header and filter cells to the pinned columns and those in view, between spacer cells (default: `None`).
+/// - `selection`: A `SelectionMode`; unless `None`, a cell is added before the headers, holding a "select all" checkbox in `Multiple` mode (default: `None`).
+/// - `select_all`: A `Signal<CheckState>` of the "select all" checkbox, shown as indeterminate when only some rows are selected (default: `Unchecked`).
+/// - `on_select_all`: An optional `Callback<bool>` run by the "select all" checkbox with whether to select the rows it covers.
+/// - `expandable`: A `bool` adding a first cell with an "expand all" button, whose `aria-expanded` tells whether every row of the page is expanded (default: `false`).
+/// - `all_expanded`: A `Signal<bool>` telling whether every row of the page is expanded (default: `false`).
+/// - `on_expand_all`: An optional `Callback<bool>` run by the "expand all" button with whether to expand the rows of the page.
+///
+/// # Behavior
+/// - The primary sort column shows the proper `aria-sort` attribute for accessibility (`ascending` or `descending`); every other column has `none`, as ARIA recommends a single sorted header.
+/// - Clicking a sortable column emits an event to update sort state; shift-clicking adds or toggles a secondary sort key.
+/// - When sorting by several columns, each sorted header shows its priority in a badge.
+/// - Columns with a `filter` get a matching input (text box, drop-down, or min/max pair) in a filter row under the headers.
+/// - Each column can override default styles and classes via `Column::style` and `Column::class`.
+///
+/// # Returns
+/// Returns a Leptos view containing the `<thead>` with all column headers rendered as `<th>` elements.
+///
+/// # Example
+/// ```rust
+/// use leptos::callback::Callback;
+/// use leptos::prelude::*;
+/// use table_rs::leptos::header::TableHeader;
+/// use table_rs::leptos::types::{Column, SortOrder, TableClasses};
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let columns = vec![
+///         Column { id: "name", header: "Name", sortable: true, ..Default::default() },
+///         Column { id: "email", header: "Email", sortable: false, ..Default::default() },
+///     ];
+///
+///     let sort = RwSignal::new(vec![("name", SortOrder::Asc)]);
+///
+///     view! {
+///         <TableHeader
+///             columns=columns
+///             sort=sort
+///             on_sort_column=Callback::new(|(col_id, additive): (&'static str, bool)| {
+///                 leptos::logging::log!("Sort column changed: {} (shift: {})", col_id, additive)
+///             })
+///             classes=TableClasses::default()
+///         />
+///     }
+/// }
+/// ```
+///
+/// # See Also
+/// - [MDN `<thead>` Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/thead)
+#[component]
+pub fn TableHeader(
+    columns: Vec<Column>,
+    #[prop(into)] sort: Signal<Vec<SortKey>>,
+    #[prop(into)] on_sort_column: Callback<(&'static str, bool)>,
+    #[prop(optional, into)] filters: Signal<BTreeMap<&'static str, FilterValue>>,
+    #[prop(optional)] on_filter: Option<Callback<(&'static str, FilterValue)>>,
+    #[prop(optional)] classes: TableClasses,
+    #[prop(optional)] texts: TableTexts,
+    #[prop(optional)] sticky: bool,
+    #[prop(optional, into)] column_window: Signal<Option<ColumnWindow>>,
+    #[prop(optional)] selection: SelectionMode,
+    #[prop(optional, into)] select_all: Signal<CheckState>,
+    #[prop(optional)] on_select_all: Option<Callback<bool>>,
+    #[prop(optional)] expandable: bool,
+    #[prop(optional, into)] all_expanded: Signal<bool>,
+    #[prop(optional)] on_expand_all: Option<Callback<bool>>,
+) -> impl IntoView {
+    let style = if sticky {
+        "position: sticky; top: 0; z-index: 2;"
+    } else {
+        ""
+    };
+    let has_filters = columns.iter().any(|col| col.filter.is_some());
+    let column_count = columns.len();
+    let slots = move || {
+        column_window.with(|window| match window {
+            Some(window) => window.slots.clone(),
+            None => ColumnSlot::all(column_count),
+        })
+    };
+
+    let filter_columns = columns.clone();
+    let filter_cells = move || {
+        slots()
+            .into_iter()
+            .map(|slot| match slot {
+                ColumnSlot::Column { index, .. } => {
+                    let widget =
+                        filter_widget(&filter_columns[index], filters, on_filter, classes, texts);
+                    view! { <td class=classes.filter_cell style=slot.style()>{widget}</td> }
+                        .into_any()
+                }
+                ColumnSlot::Spacer(_) => {
+                    view! { <td aria-hidden="true" style=slot.style()></td> }.into_any()
+                }
+            })
+            .collect_view()
+    };
+
+    let header_cells = move || {
+        slots()
+            .into_iter()
+            .map(|slot| {
+                let ColumnSlot::Column { index, .. } = slot else {
+                    return view! { <th aria-hidden="true" style=slot.style()></th> }.into_any();
+                };
+                let col = columns[index].clone();
+                let col_id = col.id;
+                let position = move || sort.with(|sort| sort_key_position(sort, col_id));
+                let aria_sort = move || match position() {
+                    Some((0, order)) => order.aria_sort(),
+                    _ => "none",
+                };
+                let priority = move || {
+                    let multi_sort = sort.with(|sort| sort.len() > 1);
+                    position().filter(|_| multi_sort).map(|(idx, _)| idx + 1)
+                };
+
+                let class = format!("{} {}", classes.header_cell, col.class.unwrap_or_default());
+                let style = format!("{} {}", col.style.unwrap_or_default(), slot.style());
+                let sortable = col.sortable;
+
+                view! {
+                    <th
+                        role="columnheader"
+                        class=class.trim().to_string()
+                        style=style.trim().to_string()
+                        aria-sort=aria_sort
+                        on:click=move |ev| {
+                            if sortable {
+                                on_sort_column.run((col_id, ev.shift_key()));
+                            }
+                        }
+                    >
+                        {col.header}
+                        {move || {
+                            priority()
+                                .map(|priority| {
+                                    view! {
+                                        <span
+                                            class=classes.sort_badge
+                                            aria-label=format!("sort priority {priority}")
+                                        >
+                                            {priority}
+                                        </span>
+                                    }
+                                })
+                        }}
+                    </th>
+                }
+                .into_any()
+            })
+            .collect_view()
+    };
+
+    let select_all_cell = match selection {
+        SelectionMode::None => ().into_any(),
+        SelectionMode::Single => {
+            view! { <th class=classes.select_cell role="columnheader"></th> }.into_any()
+        }
+        SelectionMode::Multiple => {
+            let checked = move || select_all.get() == CheckState::Checked;
+            view! {
+                <th class=classes.select_cell role="columnheader">
+                    <input
+                        type="checkbox"
+                        prop:checked=checked
+                        prop:indeterminate=move || select_all.get() == CheckState::Indeterminate
+                        aria-checked=move || select_all.get().aria_checked()
+                        aria-label=texts.select_all
+                        on:click=move |_| {
+                            if let Some(on_select_all) = on_select_all {
+                                on_select_all.run(!checked());
+                            }
+                        }
+                    />
+                </th>
+            }
+            .into_any()
+        }
+    };
+
+    let expand_all_cell = expandable.then(|| {
+        view! {
+            <th class=classes.expand_cell role="columnheader">
+                <button
+                    class=classes.expand_button
+                    type="button"
+                    aria-expanded=move || all_expanded.get().to_string()
+                    aria-label=texts.expand_all
+                    on:click=move |_| {
+                        if let Some(on_expand_all) = on_expand_all {
+                            on_expand_all.run(!all_expanded.get());
+                        }
+                    }
+                >
+                    {move || if all_expanded.get() { "▾" } else { "▸" }}
+                </button>
+            </th>
+        }
+    });
+    let leading_cells = usize::from(expandable) + usize::from(selection.is_enabled());
+
+    view! {
+        <thead class=classes.thead style=style>
+            <tr class=classes.row role="row">
+                {expand_all_cell}
+                {select_all_cell}
+                {header_cells}
+            </tr>
+            {has_filters.then(|| view! {
+                <tr class=classes.filter_row role="row">
+                    {(0..leading_cells).map(|_| view! { <td class=classes.filter_cell></td> }).collect_view()}
+                    {filter_cells}
+                </tr>
+            })}
+        </thead>
+    }
+}
+
+/// Renders the input widget(s) matching the column's `ColumnFilter`, or nothing without one.
+fn filter_widget(
+    col: &Column,
+    filters: Signal<BTreeMap<&'static str, FilterValue>>,
+    on_filter: Option<Callback<(&'static str, FilterValue)>>,
+    classes: TableClasses,
+    texts: TableTexts,
+) -> AnyView {
+    let id = col.id;
+    let label = format!("Filter {}", col.header);
+    let value = move || filters.with(|filters| filters.get(id).cloned().unwrap_or_default());
+    let emit = move |value: FilterValue| {
+        if let Some(on_filter) = on_filter {
+            on_filter.run((id, value));
+        }
+    };
+
+    match col.filter {
+        None => ().into_any(),
+        Some(ColumnFilter::Text | ColumnFilter::Equals) => view! {
+            <input
+                class=classes.filter_input
+                type="text"
+                prop:value=move || value().text().to_string()
+                aria-label=label
+                on:input=move |ev| emit(FilterValue::Text(event_target_value(&ev)))
+            />
+        }
+        .into_any(),
+        Some(ColumnFilter::Select(options)) => {
+            let option_views = options
+                .iter()
+                .map(|&option| {
+                    view! {
+                        <option value=option selected=move || value().text() == option>
+                            {option}
+                        </option>
+                    }
+                })
+                .collect_view();
+
+            view! {
+                <select
+                    class=classes.filter_input
+                    aria-label=label
+                    on:change=move |ev| emit(FilterValue::Text(event_target_value(&ev)))
+                >
+                    <option value="" selected=move || value().text().is_empty()>
+                        {texts.filter_all}
+                    </option>
+                    {option_views}
+                </select>
+            }
+            .into_any()
+        }
+        Some(filter @ (ColumnFilter::NumberRange | ColumnFilter::DateRange)) => {
+            let input_type = if filter == ColumnFilter::NumberRange {
+                "number"
+            } else {
+                "date"
+            };
+            let bound = move |upper: bool| {
+                let value = value();
+                let (min, max) = value.range();
+                if upper {
+                    max.to_string()
+                } else {
+                    min.to_string()
+                }
+            };
+
+            view! {
+                <input
+                    class=classes.filter_input
+                    type=input_type
+                    prop:value=move || bound(false)
+                    placeholder=texts.filter_min
+                    aria-label=format!("{label} ({})", texts.filter_min)
+                    on:input=move |ev| {
+                        emit(FilterValue::Range { min: event_target_value(&ev), max: bound(true) })
+                    }
+                />
+                <input
+                    class=classes.filter_input
+                    type=input_type
+                    prop:value=move || bound(true)
+                    placeholder=texts.filter_max
+                    aria-label=format!("{label} ({})", texts.filter_max)
+                    on:input=move |ev| {
+                        emit(FilterValue::Range { min: bound(false), max: event_target_value(&ev) })
+                    }
+                />
+            }
+            .into_any()
+        }
+    }
+}
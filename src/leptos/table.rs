@@ -0,0 +1,657 @@
+use gloo_timers::future::TimeoutFuture;
+use leptos::callback::Callback;
+use leptos::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+#[cfg(target_family = "wasm")]
+use web_sys::UrlSearchParams;
+#[cfg(target_family = "wasm")]
+use web_sys::wasm_bindgen::JsValue;
+
+use crate::core::{
+    CheckState, RowHeight, RowHeights, SEARCH_DEBOUNCE_MS, SearchIndex, Selection, SelectionMode,
+    SlicedView, SortCycle, TableRow, TableState, ViewData, matching_rows, row_keys, search_columns,
+    sort_rows, toggle_sort_key,
+};
+use crate::leptos::body::TableBody;
+use crate::leptos::controls::PaginationControls;
+use crate::leptos::header::TableHeader;
+use crate::leptos::types::Column;
+use crate::leptos::types::FilterValue;
+use crate::leptos::types::PaginationMode;
+use crate::leptos::types::RowKey;
+use crate::leptos::types::SearchIndexKind;
+use crate::leptos::types::SearchOptions;
+use crate::leptos::types::SearchSyntax;
+use crate::leptos::types::SortKey;
+use crate::leptos::types::TableClasses;
+use crate::leptos::types::TableTexts;
+use crate::leptos::types::TimeSlicing;
+use crate::leptos::types::ViewWorker;
+use crate::leptos::types::VirtualColumns;
+use crate::leptos::types::VirtualScroll;
+
+/// A fully featured table component with sorting, pagination, and search functionality in Leptos.
+///
+/// This component renders an interactive HTML `<table>` with customizable columns, data,
+/// class names, and labels. It supports client-side sorting, debounced search with URL hydration,
+/// and pagination. All state is held in Leptos signals, so only the affected parts of the table
+/// re-render when the data, the query, the sort or the page changes.
+///
+/// # Props
+/// - `data`: A `Signal<Vec<T>>` of rows implementing `TableRow`, such as `HashMap<&'static str, String>`. A plain `Vec` is accepted too.
+/// - `columns`: A `Vec<Column>` describing each column's ID, header text, and behavior.
+/// - `page_size`: Number of rows to display per page (default: `10`).
+/// - `loading`: When `true`, displays a loading indicator (default: `false`).
+/// - `paginate`: Enables pagination controls (default: `false`).
+/// - `pagination_mode`: A `PaginationMode`: `Pages` flips pages, `LoadMore` appends the next page from a button and `InfiniteScroll` when the end of the table scrolls into view (default: `Pages`).
+/// - `virtualize`: An optional `VirtualScroll` rendering only the rows in view inside a scrolling area of fixed height, for fixed or measured row heights (default: `None`).
+/// - `virtualize_columns`: An optional `VirtualColumns` rendering only the pinned columns and those in view, for tables with hundreds of columns (default: `None`).
+/// - `search`: Enables a search input for client-side filtering (default: `false`).
+/// - `search_syntax`: `SearchSyntax::Structured` reads the search input as a query such as `status:open priority:>2 -assignee:bot`, `SearchSyntax::Fuzzy` tolerates typos, ranks rows by relevance and highlights the matches (default: `SearchSyntax::Plain`).
+/// - `search_options`: A `SearchOptions` controlling case folding, accent stripping and punctuation handling during search (default: ignore case and accents).
+/// - `search_fields`: A `Vec<&'static str>` of row keys searched without being displayed, such as tags or ids (default: none).
+/// - `search_index`: An optional `SearchIndexKind` normalizing the searched cells once per change of `data` (optionally with a trigram index) for fast plain searches of large data (default: `None`).
+/// - `worker`: A `ViewWorker` searching, filtering and sorting `data` in a Web Worker, so that large data sets do not block input (default: `None`).
+/// - `time_slicing`: A `TimeSlicing` searching, filtering and sorting `data` a slice at a time between frames, for large data sets without a worker (default: `None`).
+/// - `default_sort`: A `Vec<SortKey>` applied on first render, e.g. `vec![("date", SortOrder::Desc)]` (default: unsorted).
+/// - `allow_unsorted`: When `true`, a third header click unsorts the column and restores the data order (default: `false`).
+/// - `row_key`: A `RowKey` (a column id or a function of the row) keying each `<tr>` and identifying rows in the selection, so that DOM state follows rows however `data` changes (default: `None`, their index in `data`).
+/// - `selection`: A `SelectionMode`; `Single` or `Multiple` add a checkbox column, with shift-click selecting ranges and a "select all" checkbox in `Multiple` mode (default: `None`).
+/// - `select_all_filtered`: When `true`, "select all" covers every row matching the search and filters rather than the current page (default: `false`).
+/// - `on_selection_change`: An optional `Callback<Vec<String>>` run with the keys of the selected rows whenever the selection changes.
+/// - `expandable`: When `true`, a column of buttons shows or hides the detail panel of each row, with an "expand all" button in its header (default: `false`).
+/// - `render_detail`: An optional `Callback<T, AnyView>` rendering the detail panel of an expanded row, in a full-width row beneath it.
+/// - `expanded`: An optional `Signal<Vec<String>>` of the keys of the expanded rows, for a parent controlling the expansion; without it the table keeps track of it.
+/// - `on_expand_change`: An optional `Callback<Vec<String>>` run with the keys of the expanded rows whenever a row is expanded or collapsed.
+/// - `texts`: Customizable text labels for UI strings (default: `TableTexts::default()`).
+/// - `classes`: Customizable CSS class names for each table part (default: `TableClasses::default()`).
+///
+/// # Features
+/// - **Search**: Filters rows client-side using a debounced text input; the query is persisted in the URL via `?search=` and may use `field:value` syntax.
+/// - **Sorting**: Clickable headers allow sorting columns ascending or descending; shift-click adds a secondary sort column.
+/// - **Column Filters**: Columns with a `filter` get a text box, drop-down, or min/max inputs in a filter row; all filters are combined with AND.
+/// - **Pagination**: Navigate between pages using prev/next buttons, with an indicator showing current page.
+/// - **Load More and Infinite Scroll**: Append pages for feeds instead of flipping them.
+/// - **Virtualization**: Only the rows in view are rendered, between spacer rows and under a sticky header.
+/// - **Column Virtualization**: Only the pinned columns and the columns in view are rendered, between spacer cells.
+/// - **Web Worker Offload**: Searching, filtering and sorting run in a worker, showing the loading row meanwhile.
+/// - **Time Slicing**: Without a worker, searching, filtering and sorting run in slices between which input is handled.
+/// - **Row Selection**: Selected rows are kept across pages, sorting and filtering, with a tri-state "select all" checkbox.
+/// - **Expandable Rows**: A custom detail panel under each expanded row, controlled or uncontrolled, with an "expand all" toggle.
+/// - **Custom Classes**: All elements are styled via `TableClasses` for full customization.
+/// - **Text Overrides**: All UI strings (e.g., empty state, loading, buttons) can be customized using `TableTexts`.
+///
+/// # Returns
+/// Returns a Leptos view that renders a complete table with the above features.
+///
+/// # Example
+/// ```rust
+/// use leptos::prelude::*;
+/// use maplit::hashmap;
+/// use table_rs::leptos::table::Table;
+/// use table_rs::leptos::types::Column;
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let data = vec![
+///         hashmap! { "name" => "ferris".to_string(), "email" => "ferris@opensass.org".to_string() },
+///         hashmap! { "name" => "ferros".to_string(), "email" => "ferros@opensass.org".to_string() },
+///     ];
+///
+///     let columns = vec![
+///         Column { id: "name", header: "Name", sortable: true, ..Default::default() },
+///         Column { id: "email", header: "Email", ..Default::default() },
+///     ];
+///
+///     view! {
+///         <Table data=data columns=columns paginate=true search=true />
+///     }
+/// }
+/// ```
+///
+/// # See Also
+/// - [MDN `<table>` Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/table)
+#[component]
+pub fn Table<T>(
+    #[prop(optional, into)] data: Signal<Vec<T>>,
+    #[prop(optional)] columns: Vec<Column>,
+    #[prop(default = 10)] page_size: usize,
+    #[prop(optional, into)] loading: Signal<bool>,
+    #[prop(optional)] paginate: bool,
+    #[prop(optional)] pagination_mode: PaginationMode,
+    #[prop(optional)] virtualize: Option<VirtualScroll>,
+    #[prop(optional)] virtualize_columns: Option<VirtualColumns>,
+    #[prop(optional)] search: bool,
+    #[prop(optional)] search_syntax: SearchSyntax,
+    #[prop(optional)] search_options: SearchOptions,
+    #[prop(optional)] search_fields: Vec<&'static str>,
+    #[prop(optional)] search_index: Option<SearchIndexKind>,
+    #[prop(optional)] worker: Option<ViewWorker>,
+    #[prop(optional)] time_slicing: Option<TimeSlicing>,
+    #[prop(optional)] default_sort: Vec<SortKey>,
+    #[prop(optional)] allow_unsorted: bool,
+    #[prop(optional)] row_key: Option<RowKey>,
+    #[prop(optional)] selection: SelectionMode,
+    #[prop(optional)] select_all_filtered: bool,
+    #[prop(optional)] on_selection_change: Option<Callback<Vec<String>>>,
+    #[prop(optional)] expandable: bool,
+    #[prop(optional)] render_detail: Option<Callback<T, AnyView>>,
+    #[prop(optional, into)] expanded: Option<Signal<Vec<String>>>,
+    #[prop(optional)] on_expand_change: Option<Callback<Vec<String>>>,
+    #[prop(optional)] texts: TableTexts,
+    #[prop(optional)] classes: TableClasses,
+) -> impl IntoView
+where
+    T: TableRow + Clone + Send + Sync + 'static,
+{
+    let page = RwSignal::new(0_usize);
+    let sort = RwSignal::new(default_sort);
+    let search_query = RwSignal::new(String::new());
+    let filters = RwSignal::new(BTreeMap::<&'static str, FilterValue>::new());
+    let debounced_search = StoredValue::new(None::<TimeoutHandle>);
+
+    #[cfg(target_family = "wasm")]
+    if let Some(search_val) = web_sys::window()
+        .and_then(|w| w.location().search().ok())
+        .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
+        .and_then(|params| params.get("search"))
+    {
+        search_query.set(search_val);
+    }
+
+    let update_search = move |query: String| {
+        #[cfg(target_family = "wasm")]
+        let _ = web_sys::window().and_then(|window| {
+            let href = window.location().href().ok()?;
+            let url = web_sys::Url::new(&href).ok()?;
+            let params = url.search_params();
+            params.set("search", &query);
+            url.set_search(&params.to_string().as_string().unwrap_or_default());
+
+            window
+                .history()
+                .ok()?
+                .replace_state_with_url(&JsValue::NULL, "", Some(&url.href()))
+                .ok()
+        });
+
+        search_query.set(query);
+        // Reset page to 0 when search query changes to prevent invalid page states
+        page.set(0);
+    };
+
+    let on_search_change = move |ev| {
+        let value = event_target_value(&ev);
+
+        // Cancel previous timeout to prevent multiple URL updates
+        if let Some(prev) = debounced_search.get_value() {
+            prev.clear();
+        }
+
+        // Create new debounced timeout
+        let handle = set_timeout_with_handle(
+            move || update_search(value),
+            Duration::from_millis(SEARCH_DEBOUNCE_MS.into()),
+        )
+        .ok();
+        debounced_search.set_value(handle);
+    };
+
+    let sort_columns = columns.clone();
+
+    // The worker gets a copy of the rows whenever they change, then a request whenever they, the
+    // search, the filters or the sort change; the answers to superseded requests are dropped
+    let offload = worker.filter(|_| ViewData::supports(&columns));
+    let offloaded = offload.is_some();
+    let loaded_version = RwSignal::new(0_u64);
+    let worker_rows = RwSignal::new(None::<(u64, Vec<usize>)>);
+    let computing = RwSignal::new(false);
+    if let Some(worker) = offload {
+        let load_worker = worker.clone();
+        let load_columns = columns.clone();
+        let load_fields = search_fields.clone();
+        Effect::new(move |_| {
+            data.with(|data| load_worker.load(ViewData::new(data, &load_columns, &load_fields)));
+            loaded_version.update(|version| *version += 1);
+        });
+
+        let generation = StoredValue::new(0_u64);
+        let request_fields = search_fields.clone();
+        Effect::new(move |_| {
+            let version = loaded_version.get();
+            let state = TableState {
+                query: search_query.get(),
+                search_syntax,
+                search_options,
+                search_fields: request_fields.clone(),
+                sort: sort.get(),
+                filters: filters.get(),
+                ..Default::default()
+            };
+            let sorted = worker.sorted_rows(&state);
+            generation.update_value(|generation| *generation += 1);
+            let current = generation.get_value();
+            computing.set(true);
+            leptos::task::spawn_local(async move {
+                let indices = sorted.await;
+                if generation.try_get_value() == Some(current) {
+                    worker_rows.set(Some((version, indices)));
+                    computing.set(false);
+                }
+            });
+        });
+    }
+
+    // Without a worker, the rows can be searched, filtered and sorted a slice at a time, with
+    // the browser handling input in between; other rows, search, filters or sort abandon the
+    // computation under way
+    let slicing = time_slicing.filter(|_| !offloaded);
+    let sliced_rows = RwSignal::new(None::<Vec<usize>>);
+    if let Some(time_slicing) = slicing {
+        let generation = StoredValue::new(0_u64);
+        let slice_columns = columns.clone();
+        let slice_fields = search_fields.clone();
+        Effect::new(move |_| {
+            data.track();
+            let state = TableState {
+                query: search_query.get(),
+                search_syntax,
+                search_options,
+                search_fields: slice_fields.clone(),
+                sort: sort.get(),
+                filters: filters.get(),
+                ..Default::default()
+            };
+            generation.update_value(|generation| *generation += 1);
+            let current = generation.get_value();
+            let columns = slice_columns.clone();
+            sliced_rows.set(None);
+            computing.set(true);
+            leptos::task::spawn_local(async move {
+                let mut view = SlicedView::new(&state);
+                loop {
+                    if generation.try_get_value() != Some(current) {
+                        return;
+                    }
+                    let step = |data: &Vec<T>| view.step(data, &columns, time_slicing.budget);
+                    match data.try_with_untracked(step) {
+                        Some(true) => break,
+                        Some(false) => TimeoutFuture::new(0).await,
+                        None => return,
+                    }
+                }
+                sliced_rows.set(view.indices().map(<[usize]>::to_vec));
+                computing.set(false);
+            });
+        });
+    }
+    let slicing = slicing.is_some();
+    let loading = Signal::derive(move || loading.get() || computing.get());
+
+    // The index follows `data` from one version of it to the next, so only the rows appended or
+    // changed since the last one are normalized. It is synced by the search below, before it is
+    // read, whenever `data` has changed since the generation it was last synced with.
+    let index = search_index.filter(|_| !offloaded && !slicing).map(|kind| {
+        let searched = search_columns(&columns, &search_fields);
+        StoredValue::new((
+            None::<u64>,
+            SearchIndex::new(&searched, search_options, kind),
+        ))
+    });
+    let data_generation = Memo::new(move |generation: Option<&u64>| {
+        data.track();
+        generation.map_or(0, |generation| generation + 1)
+    });
+
+    // Filter only when the data, the query or the filters change, and sort only when the
+    // filtered rows or the sort change, so that page flips do neither
+    let matching_indices = Memo::new({
+        let sort_columns = sort_columns.clone();
+        move |_| {
+            let state = TableState {
+                query: search_query.get(),
+                search_syntax,
+                search_options,
+                search_fields: search_fields.clone(),
+                filters: filters.get(),
+                ..Default::default()
+            };
+            data.with(|data| match index {
+                Some(index) => {
+                    let generation = data_generation.get();
+                    index.update_value(|(synced, index)| {
+                        if *synced != Some(generation) {
+                            index.sync(data);
+                            *synced = Some(generation);
+                        }
+                    });
+                    index.with_value(|(_, index)| index.matching_rows(data, &sort_columns, &state))
+                }
+                None => matching_rows(data, &sort_columns, &state),
+            })
+        }
+    });
+    let filtered_indices = Memo::new(move |_| {
+        if slicing {
+            // Until the last slice is done, the loading row stands in for the rows
+            return sliced_rows.get().unwrap_or_default();
+        }
+        if offloaded {
+            // Until the worker has answered for these rows, the loading row stands in for them
+            let version = loaded_version.get();
+            return worker_rows.with(|rows| match rows {
+                Some((answered, indices)) if *answered == version => indices.clone(),
+                _ => Vec::new(),
+            });
+        }
+        let mut indices = matching_indices.get();
+        data.with(|data| sort.with(|sort| sort_rows(data, &sort_columns, &mut indices, sort)));
+        indices
+    });
+
+    let page_view = Memo::new(move |_| {
+        let state = TableState {
+            page: page.get(),
+            page_size,
+            pagination_mode,
+            ..Default::default()
+        };
+        filtered_indices.with(|indices| crate::core::page_view(indices, &state))
+    });
+    let total_pages = Signal::derive(move || page_view.with(|view| view.total_pages));
+    let has_more = Signal::derive(move || page_view.with(|view| view.page + 1 < view.total_pages));
+
+    let row_height = virtualize
+        .map(|virtualize| virtualize.row_height)
+        .unwrap_or_default();
+    let heights = StoredValue::new(RowHeights::new(row_height));
+    let measured = Trigger::new();
+    let scroll_top = RwSignal::new(0.0_f64);
+    let scroll_left = RwSignal::new(0.0_f64);
+
+    // Row heights are measured per position, so start over whenever the rows are reordered or
+    // replaced; appending pages keeps the positions of the rows already shown.
+    Effect::new(move |_| {
+        filtered_indices.track();
+        if !pagination_mode.appends() {
+            page.track();
+        }
+        heights.set_value(RowHeights::new(row_height));
+        measured.notify();
+    });
+
+    let virtual_window = Memo::new(move |_| {
+        let virtualize = virtualize?;
+        measured.track();
+        let count = page_view.with(|view| view.indices.len());
+        Some(heights.with_value(|heights| {
+            heights.window(
+                count,
+                scroll_top.get(),
+                virtualize.viewport_height,
+                virtualize.overscan,
+            )
+        }))
+    });
+    let on_measure = matches!(row_height, RowHeight::Measured { .. }).then(|| {
+        Callback::new(move |(row, height): (usize, f64)| {
+            let changed = heights
+                .try_update_value(|heights| heights.measure(row, height))
+                .unwrap_or(false);
+            if changed {
+                measured.notify();
+            }
+        })
+    });
+
+    let visible = Memo::new(move |_| {
+        let window = virtual_window.get();
+        page_view.with(|view| match window {
+            Some(window) => view.indices[window.range].to_vec(),
+            None => view.indices.clone(),
+        })
+    });
+    // The rows are cloned once when they scroll or page into view and shared from then on; rows
+    // need not be comparable, so every recomputation counts as a change
+    let page_rows = Memo::new_with_compare(
+        move |_| {
+            visible.with(|indices| {
+                data.with(|data| {
+                    indices
+                        .iter()
+                        .map(|&idx| data[idx].clone())
+                        .collect::<Vec<_>>()
+                })
+            })
+        },
+        |_, _| true,
+    );
+
+    // Rows are keyed and selected by their `row_key`, or else by their index in `data`, which
+    // stays put across pages, sorting and filtering
+    let selected = RwSignal::new(Selection::default());
+    let keys_of = move |indices: &[usize]| {
+        data.with(|data| row_keys(row_key, indices, |idx| data.get(idx), 0))
+    };
+    let row_keys = Signal::derive(move || visible.with(|indices| keys_of(indices)));
+    let select_all = Memo::new(move |_| {
+        let keys = |indices: &Vec<usize>| {
+            let keys = keys_of(indices);
+            selected.with(|selected| selected.check_state(keys))
+        };
+        match selection {
+            SelectionMode::Multiple if select_all_filtered => filtered_indices.with(keys),
+            SelectionMode::Multiple => page_view.with(|view| keys(&view.indices)),
+            _ => CheckState::Unchecked,
+        }
+    });
+    let selection_changed = move || {
+        if let Some(on_selection_change) = on_selection_change {
+            on_selection_change.run(selected.with_untracked(Selection::keys));
+        }
+    };
+    let on_select = Callback::new(move |(key, extend): (String, bool)| {
+        let order = filtered_indices.with_untracked(|order| keys_of(order));
+        selected.update(|selected| selected.toggle(selection, &key, extend, order));
+        selection_changed();
+    });
+    let on_select_all = Callback::new(move |checked: bool| {
+        let scope = if select_all_filtered {
+            filtered_indices.get_untracked()
+        } else {
+            page_view.with_untracked(|view| view.indices.clone())
+        };
+        let scope = keys_of(&scope);
+        selected.update(|selected| selected.set_all(scope, checked));
+        selection_changed();
+    });
+
+    // A parent passing `expanded` controls the expansion; otherwise the table keeps track of it
+    let expanded_state = RwSignal::new(BTreeSet::<String>::new());
+    let expanded_rows = Memo::new(move |_| match expanded {
+        Some(keys) => keys.with(|keys| keys.iter().cloned().collect()),
+        None => expanded_state.get(),
+    });
+    let all_expanded = Memo::new(move |_| {
+        let keys = page_view.with(|view| keys_of(&view.indices));
+        expandable
+            && !keys.is_empty()
+            && expanded_rows.with(|expanded| keys.iter().all(|key| expanded.contains(key)))
+    });
+    let set_expanded = move |next: BTreeSet<String>| {
+        if let Some(on_expand_change) = on_expand_change {
+            on_expand_change.run(next.iter().cloned().collect());
+        }
+        expanded_state.set(next);
+    };
+    let on_expand = Callback::new(move |key: String| {
+        let mut next = expanded_rows.get_untracked();
+        if !next.remove(&key) {
+            next.insert(key);
+        }
+        set_expanded(next);
+    });
+    let on_expand_all = Callback::new(move |expand: bool| {
+        let mut next = expanded_rows.get_untracked();
+        for key in page_view.with_untracked(|view| keys_of(&view.indices)) {
+            if expand {
+                next.insert(key);
+            } else {
+                next.remove(&key);
+            }
+        }
+        set_expanded(next);
+    });
+
+    let cycle_columns = columns.clone();
+    let on_sort_column = Callback::new(move |(id, additive): (&'static str, bool)| {
+        let cycle = SortCycle::for_column(&cycle_columns, id, allow_unsorted);
+        sort.update(|keys| toggle_sort_key(keys, id, additive, cycle));
+        // Appended pages no longer line up with the new order, so start over
+        if pagination_mode.appends() {
+            page.set(0);
+        }
+    });
+
+    let on_load_more = Callback::new(move |()| {
+        if has_more.get_untracked() && !loading.get_untracked() {
+            page.set(page_view.with_untracked(|view| view.page) + 1);
+        }
+    });
+
+    let on_filter = Callback::new(move |(id, value): (&'static str, FilterValue)| {
+        filters.update(|filters| {
+            filters.insert(id, value);
+        });
+        page.set(0);
+    });
+
+    let highlight = Signal::derive(move || match search_syntax {
+        SearchSyntax::Fuzzy => search_query.get(),
+        _ => String::new(),
+    });
+
+    let window_columns = columns.clone();
+    let column_window = Memo::new(move |_| {
+        virtualize_columns
+            .map(|virtualize_columns| virtualize_columns.window(&window_columns, scroll_left.get()))
+    });
+
+    let mut scroll_style = String::new();
+    if let Some(virtualize) = virtualize {
+        scroll_style += &format!(
+            "height: {}px; overflow-y: auto;",
+            virtualize.viewport_height
+        );
+    }
+    if let Some(virtualize_columns) = virtualize_columns {
+        scroll_style += &format!(
+            " max-width: {}px; overflow-x: auto;",
+            virtualize_columns.viewport_width
+        );
+    }
+    // A fixed layout keeps the columns from resizing as different rows scroll into view, and
+    // sizes them from the header cells so that the body lines up under the header
+    let mut table_style = String::new();
+    if virtualize.is_some() || virtualize_columns.is_some() {
+        table_style += "table-layout: fixed;";
+    }
+    if let Some(column_window) = column_window.get_untracked() {
+        table_style += &format!(" width: {}px;", column_window.total_width);
+    }
+
+    let table = view! {
+        <table
+            class=classes.table
+            style=(!table_style.is_empty()).then(|| table_style.trim().to_string())
+            role="table"
+            aria-busy=move || loading.get().to_string()
+        >
+            <TableHeader
+                columns=columns.clone()
+                sort=sort
+                on_sort_column=on_sort_column
+                filters=filters
+                on_filter=on_filter
+                classes=classes
+                texts=texts
+                sticky=virtualize.is_some()
+                column_window=column_window
+                selection=selection
+                select_all=select_all
+                on_select_all=on_select_all
+                expandable=expandable
+                all_expanded=all_expanded
+                on_expand_all=on_expand_all
+            />
+            <TableBody
+                columns=columns
+                rows=page_rows
+                loading=loading
+                classes=classes
+                texts=texts
+                highlight=highlight
+                search_options=search_options
+                pagination_mode=pagination_mode
+                has_more=has_more
+                on_load_more=on_load_more
+                virtual_window=virtual_window
+                on_measure=on_measure
+                column_window=column_window
+                selection=selection
+                row_keys=row_keys
+                selected=selected
+                on_select=on_select
+                expandable=expandable
+                expanded=expanded_rows
+                on_expand=on_expand
+                render_detail=render_detail
+            />
+        </table>
+    };
+    let table = if scroll_style.is_empty() {
+        table.into_any()
+    } else {
+        view! {
+            <div
+                class=classes.scroll_container
+                style=scroll_style.trim().to_string()
+                on:scroll=move |ev| {
+                    let container = event_target::<web_sys::Element>(&ev);
+                    scroll_top.set(container.scroll_top() as f64);
+                    scroll_left.set(container.scroll_left() as f64);
+                }
+            >
+                {table}
+            </div>
+        }
+        .into_any()
+    };
+
+    view! {
+        <div class=classes.container>
+            {search.then(|| view! {
+                <input
+                    class=classes.search_input
+                    type="text"
+                    prop:value=move || search_query.get()
+                    placeholder=texts.search_placeholder
+                    aria-label="Search table"
+                    on:input=on_search_change
+                />
+            })}
+            {table}
+            {paginate.then(|| view! {
+                <PaginationControls
+                    page=page
+                    total_pages=total_pages
+                    classes=classes
+                    texts=texts
+                    pagination_mode=pagination_mode
+                />
+            })}
+        </div>
+    }
+}
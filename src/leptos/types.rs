@@ -0,0 +1,306 @@
+use leptos::callback::Callback;
+use leptos::prelude::AnyView;
+
+use crate::core::{ColumnDef, SortFn, TableColumn};
+
+pub use crate::core::{
+    CheckState, ColumnFilter, ColumnKind, ColumnSlot, ColumnWindow, FilterValue, PaginationMode,
+    RowHeight, RowKey, SearchIndexKind, SearchOptions, SearchSyntax, Selection, SelectionMode,
+    SortKey, SortOrder, TimeSlicing, VirtualColumns, VirtualScroll, VirtualWindow,
+};
+pub use crate::worker::ViewWorker;
+
+/// Represents a column definition for the table.
+#[derive(Clone)]
+pub struct Column {
+    /// Unique identifier for the column.
+    pub id: &'static str,
+
+    /// Header text displayed in the column.
+    pub header: &'static str,
+
+    /// Whether this column is sortable.
+    pub sortable: bool,
+
+    /// How values are parsed and compared when sorting (defaults to text).
+    pub kind: ColumnKind,
+
+    /// Custom comparator for sorting, e.g. `SortFn::Natural` or a severity order.
+    pub sort_fn: Option<SortFn>,
+
+    /// Direction of the first click on the header, e.g. `SortOrder::Desc` for newest-first dates.
+    pub first_sort_order: SortOrder,
+
+    /// Filter widget shown for this column in the filter row, if any.
+    pub filter: Option<ColumnFilter>,
+
+    /// Whether the search input looks at this column; turn off for columns such as avatars.
+    pub searchable: bool,
+
+    /// Provide custom view generator (defaults to plain String -> String).
+    pub cell: Option<Callback<String, AnyView>>,
+
+    /// Minimum width of the column (default is 100).
+    pub min_width: u32,
+
+    /// Whether the column always renders, stuck to the left edge, when the columns are virtualized.
+    pub pinned: bool,
+
+    /// Optional inline styles for the column header.
+    pub style: Option<&'static str>,
+
+    /// Optional CSS classes for the column header.
+    pub class: Option<&'static str>,
+}
+
+impl TableColumn for Column {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn kind(&self) -> ColumnKind {
+        self.kind
+    }
+
+    fn sort_fn(&self) -> Option<SortFn> {
+        self.sort_fn
+    }
+
+    fn first_sort_order(&self) -> SortOrder {
+        self.first_sort_order
+    }
+
+    fn filter(&self) -> Option<ColumnFilter> {
+        self.filter
+    }
+
+    fn searchable(&self) -> bool {
+        self.searchable
+    }
+
+    fn min_width(&self) -> u32 {
+        self.min_width
+    }
+
+    fn pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Self {
+            id: "",
+            header: "",
+            sortable: false,
+            kind: ColumnKind::Text,
+            sort_fn: None,
+            first_sort_order: SortOrder::Asc,
+            filter: None,
+            searchable: true,
+            cell: None,
+            min_width: 0,
+            pinned: false,
+            style: None,
+            class: None,
+        }
+    }
+}
+
+impl From<ColumnDef> for Column {
+    fn from(def: ColumnDef) -> Self {
+        Self {
+            id: def.id,
+            header: def.header,
+            sortable: def.sortable,
+            kind: def.kind,
+            sort_fn: def.sort_fn,
+            first_sort_order: def.first_sort_order,
+            filter: def.filter,
+            searchable: def.searchable,
+            ..Default::default()
+        }
+    }
+}
+
+/// Text labels for table UI elements.
+#[derive(Clone, Copy, PartialEq)]
+pub struct TableTexts {
+    /// Text shown when data is loading.
+    pub loading: &'static str,
+
+    /// Text shown when no data is available.
+    pub empty: &'static str,
+
+    /// Placeholder text for the search input.
+    pub search_placeholder: &'static str,
+
+    /// Label for the previous page button.
+    pub previous_button: &'static str,
+
+    /// Label for the next page button.
+    pub next_button: &'static str,
+
+    /// Page indicator text with placeholders `{current}` and `{total}`.
+    pub page_indicator: &'static str,
+
+    /// Label for the button appending the next page.
+    pub load_more: &'static str,
+
+    /// Label of the drop-down option that clears a select filter.
+    pub filter_all: &'static str,
+
+    /// Placeholder and label suffix for the lower bound of a range filter.
+    pub filter_min: &'static str,
+
+    /// Placeholder and label suffix for the upper bound of a range filter.
+    pub filter_max: &'static str,
+
+    /// Label of the checkbox selecting a row.
+    pub select_row: &'static str,
+
+    /// Label of the checkbox selecting every row.
+    pub select_all: &'static str,
+
+    /// Label of the button showing the detail panel of a row.
+    pub expand_row: &'static str,
+
+    /// Label of the button showing the detail panels of every row.
+    pub expand_all: &'static str,
+}
+
+impl Default for TableTexts {
+    fn default() -> Self {
+        Self {
+            loading: "Loading...",
+            empty: "No results found",
+            search_placeholder: "Search...",
+            previous_button: "Previous",
+            next_button: "Next",
+            page_indicator: "Page {current} of {total}",
+            load_more: "Load more",
+            filter_all: "All",
+            filter_min: "Min",
+            filter_max: "Max",
+            select_row: "Select row",
+            select_all: "Select all rows",
+            expand_row: "Show details",
+            expand_all: "Show all details",
+        }
+    }
+}
+
+/// Defines the styling classes for each part of the table.
+#[derive(Clone, Copy, PartialEq)]
+pub struct TableClasses {
+    /// Wrapper around the entire table.
+    pub container: &'static str,
+
+    /// Class for the `<table>` element.
+    pub table: &'static str,
+
+    /// Class for the `<thead>` element.
+    pub thead: &'static str,
+
+    /// Class for the `<tbody>` element.
+    pub tbody: &'static str,
+
+    /// Wrapper for pagination controls.
+    pub pagination: &'static str,
+
+    /// Class for the search input field.
+    pub search_input: &'static str,
+
+    /// Class for the `<mark>` around characters matched by a fuzzy search.
+    pub search_highlight: &'static str,
+
+    /// Class for header cells (`<th>`).
+    pub header_cell: &'static str,
+
+    /// Class for body cells (`<td>`).
+    pub body_cell: &'static str,
+
+    /// Class for each table row.
+    pub row: &'static str,
+
+    /// Class for the row shown while loading.
+    pub loading_row: &'static str,
+
+    /// Class for the row shown when no data is found.
+    pub empty_row: &'static str,
+
+    /// Class for pagination buttons.
+    pub pagination_button: &'static str,
+
+    /// Class for the "Load more" button.
+    pub load_more_button: &'static str,
+
+    /// Class for the empty row after the last one that loads more rows when scrolled into view.
+    pub sentinel_row: &'static str,
+
+    /// Class for the scrolling area wrapping a virtualized table.
+    pub scroll_container: &'static str,
+
+    /// Class for the empty rows that take the place of the rows scrolled out of a virtualized table.
+    pub spacer_row: &'static str,
+
+    /// Class for the sort priority badge shown when sorting by several columns.
+    pub sort_badge: &'static str,
+
+    /// Class for the filter row under the header row.
+    pub filter_row: &'static str,
+
+    /// Class for the cells of the filter row.
+    pub filter_cell: &'static str,
+
+    /// Class for the filter inputs and drop-downs.
+    pub filter_input: &'static str,
+
+    /// Class added to the selected rows.
+    pub selected_row: &'static str,
+
+    /// Class for the cells holding the selection checkboxes.
+    pub select_cell: &'static str,
+
+    /// Class for the cells holding the expand buttons.
+    pub expand_cell: &'static str,
+
+    /// Class for the buttons showing and hiding the detail panel of a row.
+    pub expand_button: &'static str,
+
+    /// Class for the rows holding the detail panel of an expanded row.
+    pub detail_row: &'static str,
+}
+
+impl Default for TableClasses {
+    fn default() -> Self {
+        Self {
+            container: "table-container",
+            table: "table",
+            thead: "thead",
+            tbody: "tbody",
+            pagination: "pagination-controls",
+            search_input: "search-input",
+            search_highlight: "search-highlight",
+            header_cell: "th",
+            body_cell: "td",
+            row: "tr",
+            loading_row: "loading-row",
+            empty_row: "empty-row",
+            pagination_button: "pagination-button",
+            load_more_button: "load-more-button",
+            sentinel_row: "sentinel-row",
+            scroll_container: "table-scroll",
+            spacer_row: "spacer-row",
+            sort_badge: "sort-priority",
+            filter_row: "filter-row",
+            filter_cell: "filter-cell",
+            filter_input: "filter-input",
+            selected_row: "selected-row",
+            select_cell: "select-cell",
+            expand_cell: "expand-cell",
+            expand_button: "expand-button",
+            detail_row: "detail-row",
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! The Web Worker a table can hand its search, filters and sort to.
+//!
+//! The rows and requests it exchanges are the plain [`ViewData`] and [`WorkerRequest`] of the
+//! [core](crate::core); this module holds the browser side only, so it needs a `wasm32` target to
+//! do anything.
+
+use crate::core::{TableState, ViewData, WorkerRequest};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use web_sys::js_sys::{self, Array, Uint32Array};
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker};
+
+/// A dedicated Web Worker that searches, filters and sorts the rows of a table, so that large
+/// data sets do not block input on the main thread.
+///
+/// The table sends its rows once, as a [`ViewData`], whenever they change, then asks for the
+/// [sorted rows](Self::sorted_rows) whenever the search, the filters or the sort change. The
+/// worker replies with the row indices only, in a transferred buffer.
+///
+/// The worker script must call [`serve_view_worker`] on start; with Trunk, for example, a binary
+/// of the app's crate whose `main` does nothing else, loaded with `data-type="worker"`.
+///
+/// A worker holds the rows of a single table: each [load](Self::load) replaces the rows sent
+/// before, so two tables sharing one worker would both be answered from whichever loaded last.
+/// Start one worker per table.
+///
+/// Handles compare equal when they point to the same worker, which is terminated once the last
+/// handle is dropped.
+#[derive(Clone)]
+pub struct ViewWorker(Rc<WorkerHandle>);
+
+type Replies = Rc<RefCell<HashMap<u64, Reply>>>;
+
+struct WorkerHandle {
+    worker: Worker,
+    next_id: Cell<u64>,
+    replies: Replies,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+}
+
+#[derive(Default)]
+struct Reply {
+    indices: Option<Vec<usize>>,
+    waker: Option<Waker>,
+}
+
+impl ViewWorker {
+    /// Starts a classic worker running the script at `url`.
+    pub fn new(url: &str) -> Result<Self, String> {
+        let worker = Worker::new(url).map_err(|err| format!("{err:?}"))?;
+        Ok(Self::from_worker(worker))
+    }
+
+    /// Takes over a worker started by the caller, for example as a module worker.
+    pub fn from_worker(worker: Worker) -> Self {
+        let replies = Replies::default();
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new({
+            let replies = Rc::clone(&replies);
+            move |event: MessageEvent| {
+                let message: Array = event.data().unchecked_into();
+                let Some(id) = message.get(0).as_f64() else {
+                    return;
+                };
+                let indices: Uint32Array = message.get(1).unchecked_into();
+                // Replies nobody waits for any more were superseded and are dropped
+                if let Some(reply) = replies.borrow_mut().get_mut(&(id as u64)) {
+                    reply.indices =
+                        Some(indices.to_vec().into_iter().map(|i| i as usize).collect());
+                    if let Some(waker) = reply.waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
+        });
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        Self(Rc::new(WorkerHandle {
+            worker,
+            next_id: Cell::new(0),
+            replies,
+            _onmessage: onmessage,
+        }))
+    }
+
+    /// Sends the rows to order from now on, in place of any rows sent before.
+    pub fn load(&self, data: ViewData) {
+        self.post(&WorkerRequest::Load(data));
+    }
+
+    /// Asks for the indices of the rows matching `state`, in display order.
+    ///
+    /// The reply is dropped if the returned future is dropped first.
+    pub fn sorted_rows(&self, state: &TableState) -> SortedRows {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        self.0.replies.borrow_mut().insert(id, Reply::default());
+        self.post(&WorkerRequest::View {
+            id,
+            state: state.clone(),
+        });
+        SortedRows {
+            id,
+            replies: Rc::clone(&self.0.replies),
+        }
+    }
+
+    fn post(&self, request: &WorkerRequest) {
+        let message = JsValue::from_str(&request.encode());
+        if let Err(err) = self.0.worker.post_message(&message) {
+            web_sys::console::error_1(&err);
+        }
+    }
+}
+
+impl Drop for WorkerHandle {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+impl PartialEq for ViewWorker {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for ViewWorker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ViewWorker").finish_non_exhaustive()
+    }
+}
+
+/// The future returned by [`ViewWorker::sorted_rows`].
+pub struct SortedRows {
+    id: u64,
+    replies: Replies,
+}
+
+impl Future for SortedRows {
+    type Output = Vec<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<usize>> {
+        let mut replies = self.replies.borrow_mut();
+        let Some(reply) = replies.get_mut(&self.id) else {
+            return Poll::Ready(Vec::new());
+        };
+        match reply.indices.take() {
+            Some(indices) => {
+                replies.remove(&self.id);
+                Poll::Ready(indices)
+            }
+            None => {
+                reply.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for SortedRows {
+    fn drop(&mut self) {
+        self.replies.borrow_mut().remove(&self.id);
+    }
+}
+
+/// Runs the worker side of a [`ViewWorker`]: call it once from the worker's entry point.
+///
+/// It keeps the last [`ViewData`] received and answers each request with the indices of the
+/// matching rows, in display order.
+pub fn serve_view_worker() {
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+    let data = RefCell::new(None::<ViewData>);
+    let reply_scope = scope.clone();
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        let request = event.data().as_string();
+        match request.as_deref().and_then(WorkerRequest::decode) {
+            Some(WorkerRequest::Load(loaded)) => *data.borrow_mut() = Some(loaded),
+            Some(WorkerRequest::View { id, state }) => {
+                let indices: Vec<u32> = data
+                    .borrow()
+                    .as_ref()
+                    .map(|data| data.sorted_rows(&state))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|idx| idx as u32)
+                    .collect();
+                let indices = Uint32Array::from(&indices[..]);
+                let message = Array::of2(&JsValue::from_f64(id as f64), &indices);
+                let transfer = Array::of1(&indices.buffer());
+                if let Err(err) = reply_scope.post_message_with_transfer(&message, &transfer) {
+                    web_sys::console::error_1(&err);
+                }
+            }
+            None => web_sys::console::error_1(&"Unexpected message for the table worker".into()),
+        }
+    });
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    // The handler lives as long as the worker
+    onmessage.forget();
+}
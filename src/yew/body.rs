@@ -1,4 +1,10 @@
-use crate::yew::types::TableBodyProps;
+use crate::core::{SearchOptions, TableRow, fuzzy_highlight};
+use crate::yew::types::{ColumnSlot, PaginationMode, TableBodyProps};
+use std::collections::HashMap;
+use web_sys::js_sys::Array;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
 use yew::prelude::*;
 
 /// A table body component that handles rendering of table rows, empty state, and loading state.
@@ -9,16 +15,38 @@ use yew::prelude::*;
 /// # Arguments
 /// * `props` - The properties passed to the component.
 ///   - `columns` - A list of column definitions (`Vec<Column>`) specifying which fields to render.
-///   - `rows` - A vector of row data (`Vec<HashMap<&'static str, String>>`) to display.
+///   - `rows` - An `Rc<Vec<T>>` of rows implementing `TableRow` (e.g. `HashMap<&'static str, String>`) to display; when virtualized, only the rows of `virtual_window`.
 ///   - `loading` - A boolean flag indicating whether the table is in a loading state.
+///   - `error` - An optional error message; when set, an error row with a retry button replaces the rows.
+///   - `on_retry` - A `Callback<()>` invoked when the retry button is clicked.
+///   - `pagination_mode` - A `PaginationMode`; the appending modes keep the rows visible while more load.
+///   - `has_more` - A boolean flag indicating whether more rows can be loaded after the ones shown.
+///   - `on_load_more` - A `Callback<()>` invoked when the sentinel row of `InfiniteScroll` comes into view.
 ///   - `classes` - A `TableClasses` object defining CSS class names for customization.
 ///   - `texts` - A `TableTexts` object defining UI text like loading or empty messages.
+///   - `highlight` - A fuzzy search query whose matched characters are wrapped in `<mark>`.
+///   - `search_options` - The `SearchOptions` used to match `highlight`.
+///   - `virtual_window` - An optional `VirtualWindow`; its range places `rows` among all the rows, with spacer rows of the given heights above and below them.
+///   - `on_measure` - An optional `Callback<Vec<(usize, f64)>>` receiving the position and height of each rendered row after every render.
+///   - `column_window` - An optional `ColumnWindow` restricting each row to the cells of the pinned columns and those in view, between spacer cells.
+///   - `selection` - A `SelectionMode`; unless `None`, each row starts with a checkbox cell.
+///   - `row_keys` - An `Rc<Vec<String>>` holding the key of each row of `rows`, used as the key of its `<tr>`; rows without one are keyed by position.
+///   - `selected` - An `Rc<Selection>` of the selected rows, which get `aria-selected="true"` and the `selected_row` class.
+///   - `on_select` - A `Callback<(String, bool)>` triggered by the checkbox of a row, with its key and whether shift was held.
+///   - `expandable` - A `bool` starting each row with a button that shows or hides its detail panel, with `aria-expanded` telling which.
+///   - `expanded` - An `Rc<BTreeSet<String>>` of the keys of the expanded rows.
+///   - `on_expand` - A `Callback<String>` triggered by the expand button of a row, with its key.
+///   - `render_detail` - An optional `Callback<T, Html>` rendering the detail panel of an expanded row, shown in a full-width row beneath it.
 ///
 /// # Returns
 /// (Html): A rendered `<tbody>` element, containing:
 ///   - A loading row if `loading` is `true`.
+///   - An error row with a retry button if `error` is set.
 ///   - An empty state row if `rows` is empty.
-///   - The list of rows otherwise.
+///   - The list of rows otherwise, followed by the loading or error row and, with infinite
+///     scroll, a sentinel row while rows are being appended.
+///   - Each row carries its position as `data-row`; virtualized rows sit between two spacer rows.
+///   - Each expanded row is followed by a row holding its detail panel.
 ///
 /// # Examples
 /// ```rust
@@ -26,6 +54,7 @@ use yew::prelude::*;
 /// use table_rs::yew::types::{TableBodyProps, Column, TableClasses, TableTexts};
 /// use yew::prelude::*;
 /// use maplit::hashmap;
+/// use std::rc::Rc;
 ///
 /// #[function_component(App)]
 /// pub fn app() -> Html {
@@ -41,10 +70,28 @@ use yew::prelude::*;
 ///    
 ///     let props = TableBodyProps {
 ///         columns,
-///         rows,
+///         rows: Rc::new(rows),
 ///         loading: false,
+///         error: None,
+///         on_retry: Callback::noop(),
+///         pagination_mode: Default::default(),
+///         has_more: false,
+///         on_load_more: Callback::noop(),
 ///         classes: Default::default(),
 ///         texts: Default::default(),
+///         highlight: Default::default(),
+///         search_options: Default::default(),
+///         virtual_window: None,
+///         on_measure: None,
+///         column_window: None,
+///         selection: Default::default(),
+///         row_keys: Default::default(),
+///         selected: Default::default(),
+///         on_select: Callback::noop(),
+///         expandable: false,
+///         expanded: Default::default(),
+///         on_expand: Callback::noop(),
+///         render_detail: None,
 ///     };
 ///    
 ///     html! {
@@ -56,38 +103,280 @@ use yew::prelude::*;
 /// # See Also
 /// - [MDN tbody Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/tbody)
 #[function_component(TableBody)]
-pub fn body(props: &TableBodyProps) -> Html {
+pub fn body<T = HashMap<&'static str, String>>(props: &TableBodyProps<T>) -> Html
+where
+    T: TableRow + PartialEq + Clone + 'static,
+{
     let TableBodyProps {
         columns,
         rows,
         loading,
+        error,
+        on_retry,
+        pagination_mode,
+        has_more,
+        on_load_more,
         classes,
         texts,
+        highlight,
+        search_options,
+        virtual_window,
+        on_measure,
+        column_window,
+        selection,
+        row_keys,
+        selected,
+        on_select,
+        expandable,
+        expanded,
+        on_expand,
+        render_detail,
     } = props;
+    let tbody = use_node_ref();
+    let slots = column_window.as_ref().map_or_else(
+        || ColumnSlot::all(columns.len()),
+        |window| window.slots.clone(),
+    );
+    let colspan = slots.len() + usize::from(*expandable) + usize::from(selection.is_enabled());
 
-    let empty_string = String::new();
+    // Report the rendered heights once the rows are in the DOM
+    {
+        let tbody = tbody.clone();
+        let on_measure = on_measure.clone();
+        use_effect(move || {
+            if let (Some(on_measure), Some(tbody)) = (on_measure, tbody.cast::<Element>()) {
+                let children = tbody.children();
+                let heights = (0..children.length())
+                    .filter_map(|idx| children.item(idx))
+                    .filter_map(|row| {
+                        let position = row.get_attribute("data-row")?.parse().ok()?;
+                        Some((position, row.get_bounding_client_rect().height()))
+                    })
+                    .collect();
+                on_measure.emit(heights);
+            }
+        });
+    }
+
+    // In the appending modes the rows stay put while the next page loads or fails below them
+    let appending = pagination_mode.appends() && !rows.is_empty();
+    let status = if *loading {
+        html! {
+            <tr class={classes.loading_row}><td colspan={colspan.to_string()}>{ texts.loading }</td></tr>
+        }
+    } else if error.is_some() {
+        let onclick = on_retry.reform(|_: MouseEvent| ());
+        html! {
+            <tr class={classes.error_row} role="alert">
+                <td colspan={colspan.to_string()}>
+                    { texts.error }
+                    <button class={classes.retry_button} type="button" {onclick}>{ texts.retry }</button>
+                </td>
+            </tr>
+        }
+    } else if rows.is_empty() {
+        html! {
+            <tr class={classes.empty_row}><td colspan={colspan.to_string()}>{ texts.empty }</td></tr>
+        }
+    } else {
+        html! {}
+    };
+    let show_rows = appending || (!*loading && error.is_none() && !rows.is_empty());
+    let show_sentinel = appending
+        && *pagination_mode == PaginationMode::InfiniteScroll
+        && *has_more
+        && !*loading
+        && error.is_none();
+    let first = virtual_window
+        .as_ref()
+        .map_or(0, |window| window.range.start);
+    let spacer = |height: f64| {
+        if show_rows && height > 0.0 {
+            html! {
+                <tr class={classes.spacer_row} aria-hidden="true" style={format!("height: {height}px;")}>
+                    <td colspan={colspan.to_string()} style="padding: 0; border: 0;"></td>
+                </tr>
+            }
+        } else {
+            html! {}
+        }
+    };
+
+    let key_of = |idx: usize| {
+        row_keys
+            .get(idx)
+            .cloned()
+            .unwrap_or_else(|| (first + idx).to_string())
+    };
+    let is_selected = |idx: usize| selection.is_enabled() && selected.contains(&key_of(idx));
+    let row_class = |idx: usize| {
+        if is_selected(idx) {
+            format!("{} {}", classes.row, classes.selected_row)
+        } else {
+            classes.row.to_string()
+        }
+    };
+    let select_cell = |idx: usize| {
+        if !selection.is_enabled() {
+            return html! {};
+        }
+        let key = key_of(idx);
+        let on_select = on_select.clone();
+        let onclick =
+            Callback::from(move |e: MouseEvent| on_select.emit((key.clone(), e.shift_key())));
+        html! {
+            <td class={classes.select_cell} role="cell">
+                <input type="checkbox" checked={is_selected(idx)} aria-label={texts.select_row} {onclick} />
+            </td>
+        }
+    };
+
+    let is_expanded = |idx: usize| *expandable && expanded.contains(&key_of(idx));
+    let expand_cell = |idx: usize| {
+        if !*expandable {
+            return html! {};
+        }
+        let key = key_of(idx);
+        let onclick = on_expand.reform(move |_: MouseEvent| key.clone());
+        html! {
+            <td class={classes.expand_cell} role="cell">
+                <button
+                    class={classes.expand_button}
+                    type="button"
+                    aria-expanded={is_expanded(idx).to_string()}
+                    aria-label={texts.expand_row}
+                    {onclick}
+                >
+                    { if is_expanded(idx) { "▾" } else { "▸" } }
+                </button>
+            </td>
+        }
+    };
+    let detail_row = |idx: usize, row: &T| match render_detail {
+        Some(render_detail) if is_expanded(idx) => html! {
+            <tr class={classes.detail_row} role="row">
+                <td colspan={colspan.to_string()} role="cell">{ render_detail.emit(row.clone()) }</td>
+            </tr>
+        },
+        _ => html! {},
+    };
 
     html! {
-        <tbody class={classes.tbody}>
-            { if *loading {
-                    html! {
-                        <tr class={classes.loading_row}><td colspan={columns.len().to_string()}>{ texts.loading }</td></tr>
-                    }
-                } else if rows.is_empty() {
-                    html! {
-                        <tr class={classes.empty_row}><td colspan={columns.len().to_string()}>{ texts.empty }</td></tr>
-                    }
-                } else {
+        <tbody ref={tbody} class={classes.tbody}>
+            { spacer(virtual_window.as_ref().map_or(0.0, |window| window.before)) }
+            { if show_rows {
                     html! {
-                        for row in rows.iter() {
-                                <tr class={classes.row} role="row">
-                                        for col in columns.iter() {
-                                                <td class={classes.body_cell} role="cell">{ row.get(col.id).unwrap_or(&empty_string) }</td>
+                        for (idx, row) in rows.iter().enumerate() {
+                            <key={key_of(idx)}>
+                                <tr
+                                    class={row_class(idx)}
+                                    role="row"
+                                    data-row={(first + idx).to_string()}
+                                    aria-selected={selection.is_enabled().then(|| is_selected(idx).to_string())}
+                                >
+                                        { expand_cell(idx) }
+                                        { select_cell(idx) }
+                                        for slot in slots.iter() {
+                                                { match *slot {
+                                                    ColumnSlot::Column { index, .. } => {
+                                                        let col = &columns[index];
+                                                        html! {
+                                                            <td class={classes.body_cell} role="cell" style={slot.style()}>{ cell_content(&row.cell(col.id).unwrap_or_default(), if col.searchable { highlight } else { "" }, search_options, classes.search_highlight) }</td>
+                                                        }
+                                                    }
+                                                    ColumnSlot::Spacer(_) => html! { <td aria-hidden="true" style={slot.style()}></td> },
+                                                } }
                                         }
                                 </tr>
+                                { detail_row(idx, row) }
+                            </>
                         }
                     }
+                } else {
+                    html! {}
+                } }
+            { spacer(virtual_window.as_ref().map_or(0.0, |window| window.after)) }
+            { status }
+            { if show_sentinel {
+                    html! {
+                        <LoadMoreSentinel {colspan} class={classes.sentinel_row} on_visible={on_load_more.clone()} />
+                    }
+                } else {
+                    html! {}
                 } }
         </tbody>
     }
 }
+
+#[derive(Properties, PartialEq)]
+struct LoadMoreSentinelProps {
+    colspan: usize,
+    class: &'static str,
+    on_visible: Callback<()>,
+}
+
+/// An empty row that triggers `on_visible` whenever it scrolls into view, using an
+/// `IntersectionObserver`.
+#[function_component(LoadMoreSentinel)]
+fn load_more_sentinel(props: &LoadMoreSentinelProps) -> Html {
+    let node = use_node_ref();
+
+    {
+        let node = node.clone();
+        // Observing again after each render reports the row right away if it is still visible,
+        // so short pages keep loading until the viewport is filled.
+        use_effect_with(props.on_visible.clone(), move |on_visible| {
+            let on_visible = on_visible.clone();
+            let callback = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+                let visible = entries.iter().any(|entry| {
+                    entry
+                        .unchecked_into::<IntersectionObserverEntry>()
+                        .is_intersecting()
+                });
+                if visible {
+                    on_visible.emit(());
+                }
+            });
+            let observer = IntersectionObserver::new(callback.as_ref().unchecked_ref()).ok();
+            if let (Some(observer), Some(row)) = (&observer, node.cast::<Element>()) {
+                observer.observe(&row);
+            }
+
+            move || {
+                if let Some(observer) = observer {
+                    observer.disconnect();
+                }
+                drop(callback);
+            }
+        });
+    }
+
+    html! {
+        <tr ref={node} class={props.class} aria-hidden="true">
+            <td colspan={props.colspan.to_string()}></td>
+        </tr>
+    }
+}
+
+/// Renders the text of a cell, marking the characters matched by the fuzzy `highlight` query.
+fn cell_content(
+    text: &str,
+    highlight: &str,
+    options: &SearchOptions,
+    mark_class: &'static str,
+) -> Html {
+    if highlight.trim().is_empty() {
+        return html! { text.to_string() };
+    }
+
+    fuzzy_highlight(text, highlight, options)
+        .into_iter()
+        .map(|(segment, matched)| {
+            if matched {
+                html! { <mark class={mark_class}>{ segment.to_string() }</mark> }
+            } else {
+                html! { segment.to_string() }
+            }
+        })
+        .collect()
+}
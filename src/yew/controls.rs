@@ -1,4 +1,4 @@
-use crate::yew::types::PaginationControlsProps;
+use crate::yew::types::{PaginationControlsProps, PaginationMode};
 use yew::prelude::*;
 
 #[function_component(PaginationControls)]
@@ -6,11 +6,44 @@ pub fn pagination_controls(props: &PaginationControlsProps) -> Html {
     let PaginationControlsProps {
         page,
         total_pages,
+        pagination_mode,
+        on_load_more,
         classes,
         texts,
     } = props;
     let page_val = **page;
 
+    match pagination_mode {
+        PaginationMode::Pages => {}
+        PaginationMode::LoadMore => {
+            let onclick = match on_load_more {
+                Some(on_load_more) => on_load_more.reform(|_: MouseEvent| ()),
+                None => {
+                    let page = page.clone();
+                    let total_pages = *total_pages;
+                    Callback::from(move |_| {
+                        if *page + 1 < total_pages {
+                            page.set(*page + 1);
+                        }
+                    })
+                }
+            };
+            return html! {
+                <div class={classes.pagination}>
+                    <button
+                        class={classes.load_more_button}
+                        {onclick}
+                        disabled={page_val + 1 >= *total_pages}
+                    >
+                        { texts.load_more }
+                    </button>
+                </div>
+            };
+        }
+        // The sentinel row at the end of the body loads the next page
+        PaginationMode::InfiniteScroll => return html! {},
+    }
+
     let on_prev = {
         let page = page.clone();
         Callback::from(move |_| {
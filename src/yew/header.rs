@@ -1,4 +1,9 @@
-use crate::yew::types::{SortOrder, TableHeaderProps};
+use crate::core::sort_key_position;
+use crate::yew::types::{
+    CheckState, Column, ColumnFilter, ColumnSlot, FilterValue, SelectionMode, TableClasses,
+    TableHeaderProps, TableTexts,
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 /// A table header component that renders column headers with optional sorting functionality.
@@ -10,16 +15,28 @@ use yew::prelude::*;
 /// # Arguments
 /// * `props` - The properties passed to the component.
 ///   - `columns` - A list of column definitions (`Vec<Column>`) specifying the headers to render.
-///   - `sort_column` - An `Option<&'static str>` indicating the currently sorted column, if any.
-///   - `sort_order` - A `SortOrder` indicating whether the sort is ascending or descending.
-///   - `on_sort_column` - A `Callback<&'static str>` triggered when a sortable column is clicked.
+///   - `sort` - A `Vec<SortKey>` of the sorted columns and their directions, highest priority first.
+///   - `on_sort_column` - A `Callback<(&'static str, bool)>` triggered when a sortable column is
+///     clicked, with the column id and whether shift was held to add a secondary sort key.
+///   - `filters` - A `BTreeMap<&'static str, FilterValue>` of the current filter inputs by column id.
+///   - `on_filter` - A `Callback<(&'static str, FilterValue)>` triggered when a filter input changes.
 ///   - `classes` - A `TableClasses` object defining CSS class names for customization.
+///   - `texts` - A `TableTexts` object providing the filter row labels.
+///   - `sticky` - A `bool` keeping the header at the top of the scrolling area while rows scroll under it.
+///   - `column_window` - An optional `ColumnWindow` restricting the header and filter cells to the pinned columns and those in view, between spacer cells.
+///   - `selection` - A `SelectionMode`; unless `None`, a cell is added before the headers, holding a tri-state "select all" checkbox in `Multiple` mode.
+///   - `select_all` - The `CheckState` of the "select all" checkbox.
+///   - `on_select_all` - A `Callback<bool>` triggered by the "select all" checkbox, with whether to select the rows it covers.
+///   - `expandable` - A `bool` adding a first cell with an "expand all" button, whose `aria-expanded` tells whether every row of the page is expanded.
+///   - `all_expanded` - A `bool` telling whether every row of the page is expanded.
+///   - `on_expand_all` - A `Callback<bool>` triggered by the "expand all" button, with whether to expand the rows of the page.
 ///
 /// # Returns
 /// (Html): A rendered `<thead>` element containing the table header row and interactive sorting logic.
 ///
 /// # Examples
 /// ```rust
+/// use std::collections::BTreeMap;
 /// use table_rs::yew::header::TableHeader;
 /// use table_rs::yew::types::{TableHeaderProps, Column, SortOrder, TableClasses};
 /// use yew::prelude::*;
@@ -31,15 +48,27 @@ use yew::prelude::*;
 ///         Column { id: "email", header: "Email", sortable: false, ..Default::default() },
 ///     ];
 ///
-///     let sort_order = use_state(|| SortOrder::Asc);
-///     let sort_column = use_state(|| Some("name"));
+///     let sort = use_state(|| vec![("name", SortOrder::Asc)]);
+///     let filters = use_state(BTreeMap::new);
 ///
 ///     let props = TableHeaderProps {
 ///         columns,
-///         sort_column: sort_column,
-///         sort_order: sort_order,
-///         on_sort_column: Callback::from(|col_id| web_sys::console::log_1(&format!("Sort: {}", col_id).into())),
+///         sort,
+///         on_sort_column: Callback::from(|(col_id, additive): (&'static str, bool)| {
+///             web_sys::console::log_1(&format!("Sort: {} (shift: {})", col_id, additive).into())
+///         }),
+///         filters,
+///         on_filter: Callback::noop(),
+///         texts: Default::default(),
 ///         classes: Default::default(),
+///         sticky: false,
+///         column_window: None,
+///         selection: Default::default(),
+///         select_all: Default::default(),
+///         on_select_all: Callback::noop(),
+///         expandable: false,
+///         all_expanded: false,
+///         on_expand_all: Callback::noop(),
 ///     };
 ///    
 ///     html! {
@@ -54,20 +83,93 @@ use yew::prelude::*;
 pub fn header(props: &TableHeaderProps) -> Html {
     let TableHeaderProps {
         columns,
-        sort_column,
-        sort_order,
+        sort,
         on_sort_column,
+        filters,
+        on_filter,
         classes,
+        texts,
+        sticky,
+        column_window,
+        selection,
+        select_all,
+        on_select_all,
+        expandable,
+        all_expanded,
+        on_expand_all,
     } = props;
+    let style = if *sticky {
+        "position: sticky; top: 0; z-index: 2;"
+    } else {
+        ""
+    };
+    let slots = column_window.as_ref().map_or_else(
+        || ColumnSlot::all(columns.len()),
+        |window| window.slots.clone(),
+    );
+    let multi_sort = sort.len() > 1;
+    let has_filters = columns.iter().any(|col| col.filter.is_some());
+    let leading_cells = usize::from(*expandable) + usize::from(selection.is_enabled());
+    let expand_all_cell = if *expandable {
+        let expand = !*all_expanded;
+        let onclick = on_expand_all.reform(move |_: MouseEvent| expand);
+        html! {
+            <th class={classes.expand_cell} role="columnheader">
+                <button
+                    class={classes.expand_button}
+                    type="button"
+                    aria-expanded={all_expanded.to_string()}
+                    aria-label={texts.expand_all}
+                    {onclick}
+                >
+                    { if *all_expanded { "▾" } else { "▸" } }
+                </button>
+            </th>
+        }
+    } else {
+        html! {}
+    };
+    let select_all_cell = match selection {
+        SelectionMode::None => html! {},
+        SelectionMode::Single => {
+            html! { <th class={classes.select_cell} role="columnheader"></th> }
+        }
+        SelectionMode::Multiple => {
+            let checked = *select_all == CheckState::Checked;
+            let on_select_all = on_select_all.clone();
+            let onclick = Callback::from(move |_: MouseEvent| on_select_all.emit(!checked));
+            html! {
+                <th class={classes.select_cell} role="columnheader">
+                    <input
+                        type="checkbox"
+                        {checked}
+                        ~indeterminate={*select_all == CheckState::Indeterminate}
+                        aria-checked={select_all.aria_checked()}
+                        aria-label={texts.select_all}
+                        {onclick}
+                    />
+                </th>
+            }
+        }
+    };
 
     html! {
-        <thead class={classes.thead}>
+        <thead class={classes.thead} {style}>
             <tr class={classes.row} role="row">
-                { for columns.iter().map(|col| {
+                { expand_all_cell }
+                { select_all_cell }
+                { for slots.iter().map(|slot| {
+                    let ColumnSlot::Column { index, .. } = *slot else {
+                        return html! { <th aria-hidden="true" style={slot.style()}></th> };
+                    };
+                    let col = &columns[index];
                     let col_id = col.id;
+                    let position = sort_key_position(sort, col_id);
                     let onclick = if col.sortable {
                         let on_sort_column = on_sort_column.clone();
-                        Some(Callback::from(move |_| on_sort_column.emit(col_id)))
+                        Some(Callback::from(move |e: MouseEvent| {
+                            on_sort_column.emit((col_id, e.shift_key()))
+                        }))
                     } else { None };
 
                     html! {
@@ -75,23 +177,159 @@ pub fn header(props: &TableHeaderProps) -> Html {
                             {onclick}
                             role="columnheader"
                             class={format!("{} {}", classes.header_cell, col.class.unwrap_or("")).trim().to_string()}
-                            style={col.style.unwrap_or_default()}
+                            style={format!("{} {}", col.style.unwrap_or_default(), slot.style()).trim().to_string()}
                             aria-sort={
-                                if Some(col.id) == **sort_column {
-                                    match **sort_order {
-                                        SortOrder::Asc => "ascending",
-                                        SortOrder::Desc => "descending",
-                                    }
-                                } else {
-                                    "none"
+                                match position {
+                                    Some((0, order)) => order.aria_sort(),
+                                    _ => "none",
                                 }
                             }
                         >
                             { col.header }
+                            { match position {
+                                Some((idx, _)) if multi_sort => html! {
+                                    <span
+                                        class={classes.sort_badge}
+                                        aria-label={format!("sort priority {}", idx + 1)}
+                                    >
+                                        { idx + 1 }
+                                    </span>
+                                },
+                                _ => html! {},
+                            } }
                         </th>
                     }
                 }) }
             </tr>
+            { if has_filters {
+                html! {
+                    <tr class={classes.filter_row} role="row">
+                        { for (0..leading_cells).map(|_| html! { <td class={classes.filter_cell}></td> }) }
+                        { for slots.iter().map(|slot| match *slot {
+                            ColumnSlot::Column { index, .. } => {
+                                let col = &columns[index];
+                                html! {
+                                    <td class={classes.filter_cell} style={slot.style()}>
+                                        { filter_widget(col, filters.get(col.id), on_filter, classes, texts) }
+                                    </td>
+                                }
+                            }
+                            ColumnSlot::Spacer(_) => html! { <td aria-hidden="true" style={slot.style()}></td> },
+                        }) }
+                    </tr>
+                }
+            } else {
+                html! {}
+            } }
         </thead>
     }
 }
+
+/// Renders the input widget(s) matching the column's `ColumnFilter`, or nothing without one.
+fn filter_widget(
+    col: &Column,
+    value: Option<&FilterValue>,
+    on_filter: &Callback<(&'static str, FilterValue)>,
+    classes: &TableClasses,
+    texts: &TableTexts,
+) -> Html {
+    let id = col.id;
+    let label = format!("Filter {}", col.header);
+    let value = value.cloned().unwrap_or_default();
+
+    match col.filter {
+        None => html! {},
+        Some(ColumnFilter::Text | ColumnFilter::Equals) => {
+            let on_filter = on_filter.clone();
+            let oninput = Callback::from(move |e: InputEvent| {
+                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                    on_filter.emit((id, FilterValue::Text(input.value())));
+                }
+            });
+
+            html! {
+                <input
+                    class={classes.filter_input}
+                    type="text"
+                    value={value.text().to_string()}
+                    aria-label={label}
+                    {oninput}
+                />
+            }
+        }
+        Some(ColumnFilter::Select(options)) => {
+            let on_filter = on_filter.clone();
+            let onchange = Callback::from(move |e: Event| {
+                if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                    on_filter.emit((id, FilterValue::Text(select.value())));
+                }
+            });
+
+            html! {
+                <select class={classes.filter_input} aria-label={label} {onchange}>
+                    <option value="" selected={value.text().is_empty()}>{ texts.filter_all }</option>
+                    { for options.iter().map(|option| html! {
+                        <option value={*option} selected={value.text() == *option}>{ *option }</option>
+                    }) }
+                </select>
+            }
+        }
+        Some(filter @ (ColumnFilter::NumberRange | ColumnFilter::DateRange)) => {
+            let input_type = if filter == ColumnFilter::NumberRange {
+                "number"
+            } else {
+                "date"
+            };
+            let (min, max) = value.range();
+            let (min, max) = (min.to_string(), max.to_string());
+
+            let on_min = {
+                let on_filter = on_filter.clone();
+                let max = max.clone();
+                Callback::from(move |e: InputEvent| {
+                    if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                        let range = FilterValue::Range {
+                            min: input.value(),
+                            max: max.clone(),
+                        };
+                        on_filter.emit((id, range));
+                    }
+                })
+            };
+            let on_max = {
+                let on_filter = on_filter.clone();
+                let min = min.clone();
+                Callback::from(move |e: InputEvent| {
+                    if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                        let range = FilterValue::Range {
+                            min: min.clone(),
+                            max: input.value(),
+                        };
+                        on_filter.emit((id, range));
+                    }
+                })
+            };
+
+            html! {
+                <>
+                    <input
+                        class={classes.filter_input}
+                        type={input_type}
+                        value={min}
+                        placeholder={texts.filter_min}
+                        aria-label={format!("{label} ({})", texts.filter_min)}
+                        oninput={on_min}
+                    />
+                    <input
+                        class={classes.filter_input}
+                        type={input_type}
+                        value={max}
+                        placeholder={texts.filter_max}
+                        aria-label={format!("{label} ({})", texts.filter_max)}
+                        oninput={on_max}
+                    />
+                </>
+            }
+        }
+    }
+}
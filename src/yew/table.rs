@@ -1,13 +1,25 @@
 use gloo_timers::callback::Timeout;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::rc::Rc;
 use web_sys::UrlSearchParams;
 use web_sys::wasm_bindgen::JsValue;
 use yew::prelude::*;
 
+use crate::core::{
+    CheckState, LoadedPages, RowHeight, RowHeights, SEARCH_DEBOUNCE_MS, SearchIndex, Selection,
+    SelectionMode, SlicedView, SortCycle, TableRow, TableState, ViewData, matching_rows, page_view,
+    row_keys, search_columns, sort_rows, toggle_sort_key,
+};
 use crate::yew::body::TableBody;
 use crate::yew::controls::PaginationControls;
 use crate::yew::header::TableHeader;
-use crate::yew::types::SortOrder;
+use crate::yew::types::FilterValue;
+use crate::yew::types::SearchSyntax;
 use crate::yew::types::TableProps;
+use crate::yew::types::ViewWorker;
+
+/// Addresses and lengths of the `data` and `columns` props, which change whenever they do.
+type Identity = (usize, usize, usize, usize);
 
 /// A fully featured table component with pagination, sorting, and search support.
 ///
@@ -17,20 +29,50 @@ use crate::yew::types::TableProps;
 ///
 /// # Arguments
 /// * `props` - The properties passed to the component.
-///   - `data` - A `Vec<HashMap<&'static str, String>>` representing the table's row data.
+///   - `data` - A `Vec<T>` of rows implementing `TableRow`, such as `HashMap<&'static str, String>`.
+///   - `data_source` - An optional `DataSourceHandle<T>` fetching each page asynchronously instead of using `data`; the source then does the searching, filtering, sorting and paging.
+///   - `retry` - A `RetryPolicy` for failed `data_source` fetches, retried with exponential backoff before an error row with a retry button is shown.
 ///   - `columns` - A `Vec<Column>` defining the structure and behavior of each column.
 ///   - `page_size` - A `usize` defining how many rows to show per page.
 ///   - `loading` - A `bool` indicating whether the table is in a loading state.
 ///   - `classes` - A `TableClasses` struct for customizing class names of elements.
 ///   - `styles` - A `HashMap<&'static str, &'static str>` for inline style overrides.
 ///   - `paginate` - A `bool` controlling whether pagination controls are displayed.
+///   - `pagination_mode` - A `PaginationMode`: `Pages` flips pages, `LoadMore` appends the next page from a button and `InfiniteScroll` when the end of the table scrolls into view.
+///   - `virtualize` - An optional `VirtualScroll` rendering only the rows in view inside a scrolling area of fixed height, for fixed or measured row heights.
+///   - `virtualize_columns` - An optional `VirtualColumns` rendering only the pinned columns and those in view, for tables with hundreds of columns.
 ///   - `search` - A `bool` enabling a search input above the table.
+///   - `search_syntax` - A `SearchSyntax`; `Structured` reads the search input as a query such as `status:open priority:>2 -assignee:bot`, `Fuzzy` tolerates typos, ranks rows by relevance and highlights the matches.
+///   - `search_options` - A `SearchOptions` controlling case folding, accent stripping and punctuation handling during search.
+///   - `search_fields` - A `Vec<&'static str>` of row keys searched without being displayed, such as tags or ids.
+///   - `search_index` - An optional `SearchIndexKind` normalizing the searched cells once per change of `data` (optionally with a trigram index) for fast plain searches of large data.
+///   - `worker` - An optional `ViewWorker` searching, filtering and sorting `data` in a Web Worker, so that large data sets do not block input.
+///   - `time_slicing` - An optional `TimeSlicing` searching, filtering and sorting `data` a slice at a time between renders, for large data sets without a worker.
+///   - `default_sort` - A `Vec<SortKey>` applied on first render, e.g. `vec![("date", SortOrder::Desc)]`.
+///   - `allow_unsorted` - A `bool` letting a third header click unsort the column, restoring the data order.
+///   - `row_key` - An optional `RowKey` (a column id or a function of the row) keying each `<tr>` and identifying rows in the selection, so that DOM state follows rows as they are sorted and paged.
+///   - `selection` - A `SelectionMode`; `Single` or `Multiple` add a checkbox column, with shift-click selecting ranges and a "select all" checkbox in `Multiple` mode.
+///   - `select_all_filtered` - A `bool` making "select all" cover every row matching the search and filters rather than the current page.
+///   - `on_selection_change` - A `Callback<Vec<String>>` receiving the keys of the selected rows whenever the selection changes.
+///   - `expandable` - A `bool` adding a column of buttons that show or hide the detail panel of each row, and an "expand all" button in its header.
+///   - `render_detail` - An optional `Callback<T, Html>` rendering the detail panel of an expanded row, in a full-width row beneath it.
+///   - `expanded` - An optional `Vec<String>` of the keys of the expanded rows, for a parent controlling the expansion; without it the table keeps track of it.
+///   - `on_expand_change` - A `Callback<Vec<String>>` receiving the keys of the expanded rows whenever a row is expanded or collapsed.
 ///   - `texts` - A `TableTexts` struct for customizing placeholder and fallback texts.
 ///
 /// # Features
-/// - **Client-side search** with URL hydration via `?search=`
-/// - **Column sorting** (ascending/descending toggle)
-/// - **Pagination controls**
+/// - **Client-side search** with URL hydration via `?search=`, optionally with `field:value` query syntax
+/// - **Column sorting** (ascending/descending toggle, shift-click to sort by several columns)
+/// - **Per-column filters** (text, equals, select, numeric and date ranges) in a filter row, combined with AND
+/// - **Server-side data** through a `DataSource`, refetched when the page, sort, filters or debounced query change
+/// - **Request lifecycle**: responses to superseded requests are ignored, failures are retried with backoff
+/// - **Pagination controls**, or "load more" and infinite scroll for feeds, with cursor-based continuation for data sources
+/// - **Row virtualization** with spacer rows and a sticky header, for pages of many thousands of rows
+/// - **Column virtualization** with spacer cells and pinned columns, for very wide tables
+/// - **Web Worker offload** of searching, filtering and sorting, with the loading row shown meanwhile
+/// - **Time slicing** of searching, filtering and sorting, for large data sets without a worker
+/// - **Row selection** kept across pages, sorting and filtering, with a tri-state "select all" checkbox
+/// - **Expandable rows** with a custom detail panel, controlled or uncontrolled, and an "expand all" toggle
 /// - **Custom class and inline style support**
 /// - Displays a loading row or empty state message when appropriate
 ///
@@ -74,22 +116,46 @@ use crate::yew::types::TableProps;
 /// # See Also
 /// - [MDN table Element](https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Elements/table)
 #[function_component(Table)]
-pub fn table(props: &TableProps) -> Html {
+pub fn table<T = HashMap<&'static str, String>>(props: &TableProps<T>) -> Html
+where
+    T: TableRow + PartialEq + Clone + 'static,
+{
     let TableProps {
         data,
+        data_source,
+        retry,
         columns,
         page_size,
         loading,
         classes,
         styles,
         paginate,
+        pagination_mode,
+        virtualize,
+        virtualize_columns,
         search,
+        search_syntax,
+        search_options,
+        search_fields,
+        search_index,
+        worker,
+        time_slicing,
+        default_sort,
+        allow_unsorted,
+        row_key,
+        selection,
+        select_all_filtered,
+        on_selection_change,
+        expandable,
+        render_detail,
+        expanded,
+        on_expand_change,
         texts,
     } = props;
 
     let page = use_state(|| 0);
-    let sort_column = use_state(|| None::<&'static str>);
-    let sort_order = use_state(|| SortOrder::Asc);
+    let sort = use_state(|| default_sort.clone());
+    let filters = use_state(BTreeMap::<&'static str, FilterValue>::new);
     let search_query = use_state(|| {
         web_sys::window()
             .and_then(|w| w.location().search().ok())
@@ -99,18 +165,28 @@ pub fn table(props: &TableProps) -> Html {
     });
 
     let debounced_search = use_mut_ref(|| None::<Timeout>);
-
-    // Reset page to 0 when search query changes to prevent invalid page states
-    {
-        let page = page.clone();
-        let search_query = search_query.clone();
-        use_effect_with(search_query, move |_| {
-            page.set(0);
-        });
-    }
+    let loaded = use_state(LoadedPages::<T>::default);
+    let fetching = use_state(|| false);
+    let remote_error = use_state(|| None::<String>);
+    let reloads = use_state(|| 0_u32);
+    let generation = use_mut_ref(|| 0_u64);
+    let scroll_top = use_mut_ref(|| 0.0_f64);
+    let scroll_left = use_mut_ref(|| 0.0_f64);
+    let heights = use_mut_ref(|| None::<(TableState, RowHeights)>);
+    let index = use_mut_ref(|| None::<(Option<(usize, usize)>, SearchIndex)>);
+    let worker_data = use_mut_ref(|| None::<(ViewWorker, Identity, Vec<&'static str>)>);
+    let worker_sorted = use_state(|| None::<(Identity, Rc<Vec<usize>>)>);
+    let computing = use_state(|| false);
+    let worker_generation = use_mut_ref(|| 0_u64);
+    let sliced = use_mut_ref(|| None::<(Identity, SlicedView, Option<Rc<Vec<usize>>>)>);
+    let next_slice = use_mut_ref(|| None::<Timeout>);
+    let selected = use_state(Rc::<Selection>::default);
+    let expanded_state = use_state(Rc::<BTreeSet<String>>::default);
+    let force_update = use_force_update();
 
     let update_search_url = {
         let search_query = search_query.clone();
+        let page = page.clone();
         Callback::from(move |query: String| {
             let result = web_sys::window().and_then(|window| {
                 let url = window.location().href().ok()?;
@@ -126,8 +202,11 @@ pub fn table(props: &TableProps) -> Html {
             });
 
             // Only update search_query if URL update succeeded or if we're not in a browser environment
+            // The page is reset in the same render as the query, so that no fetch goes out for
+            // the new query with the page, or the cursor, of the old one
             if result.is_some() || web_sys::window().is_none() {
                 search_query.set(query);
+                page.set(0);
             }
         })
     };
@@ -150,8 +229,8 @@ pub fn table(props: &TableProps) -> Html {
                 prev.cancel();
             }
 
-            // Create new debounced timeout (300ms delay)
-            let timeout = Timeout::new(300, move || {
+            // Create new debounced timeout
+            let timeout = Timeout::new(SEARCH_DEBOUNCE_MS, move || {
                 update_search_url.emit(value.clone());
             });
 
@@ -159,68 +238,583 @@ pub fn table(props: &TableProps) -> Html {
         })
     };
 
-    // Work with indices instead of cloning data to reduce memory allocations
-    let mut filtered_indices: Vec<usize> = if !search_query.is_empty() {
-        data.iter()
-            .enumerate()
-            .filter(|(_, row)| {
-                columns.iter().any(|col| {
-                    row.get(col.id)
-                        .map(|v| v.to_lowercase().contains(&search_query.to_lowercase()))
-                        .unwrap_or(false)
-                })
-            })
-            .map(|(idx, _)| idx)
-            .collect()
-    } else {
-        (0..data.len()).collect()
+    let state = TableState {
+        query: (*search_query).clone(),
+        search_syntax: *search_syntax,
+        search_options: *search_options,
+        search_fields: search_fields.clone(),
+        sort: (*sort).clone(),
+        filters: (*filters).clone(),
+        page: *page,
+        page_size: *page_size,
+        pagination_mode: *pagination_mode,
+        cursor: loaded.cursor(*page),
+    };
+    let highlight = match search_syntax {
+        SearchSyntax::Fuzzy => state.query.clone(),
+        _ => String::new(),
+    };
+
+    // Fetch the page from the data source whenever the request changes. Each request gets a
+    // generation number; the cleanup of the effect bumps it, so responses (and pending retries)
+    // of superseded requests are dropped instead of overwriting a newer page.
+    {
+        let loaded = loaded.clone();
+        let fetching = fetching.clone();
+        let remote_error = remote_error.clone();
+        let retry = *retry;
+        use_effect_with(
+            (data_source.clone(), state.clone(), *reloads),
+            move |(data_source, state, _)| {
+                if let Some(data_source) = data_source.clone() {
+                    let state = state.clone();
+                    let current = *generation.borrow();
+                    let is_current = {
+                        let generation = generation.clone();
+                        move || *generation.borrow() == current
+                    };
+                    fetching.set(true);
+                    yew::platform::spawn_local(async move {
+                        let sleep = gloo_timers::future::TimeoutFuture::new;
+                        let Some(result) = data_source
+                            .fetch_with_retry(&state, &retry, sleep, is_current)
+                            .await
+                        else {
+                            return;
+                        };
+                        match result {
+                            Ok(data) => {
+                                // Pages are shared, so only their handles are cloned
+                                let mut next = (*loaded).clone();
+                                next.insert(state.page, data);
+                                loaded.set(next);
+                                remote_error.set(None);
+                            }
+                            Err(err) => {
+                                web_sys::console::error_1(
+                                    &format!("Failed to fetch table rows: {err}").into(),
+                                );
+                                remote_error.set(Some(err));
+                            }
+                        }
+                        fetching.set(false);
+                    });
+                }
+                move || *generation.borrow_mut() += 1
+            },
+        );
+    }
+
+    let on_retry = {
+        let reloads = reloads.clone();
+        Callback::from(move |()| reloads.set(reloads.wrapping_add(1)))
+    };
+
+    // Row heights are measured per position, so start over whenever the rows are reordered or
+    // replaced; appending pages keeps the positions of the rows already shown.
+    if let Some(virtualize) = virtualize {
+        let layout = TableState {
+            page: if pagination_mode.appends() { 0 } else { *page },
+            cursor: None,
+            ..state.clone()
+        };
+        let mut heights = heights.borrow_mut();
+        let stale = heights.as_ref().is_none_or(|(key, heights)| {
+            *key != layout || heights.row_height() != virtualize.row_height
+        });
+        if stale {
+            *heights = Some((layout, RowHeights::new(virtualize.row_height)));
+        }
+    }
+    let window_of = |count: usize| {
+        let virtualize = (*virtualize)?;
+        let heights = heights.borrow();
+        let (_, heights) = heights.as_ref()?;
+        Some(heights.window(
+            count,
+            *scroll_top.borrow(),
+            virtualize.viewport_height,
+            virtualize.overscan,
+        ))
     };
 
-    if let Some(col_id) = *sort_column
-        && let Some(col) = columns.iter().find(|c| c.id == col_id)
+    // Each stage reruns only when its own inputs change, so that flipping pages neither searches
+    // nor sorts again. Props only change by being replaced, so the addresses of the rows and
+    // columns tell whether they did.
+    let is_remote = data_source.is_some();
+    let identity: Identity = (
+        data.as_ptr() as usize,
+        data.len(),
+        columns.as_ptr() as usize,
+        columns.len(),
+    );
+
+    // The worker gets a copy of the rows once per version of them, then a request per change of
+    // the search, filters or sort. As with remote requests, the cleanup of the effect bumps the
+    // generation so that the answers to superseded requests are dropped.
+    let offload = worker
+        .clone()
+        .filter(|_| !is_remote && ViewData::supports(columns));
+    let offloaded = offload.is_some();
+    if let Some(worker) = &offload {
+        let version = (worker.clone(), identity, search_fields.clone());
+        let mut worker_data = worker_data.borrow_mut();
+        if worker_data.as_ref() != Some(&version) {
+            worker.load(ViewData::new(data, columns, search_fields));
+            *worker_data = Some(version);
+        }
+    }
     {
-        let val = "".to_string();
-        filtered_indices.sort_by(|&a, &b| {
-            let a_val = data[a].get(col.id).unwrap_or(&val);
-            let b_val = data[b].get(col.id).unwrap_or(&val);
-            match *sort_order {
-                SortOrder::Asc => a_val.cmp(b_val),
-                SortOrder::Desc => b_val.cmp(a_val),
+        let worker_sorted = worker_sorted.clone();
+        let computing = computing.clone();
+        let request = TableState {
+            page: 0,
+            cursor: None,
+            ..state.clone()
+        };
+        use_effect_with(
+            (offload, identity, request),
+            move |(offload, identity, request)| {
+                match offload {
+                    Some(worker) => {
+                        let identity = *identity;
+                        let sorted = worker.sorted_rows(request);
+                        let current = *worker_generation.borrow();
+                        let generation = worker_generation.clone();
+                        computing.set(true);
+                        yew::platform::spawn_local(async move {
+                            let indices = sorted.await;
+                            if *generation.borrow() == current {
+                                worker_sorted.set(Some((identity, Rc::new(indices))));
+                                computing.set(false);
+                            }
+                        });
+                    }
+                    None if *computing => computing.set(false),
+                    None => {}
+                }
+                move || *worker_generation.borrow_mut() += 1
+            },
+        );
+    }
+
+    // Without a worker, the rows can be searched, filtered and sorted a slice per render, with
+    // a render scheduled after each slice so that the browser handles input in between. Other
+    // rows, search, filters or sort start the computation over, abandoning the current one.
+    let sliced_rows = time_slicing
+        .filter(|_| !is_remote && !offloaded)
+        .map(|time_slicing| {
+            let mut sliced = sliced.borrow_mut();
+            let current = sliced
+                .as_ref()
+                .is_some_and(|(version, view, _)| *version == identity && view.is_for(&state));
+            if !current {
+                *sliced = Some((identity, SlicedView::new(&state), None));
+            }
+            let (_, view, rows) = sliced.as_mut().expect("started above");
+            if rows.is_none() {
+                if view.step(data, columns, time_slicing.budget) {
+                    *rows = view.indices().map(|indices| Rc::new(indices.to_vec()));
+                } else {
+                    let force_update = force_update.clone();
+                    let timeout = Timeout::new(0, move || force_update.force_update());
+                    *next_slice.borrow_mut() = Some(timeout);
+                }
             }
+            rows.clone()
         });
+    let slicing = sliced_rows.is_some();
+
+    // The index follows `data` from one version of it to the next, so only the rows appended or
+    // changed since the last one are normalized
+    match search_index.filter(|_| !is_remote && !offloaded && !slicing) {
+        Some(kind) => {
+            let searched = search_columns(columns, search_fields);
+            let mut index = index.borrow_mut();
+            let reusable = index.as_ref().is_some_and(|(_, index)| {
+                index.kind() == kind && index.covers(&searched, search_options)
+            });
+            if !reusable {
+                *index = Some((None, SearchIndex::new(&searched, *search_options, kind)));
+            }
+            let version = (identity.0, identity.1);
+            if let Some((synced, index)) = index.as_mut()
+                && *synced != Some(version)
+            {
+                index.sync(data);
+                *synced = Some(version);
+            }
+        }
+        None => *index.borrow_mut() = None,
     }
+    let matching = use_memo(
+        (
+            identity,
+            is_remote,
+            offloaded,
+            slicing,
+            *search_index,
+            state.query.clone(),
+            *search_syntax,
+            *search_options,
+            search_fields.clone(),
+            (*filters).clone(),
+        ),
+        |_| match index.borrow().as_ref() {
+            _ if is_remote || offloaded || slicing => Vec::new(),
+            Some((_, index)) => index.matching_rows(data, columns, &state),
+            None => matching_rows(data, columns, &state),
+        },
+    );
+    let sorted = use_memo((identity, matching.clone(), (*sort).clone()), |_| {
+        let mut indices = (*matching).clone();
+        sort_rows(data, columns, &mut indices, &sort);
+        indices
+    });
+    // Until the worker or the last slice has answered for these rows, the loading row stands in
+    // for them
+    let computing_slices = matches!(sliced_rows, Some(None));
+    let sorted = match &*worker_sorted {
+        _ if slicing => sliced_rows.flatten().unwrap_or_default(),
+        _ if !offloaded => sorted,
+        Some((version, indices)) if *version == identity => Rc::clone(indices),
+        _ => Rc::default(),
+    };
 
-    // Ensure page_size is at least 1 to prevent division by zero
-    let page_size_safe = (*page_size).max(1);
-    // Ensure at least 1 page to avoid confusing 'Page 1 of 0' message when empty
-    let total_pages =
-        ((filtered_indices.len() as f64 / page_size_safe as f64).ceil() as usize).max(1);
-
-    // Clamp current page to valid range to prevent showing empty results
-    let current_page = (*page).min(total_pages.saturating_sub(1));
-    let start = current_page * page_size_safe;
-    let end = ((current_page + 1) * page_size_safe).min(filtered_indices.len());
-    let page_rows: Vec<_> = filtered_indices[start..end]
-        .iter()
-        .map(|&idx| data[idx].clone())
-        .collect();
+    // Positions in the loaded rows, or indices into `data`, of the rows on the page (`shown`)
+    // and of those to render
+    let row_count;
+    let (shown, visible, current_page, total_pages, virtual_window) = if is_remote {
+        row_count = loaded.row_count(*page, *pagination_mode);
+        let window = window_of(row_count);
+        let range = window
+            .as_ref()
+            .map_or(0..row_count, |window| window.range.clone());
+        (
+            (0..row_count).collect::<Vec<_>>(),
+            range.collect::<Vec<_>>(),
+            *page,
+            loaded.total_pages(*page_size),
+            window,
+        )
+    } else {
+        let view = page_view(&sorted, &state);
+        row_count = view.indices.len();
+        let window = window_of(row_count);
+        let indices = match &window {
+            Some(window) => view.indices[window.range.clone()].to_vec(),
+            None => view.indices.clone(),
+        };
+        (view.indices, indices, view.page, view.total_pages, window)
+    };
+    // The loaded rows are replaced whenever a page arrives, so their address tells when they did
+    let loaded_key = is_remote.then(|| {
+        let loaded: &LoadedPages<T> = &loaded;
+        (
+            loaded as *const LoadedPages<T> as usize,
+            *page,
+            *pagination_mode,
+        )
+    });
 
-    let on_sort_column = {
-        let sort_column = sort_column.clone();
-        let sort_order = sort_order.clone();
-        Callback::from(move |id: &'static str| {
-            if Some(id) == *sort_column {
-                sort_order.set(match *sort_order {
-                    SortOrder::Asc => SortOrder::Desc,
-                    SortOrder::Desc => SortOrder::Asc,
-                });
+    // Rows are keyed by `row_key`, or else local rows by their index in `data` and remote ones by
+    // their position among the results
+    let key_offset = if is_remote && !pagination_mode.appends() {
+        current_page * *page_size
+    } else {
+        0
+    };
+    let remote_rows: Vec<&T> = if is_remote {
+        loaded.iter(*page, *pagination_mode).collect()
+    } else {
+        Vec::new()
+    };
+    let keys_of = |indices: &[usize]| {
+        let row = |idx: usize| {
+            if is_remote {
+                remote_rows.get(idx).copied()
             } else {
-                sort_column.set(Some(id));
-                sort_order.set(SortOrder::Asc);
+                data.get(idx)
             }
+        };
+        row_keys(*row_key, indices, row, key_offset)
+    };
+    let row_keys = Rc::new(keys_of(&visible));
+    // The display order of all the rows is only needed for ranges and "select all"
+    let order = if is_remote {
+        Rc::new(shown.clone())
+    } else {
+        Rc::clone(&sorted)
+    };
+    let order_keys = use_memo(
+        (
+            identity, loaded_key, order, key_offset, *row_key, *selection,
+        ),
+        |(_, _, order, _, _, selection)| match selection {
+            SelectionMode::Multiple => keys_of(order),
+            _ => Vec::new(),
+        },
+    );
+    let page_keys = Rc::new(keys_of(&shown));
+    let scope = if *select_all_filtered {
+        Rc::clone(&order_keys)
+    } else {
+        Rc::clone(&page_keys)
+    };
+    let select_all = use_memo(
+        (Rc::clone(&*selected), Rc::clone(&scope), *selection),
+        |(selected, scope, selection)| match selection {
+            SelectionMode::Multiple => selected.check_state(scope.iter().cloned()),
+            _ => CheckState::Unchecked,
+        },
+    );
+    let on_select = {
+        let selected = selected.clone();
+        let on_selection_change = on_selection_change.clone();
+        let selection = *selection;
+        Callback::from(move |(key, extend): (String, bool)| {
+            let mut next = (**selected).clone();
+            next.toggle(selection, &key, extend, order_keys.iter().cloned());
+            on_selection_change.emit(next.keys());
+            selected.set(Rc::new(next));
+        })
+    };
+    let on_select_all = {
+        let selected = selected.clone();
+        let on_selection_change = on_selection_change.clone();
+        Callback::from(move |checked: bool| {
+            let mut next = (**selected).clone();
+            next.set_all(scope.iter().cloned(), checked);
+            on_selection_change.emit(next.keys());
+            selected.set(Rc::new(next));
         })
     };
 
+    // A parent passing `expanded` controls the expansion; otherwise the table keeps track of it
+    let expanded_rows = use_memo(
+        (expanded.clone(), Rc::clone(&*expanded_state)),
+        |(expanded, state)| match expanded {
+            Some(keys) => keys.iter().cloned().collect(),
+            None => (**state).clone(),
+        },
+    );
+    let all_expanded =
+        !page_keys.is_empty() && page_keys.iter().all(|key| expanded_rows.contains(key));
+    let set_expanded = {
+        let on_expand_change = on_expand_change.clone();
+        move |next: BTreeSet<String>| {
+            on_expand_change.emit(next.iter().cloned().collect());
+            expanded_state.set(Rc::new(next));
+        }
+    };
+    let on_expand = {
+        let expanded_rows = Rc::clone(&expanded_rows);
+        let set_expanded = set_expanded.clone();
+        Callback::from(move |key: String| {
+            let mut next = (*expanded_rows).clone();
+            if !next.remove(&key) {
+                next.insert(key);
+            }
+            set_expanded(next);
+        })
+    };
+    let on_expand_all = {
+        let expanded_rows = Rc::clone(&expanded_rows);
+        Callback::from(move |expand: bool| {
+            let mut next = (*expanded_rows).clone();
+            for key in page_keys.iter() {
+                if expand {
+                    next.insert(key.clone());
+                } else {
+                    next.remove(key);
+                }
+            }
+            set_expanded(next);
+        })
+    };
+
+    // The rows are cloned once when they scroll or page into view and shared from then on
+    let page_rows = use_memo((identity, loaded_key, visible), |(_, _, visible)| {
+        if is_remote {
+            visible
+                .iter()
+                .filter_map(|&pos| loaded.get(*page, *pagination_mode, pos).cloned())
+                .collect()
+        } else {
+            visible
+                .iter()
+                .map(|&idx| data[idx].clone())
+                .collect::<Vec<_>>()
+        }
+    });
+    let loading = *loading || *fetching || (offloaded && *computing) || computing_slices;
+    let has_more = current_page + 1 < total_pages;
+
+    // Appends the next page, once the current one has arrived so that its cursor is known
+    let on_load_more = {
+        let page = page.clone();
+        let ready = has_more && !loading && (data_source.is_none() || loaded.contains(*page));
+        Callback::from(move |()| {
+            if ready {
+                page.set(current_page + 1);
+            }
+        })
+    };
+
+    let on_sort_column = {
+        let sort = sort.clone();
+        let page = page.clone();
+        let columns = columns.clone();
+        let allow_unsorted = *allow_unsorted;
+        let appends = pagination_mode.appends();
+        Callback::from(move |(id, additive): (&'static str, bool)| {
+            let cycle = SortCycle::for_column(&columns, id, allow_unsorted);
+            let mut keys = (*sort).clone();
+            toggle_sort_key(&mut keys, id, additive, cycle);
+            sort.set(keys);
+            // Appended pages no longer line up with the new order, so start over
+            if appends {
+                page.set(0);
+            }
+        })
+    };
+
+    // Scrolling within the rendered rows needs no render; only a different window does
+    let column_window = virtualize_columns
+        .map(|virtualize_columns| virtualize_columns.window(columns, *scroll_left.borrow()));
+
+    // Scrolling within the rendered rows and columns needs no render; only a different window does
+    let on_scroll = {
+        let heights = heights.clone();
+        let force_update = force_update.clone();
+        let virtualize = *virtualize;
+        let virtualize_columns = *virtualize_columns;
+        let columns = columns.clone();
+        let rendered = virtual_window.as_ref().map(|window| window.range.clone());
+        let rendered_columns = column_window.clone();
+        Callback::from(move |e: Event| {
+            let container: web_sys::Element = e.target_unchecked_into();
+            let top = container.scroll_top() as f64;
+            let left = container.scroll_left() as f64;
+            let range = virtualize.and_then(|virtualize| {
+                let heights = heights.borrow();
+                let (_, heights) = heights.as_ref()?;
+                let window = heights.window(
+                    row_count,
+                    top,
+                    virtualize.viewport_height,
+                    virtualize.overscan,
+                );
+                Some(window.range)
+            });
+            let column_window = virtualize_columns
+                .map(|virtualize_columns| virtualize_columns.window(&columns, left));
+            *scroll_top.borrow_mut() = top;
+            *scroll_left.borrow_mut() = left;
+            if range != rendered || column_window != rendered_columns {
+                force_update.force_update();
+            }
+        })
+    };
+
+    let on_measure = match virtualize.map(|virtualize| virtualize.row_height) {
+        Some(RowHeight::Measured { .. }) => {
+            let heights = heights.clone();
+            Some(Callback::from(move |measured: Vec<(usize, f64)>| {
+                let mut changed = false;
+                if let Some((_, heights)) = heights.borrow_mut().as_mut() {
+                    for (row, height) in measured {
+                        changed |= heights.measure(row, height);
+                    }
+                }
+                if changed {
+                    force_update.force_update();
+                }
+            }))
+        }
+        _ => None,
+    };
+
+    let on_filter = {
+        let filters = filters.clone();
+        let page = page.clone();
+        Callback::from(move |(id, value): (&'static str, FilterValue)| {
+            let mut next = (*filters).clone();
+            next.insert(id, value);
+            filters.set(next);
+            page.set(0);
+        })
+    };
+
+    let mut table_style = styles.get("table").unwrap_or(&"").to_string();
+    let mut scroll_style = String::new();
+    if let Some(virtualize) = virtualize {
+        scroll_style += &format!(
+            "height: {}px; overflow-y: auto;",
+            virtualize.viewport_height
+        );
+    }
+    if let Some(virtualize_columns) = virtualize_columns {
+        scroll_style += &format!(
+            " max-width: {}px; overflow-x: auto;",
+            virtualize_columns.viewport_width
+        );
+    }
+    // A fixed layout keeps the columns from resizing as different rows scroll into view, and
+    // sizes them from the header cells so that the body lines up under the header
+    if virtualize.is_some() || virtualize_columns.is_some() {
+        table_style += " table-layout: fixed;";
+    }
+    if let Some(column_window) = &column_window {
+        table_style += &format!(" width: {}px;", column_window.total_width);
+    }
+    let table = html! {
+        <table class={classes.table} style={table_style} role="table" aria-busy={loading.to_string()}>
+            <TableHeader
+                columns={columns.clone()}
+                {sort}
+                {on_sort_column}
+                {filters}
+                {on_filter}
+                classes={classes.clone()}
+                texts={texts.clone()}
+                sticky={virtualize.is_some()}
+                column_window={column_window.clone()}
+                selection={*selection}
+                select_all={*select_all}
+                {on_select_all}
+                expandable={*expandable}
+                {all_expanded}
+                {on_expand_all}
+            />
+            <TableBody<T>
+                columns={columns.clone()}
+                rows={page_rows}
+                {loading}
+                error={(*remote_error).clone()}
+                {on_retry}
+                pagination_mode={*pagination_mode}
+                {has_more}
+                on_load_more={on_load_more.clone()}
+                classes={classes.clone()}
+                texts={texts.clone()}
+                {highlight}
+                search_options={*search_options}
+                {virtual_window}
+                {on_measure}
+                {column_window}
+                selection={*selection}
+                {row_keys}
+                selected={Rc::clone(&*selected)}
+                {on_select}
+                expandable={*expandable}
+                expanded={expanded_rows}
+                {on_expand}
+                render_detail={render_detail.clone()}
+            />
+        </table>
+    };
+
     html! {
         <div class={classes.container}>
             { if *search {
@@ -237,24 +831,25 @@ pub fn table(props: &TableProps) -> Html {
                 } else {
                     html! {}
                 } }
-            <table class={classes.table} style={*styles.get("table").unwrap_or(&"")} role="table">
-                <TableHeader
-                    columns={columns.clone()}
-                    {sort_column}
-                    {sort_order}
-                    {on_sort_column}
-                    classes={classes.clone()}
-                />
-                <TableBody
-                    columns={columns.clone()}
-                    rows={page_rows.to_vec()}
-                    loading={loading}
-                    classes={classes.clone()}
-                />
-            </table>
+            { if scroll_style.is_empty() {
+                    table
+                } else {
+                    html! {
+                        <div class={classes.scroll_container} style={scroll_style.trim().to_string()} onscroll={on_scroll}>
+                            { table }
+                        </div>
+                    }
+                } }
             { if *paginate {
                     html! {
-                        <PaginationControls {page} {total_pages} />
+                        <PaginationControls
+                            {page}
+                            {total_pages}
+                            pagination_mode={*pagination_mode}
+                            on_load_more={Some(on_load_more)}
+                            classes={classes.clone()}
+                            texts={texts.clone()}
+                        />
                     }
                 } else {
                     html! {}
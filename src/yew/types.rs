@@ -1,8 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::rc::Rc;
 use yew::prelude::*;
 
+use crate::core::{ColumnDef, SortFn, TableColumn, TableRow};
+
+pub use crate::core::{
+    CheckState, ColumnFilter, ColumnKind, ColumnSlot, ColumnWindow, DataFuture, DataPage,
+    DataSource, DataSourceHandle, FilterValue, PaginationMode, RetryPolicy, RowHeight, RowKey,
+    SearchIndexKind, SearchOptions, SearchSyntax, Selection, SelectionMode, SortKey, SortOrder,
+    TimeSlicing, VirtualColumns, VirtualScroll, VirtualWindow,
+};
+pub use crate::worker::ViewWorker;
+
 /// Represents a column in the table with customization options.
-#[derive(Properties, PartialEq, Clone, Default)]
+#[derive(Properties, PartialEq, Clone)]
 pub struct Column {
     /// Unique identifier for the column.
     #[prop_or("")]
@@ -20,10 +31,34 @@ pub struct Column {
     #[prop_or(false)]
     pub sortable: bool,
 
+    /// How values are parsed and compared when sorting (defaults to text).
+    #[prop_or_default]
+    pub kind: ColumnKind,
+
+    /// Custom comparator for sorting, e.g. `SortFn::Natural` or a severity order.
+    #[prop_or_default]
+    pub sort_fn: Option<SortFn>,
+
+    /// Direction of the first click on the header, e.g. `SortOrder::Desc` for newest-first dates.
+    #[prop_or_default]
+    pub first_sort_order: SortOrder,
+
+    /// Filter widget shown for this column in the filter row, if any.
+    #[prop_or_default]
+    pub filter: Option<ColumnFilter>,
+
+    /// Whether the search input looks at this column; turn off for columns such as avatars.
+    #[prop_or(true)]
+    pub searchable: bool,
+
     /// Minimum width of the column in pixels.
     #[prop_or(100)]
     pub min_width: u32,
 
+    /// Whether the column always renders, stuck to the left edge, when the columns are virtualized.
+    #[prop_or(false)]
+    pub pinned: bool,
+
     /// Optional inline style string for the column header.
     #[prop_or(Some("padding: 8px; font-weight: 600; text-align: left;"))]
     pub style: Option<&'static str>,
@@ -33,15 +68,74 @@ pub struct Column {
     pub class: Option<&'static str>,
 }
 
-/// Sort direction for a column: ascending or descending.
-#[derive(Clone, PartialEq, Default)]
-pub enum SortOrder {
-    /// Ascending order (default).
-    #[default]
-    Asc,
+impl TableColumn for Column {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn kind(&self) -> ColumnKind {
+        self.kind
+    }
+
+    fn sort_fn(&self) -> Option<SortFn> {
+        self.sort_fn
+    }
+
+    fn first_sort_order(&self) -> SortOrder {
+        self.first_sort_order
+    }
+
+    fn filter(&self) -> Option<ColumnFilter> {
+        self.filter
+    }
+
+    fn searchable(&self) -> bool {
+        self.searchable
+    }
 
-    /// Descending order.
-    Desc,
+    fn min_width(&self) -> u32 {
+        self.min_width
+    }
+
+    fn pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Self {
+            id: "",
+            header: "",
+            accessor: Callback::default(),
+            sortable: false,
+            kind: ColumnKind::Text,
+            sort_fn: None,
+            first_sort_order: SortOrder::Asc,
+            filter: None,
+            searchable: true,
+            min_width: 0,
+            pinned: false,
+            style: None,
+            class: None,
+        }
+    }
+}
+
+impl From<ColumnDef> for Column {
+    fn from(def: ColumnDef) -> Self {
+        Self {
+            id: def.id,
+            header: def.header,
+            sortable: def.sortable,
+            kind: def.kind,
+            sort_fn: def.sort_fn,
+            first_sort_order: def.first_sort_order,
+            filter: def.filter,
+            searchable: def.searchable,
+            ..Default::default()
+        }
+    }
 }
 
 /// Class names used to style various parts of the table.
@@ -71,6 +165,10 @@ pub struct TableClasses {
     #[prop_or("search-input")]
     pub search_input: &'static str,
 
+    /// Class name for the `<mark>` around characters matched by a fuzzy search.
+    #[prop_or("search-highlight")]
+    pub search_highlight: &'static str,
+
     /// Class name for header cells (`<th>`).
     #[prop_or("th")]
     pub header_cell: &'static str,
@@ -91,9 +189,69 @@ pub struct TableClasses {
     #[prop_or("empty-row")]
     pub empty_row: &'static str,
 
+    /// Class name for the row shown when the data source failed.
+    #[prop_or("error-row")]
+    pub error_row: &'static str,
+
+    /// Class name for the retry button in the error row.
+    #[prop_or("retry-button")]
+    pub retry_button: &'static str,
+
     /// Class name for pagination buttons.
     #[prop_or("pagination-button")]
     pub pagination_button: &'static str,
+
+    /// Class name for the "Load more" button.
+    #[prop_or("load-more-button")]
+    pub load_more_button: &'static str,
+
+    /// Class name for the empty row after the last one that loads more rows when scrolled into view.
+    #[prop_or("sentinel-row")]
+    pub sentinel_row: &'static str,
+
+    /// Class name for the scrolling area wrapping a virtualized table.
+    #[prop_or("table-scroll")]
+    pub scroll_container: &'static str,
+
+    /// Class name for the empty rows that take the place of the rows scrolled out of a virtualized table.
+    #[prop_or("spacer-row")]
+    pub spacer_row: &'static str,
+
+    /// Class name for the sort priority badge shown when sorting by several columns.
+    #[prop_or("sort-priority")]
+    pub sort_badge: &'static str,
+
+    /// Class name for the filter row under the header row.
+    #[prop_or("filter-row")]
+    pub filter_row: &'static str,
+
+    /// Class name for the cells of the filter row.
+    #[prop_or("filter-cell")]
+    pub filter_cell: &'static str,
+
+    /// Class name for the filter inputs and drop-downs.
+    #[prop_or("filter-input")]
+    pub filter_input: &'static str,
+
+    /// Class name added to the selected rows.
+    #[prop_or("selected-row")]
+    pub selected_row: &'static str,
+
+    /// Class name for the cells holding the selection checkboxes.
+    #[prop_or("select-cell")]
+    pub select_cell: &'static str,
+
+    /// Class name for the cells holding the expand buttons.
+    #[prop_or("expand-cell")]
+    pub expand_cell: &'static str,
+
+    /// Class name for the buttons showing and hiding the detail panel of a row.
+    #[prop_or("expand-button")]
+    pub expand_button: &'static str,
+
+    /// Class name for the rows holding the detail panel of an expanded row.
+    #[prop_or("detail-row")]
+    pub detail_row: &'static str,
 }
 
 impl Default for TableClasses {
@@ -105,12 +263,28 @@ impl Default for TableClasses {
             tbody: "tbody",
             pagination: "pagination-controls",
             search_input: "search-input",
+            search_highlight: "search-highlight",
             header_cell: "th",
             body_cell: "td",
             row: "tr",
             loading_row: "loading-row",
             empty_row: "empty-row",
+            error_row: "error-row",
+            retry_button: "retry-button",
             pagination_button: "pagination-button",
+            load_more_button: "load-more-button",
+            sentinel_row: "sentinel-row",
+            scroll_container: "table-scroll",
+            spacer_row: "spacer-row",
+            sort_badge: "sort-priority",
+            filter_row: "filter-row",
+            filter_cell: "filter-cell",
+            filter_input: "filter-input",
+            selected_row: "selected-row",
+            select_cell: "select-cell",
+            expand_cell: "expand-cell",
+            expand_button: "expand-button",
+            detail_row: "detail-row",
         }
     }
 }
@@ -126,6 +300,14 @@ pub struct TableTexts {
     #[prop_or("No results found")]
     pub empty: &'static str,
 
+    /// Text shown when the data source failed to load the rows.
+    #[prop_or("Failed to load data.")]
+    pub error: &'static str,
+
+    /// Label of the button retrying a failed load.
+    #[prop_or("Retry")]
+    pub retry: &'static str,
+
     /// Placeholder text for the search input.
     #[prop_or("Search...")]
     pub search_placeholder: &'static str,
@@ -141,6 +323,38 @@ pub struct TableTexts {
     /// Format string for the page indicator, e.g., "Page 1 of 5".
     #[prop_or("Page {current} of {total}")]
     pub page_indicator: &'static str,
+
+    /// Label for the button appending the next page.
+    #[prop_or("Load more")]
+    pub load_more: &'static str,
+
+    /// Label of the drop-down option that clears a select filter.
+    #[prop_or("All")]
+    pub filter_all: &'static str,
+
+    /// Placeholder and label suffix for the lower bound of a range filter.
+    #[prop_or("Min")]
+    pub filter_min: &'static str,
+
+    /// Placeholder and label suffix for the upper bound of a range filter.
+    #[prop_or("Max")]
+    pub filter_max: &'static str,
+
+    /// Label of the checkbox selecting a row.
+    #[prop_or("Select row")]
+    pub select_row: &'static str,
+
+    /// Label of the checkbox selecting every row.
+    #[prop_or("Select all rows")]
+    pub select_all: &'static str,
+
+    /// Label of the button showing the detail panel of a row.
+    #[prop_or("Show details")]
+    pub expand_row: &'static str,
+
+    /// Label of the button showing the detail panels of every row.
+    #[prop_or("Show all details")]
+    pub expand_all: &'static str,
 }
 
 impl Default for TableTexts {
@@ -148,20 +362,45 @@ impl Default for TableTexts {
         Self {
             loading: "Loading...",
             empty: "No results found",
+            error: "Failed to load data.",
+            retry: "Retry",
             search_placeholder: "Search...",
             previous_button: "Previous",
             next_button: "Next",
             page_indicator: "Page {current} of {total}",
+            load_more: "Load more",
+            filter_all: "All",
+            filter_min: "Min",
+            filter_max: "Max",
+            select_row: "Select row",
+            select_all: "Select all rows",
+            expand_row: "Show details",
+            expand_all: "Show all details",
         }
     }
 }
 
 /// Props for the main table component.
+///
+/// Rows can be any type implementing [`TableRow`]; the default keeps the key-value map form.
 #[derive(Properties, PartialEq, Clone)]
-pub struct TableProps {
-    /// Vector of row data as key-value pairs.
+pub struct TableProps<T = HashMap<&'static str, String>>
+where
+    T: TableRow + PartialEq + Clone + 'static,
+{
+    /// Vector of row data, either key-value pairs or any type implementing `TableRow`.
+    #[prop_or_default]
+    pub data: Vec<T>,
+
+    /// Remote source of rows that replaces `data`, for data sets too large to load at once.
+    ///
+    /// Searching, filtering, sorting and paging are then left to the source.
     #[prop_or_default]
-    pub data: Vec<HashMap<&'static str, String>>,
+    pub data_source: Option<DataSourceHandle<T>>,
+
+    /// How failed `data_source` fetches are retried before the error row is shown.
+    #[prop_or_default]
+    pub retry: RetryPolicy,
 
     /// List of column definitions.
     #[prop_or_default]
@@ -187,10 +426,96 @@ pub struct TableProps {
     #[prop_or(false)]
     pub paginate: bool,
 
+    /// Whether pages are flipped with Previous/Next, or appended with a "Load more" button or by
+    /// scrolling to the end of the table.
+    #[prop_or_default]
+    pub pagination_mode: PaginationMode,
+
+    /// Renders only the rows scrolled into view, inside a scrolling area with a sticky header.
+    ///
+    /// Paging still applies, so pass a `page_size` covering every row to scroll them all.
+    #[prop_or_default]
+    pub virtualize: Option<VirtualScroll>,
+
+    /// Renders only the columns scrolled into view, plus the pinned ones, inside a scrolling area.
+    #[prop_or_default]
+    pub virtualize_columns: Option<VirtualColumns>,
+
     /// Whether to enable search functionality.
     #[prop_or(false)]
     pub search: bool,
 
+    /// How the search input is read: plain substring text, or a structured `field:value` query.
+    #[prop_or_default]
+    pub search_syntax: SearchSyntax,
+
+    /// How the search input and the cells are normalized (case, accents, punctuation) before matching.
+    #[prop_or_default]
+    pub search_options: SearchOptions,
+
+    /// Row keys searched in addition to the searchable columns, such as tags or ids kept off screen.
+    #[prop_or_default]
+    pub search_fields: Vec<&'static str>,
+
+    /// Normalizes the searched cells once into a `SearchIndex` when `data` changes, indexing
+    /// appended rows on their own, so that plain searches of large data stay fast.
+    #[prop_or_default]
+    pub search_index: Option<SearchIndexKind>,
+
+    /// Searches, filters and sorts `data` in a Web Worker instead of on the main thread, showing
+    /// the loading row while it works. Columns with custom `Values` or `Rows` comparators are
+    /// ordered on the main thread as before.
+    #[prop_or_default]
+    pub worker: Option<ViewWorker>,
+
+    /// Searches, filters and sorts `data` a slice at a time between renders, showing the loading
+    /// row until done, so that typing stays responsive on large data without a worker.
+    #[prop_or_default]
+    pub time_slicing: Option<TimeSlicing>,
+
+    /// Sort applied when the table first renders, highest priority first.
+    #[prop_or_default]
+    pub default_sort: Vec<SortKey>,
+
+    /// Whether a third click on a sorted header unsorts it, restoring the original data order.
+    #[prop_or(false)]
+    pub allow_unsorted: bool,
+
+    /// How rows are told apart, keying their `<tr>` and identifying them in the selection;
+    /// by default their index in `data`, or their position among the results of `data_source`.
+    #[prop_or_default]
+    pub row_key: Option<RowKey>,
+
+    /// Whether rows can be selected, adding a checkbox column.
+    #[prop_or_default]
+    pub selection: SelectionMode,
+
+    /// Whether the "select all" checkbox covers every row matching the search and the filters,
+    /// on all pages, rather than the rows of the current page.
+    #[prop_or(false)]
+    pub select_all_filtered: bool,
+
+    /// Callback receiving the keys of the selected rows whenever the selection changes.
+    #[prop_or(Callback::noop())]
+    pub on_selection_change: Callback<Vec<String>>,
+
+    /// Whether rows can be expanded, adding a column of buttons showing their detail panel.
+    #[prop_or(false)]
+    pub expandable: bool,
+
+    /// Renders the detail panel of an expanded row, shown in a full-width row beneath it.
+    #[prop_or_default]
+    pub render_detail: Option<Callback<T, Html>>,
+
+    /// Keys of the expanded rows, when the parent controls the expansion; otherwise the table
+    /// keeps track of it.
+    #[prop_or_default]
+    pub expanded: Option<Vec<String>>,
+
+    /// Callback receiving the keys of the expanded rows whenever a row is expanded or collapsed.
+    #[prop_or(Callback::noop())]
+    pub on_expand_change: Callback<Vec<String>>,
+
     /// Text labels for the table UI.
     #[prop_or_default]
     pub texts: TableTexts,
@@ -203,19 +528,64 @@ pub struct HeaderProps {
     #[prop_or_default]
     pub columns: Vec<Column>,
 
-    /// Current column used for sorting.
-    pub sort_column: UseStateHandle<Option<&'static str>>,
-
-    /// Current sort order (ascending/descending).
-    pub sort_order: UseStateHandle<SortOrder>,
+    /// Current sort keys, highest priority first.
+    pub sort: UseStateHandle<Vec<SortKey>>,
 
     /// Callback triggered when a column header is clicked for sorting.
+    /// Receives the column id and whether shift was held, to add a secondary key.
+    #[prop_or(Callback::noop())]
+    pub on_sort_column: Callback<(&'static str, bool)>,
+
+    /// Current per-column filter inputs, keyed by column id.
+    pub filters: UseStateHandle<BTreeMap<&'static str, FilterValue>>,
+
+    /// Callback triggered when a filter input changes, with the column id and the new value.
     #[prop_or(Callback::noop())]
-    pub on_sort_column: Callback<&'static str>,
+    pub on_filter: Callback<(&'static str, FilterValue)>,
 
     /// CSS classes used to style the header.
     #[prop_or_default]
     pub classes: TableClasses,
+
+    /// Whether the header sticks to the top of the scrolling area of a virtualized table.
+    #[prop_or(false)]
+    pub sticky: bool,
+
+    /// Cells to render in each header row when the columns are virtualized.
+    #[prop_or_default]
+    pub column_window: Option<ColumnWindow>,
+
+    /// Text labels used in the filter row.
+    #[prop_or_default]
+    pub texts: TableTexts,
+
+    /// Whether rows can be selected, adding a cell before the headers, with a "select all"
+    /// checkbox in `Multiple` mode.
+    #[prop_or_default]
+    pub selection: SelectionMode,
+
+    /// State of the "select all" checkbox.
+    #[prop_or_default]
+    pub select_all: CheckState,
+
+    /// Callback triggered by the "select all" checkbox, with whether to select the rows it
+    /// covers (or unselect them, when all are selected).
+    #[prop_or(Callback::noop())]
+    pub on_select_all: Callback<bool>,
+
+    /// Whether rows can be expanded, adding a cell before the headers with an "expand all"
+    /// button.
+    #[prop_or(false)]
+    pub expandable: bool,
+
+    /// Whether every row of the page is expanded.
+    #[prop_or(false)]
+    pub all_expanded: bool,
+
+    /// Callback triggered by the "expand all" button, with whether to expand the rows of the
+    /// page (or collapse them, when all are expanded).
+    #[prop_or(Callback::noop())]
+    pub on_expand_all: Callback<bool>,
 }
 
 /// Alias for `HeaderProps` to be used explicitly in header components.
@@ -225,19 +595,64 @@ pub struct TableHeaderProps {
     #[prop_or_default]
     pub columns: Vec<Column>,
 
-    /// Current column used for sorting.
-    pub sort_column: UseStateHandle<Option<&'static str>>,
-
-    /// Current sort order (ascending/descending).
-    pub sort_order: UseStateHandle<SortOrder>,
+    /// Current sort keys, highest priority first.
+    pub sort: UseStateHandle<Vec<SortKey>>,
 
     /// Callback triggered when a column is sorted.
+    /// Receives the column id and whether shift was held, to add a secondary key.
+    #[prop_or(Callback::noop())]
+    pub on_sort_column: Callback<(&'static str, bool)>,
+
+    /// Current per-column filter inputs, keyed by column id.
+    pub filters: UseStateHandle<BTreeMap<&'static str, FilterValue>>,
+
+    /// Callback triggered when a filter input changes, with the column id and the new value.
     #[prop_or(Callback::noop())]
-    pub on_sort_column: Callback<&'static str>,
+    pub on_filter: Callback<(&'static str, FilterValue)>,
 
     /// CSS classes used to style the table header.
     #[prop_or_default]
     pub classes: TableClasses,
+
+    /// Whether the header sticks to the top of the scrolling area of a virtualized table.
+    #[prop_or(false)]
+    pub sticky: bool,
+
+    /// Cells to render in each header row when the columns are virtualized.
+    #[prop_or_default]
+    pub column_window: Option<ColumnWindow>,
+
+    /// Text labels used in the filter row.
+    #[prop_or_default]
+    pub texts: TableTexts,
+
+    /// Whether rows can be selected, adding a cell before the headers, with a "select all"
+    /// checkbox in `Multiple` mode.
+    #[prop_or_default]
+    pub selection: SelectionMode,
+
+    /// State of the "select all" checkbox.
+    #[prop_or_default]
+    pub select_all: CheckState,
+
+    /// Callback triggered by the "select all" checkbox, with whether to select the rows it
+    /// covers (or unselect them, when all are selected).
+    #[prop_or(Callback::noop())]
+    pub on_select_all: Callback<bool>,
+
+    /// Whether rows can be expanded, adding a cell before the headers with an "expand all"
+    /// button.
+    #[prop_or(false)]
+    pub expandable: bool,
+
+    /// Whether every row of the page is expanded.
+    #[prop_or(false)]
+    pub all_expanded: bool,
+
+    /// Callback triggered by the "expand all" button, with whether to expand the rows of the
+    /// page (or collapse them, when all are expanded).
+    #[prop_or(Callback::noop())]
+    pub on_expand_all: Callback<bool>,
 }
 
 /// Props for the pagination controls component.
@@ -250,6 +665,15 @@ pub struct PaginationControlsProps {
     #[prop_or(1)]
     pub total_pages: usize,
 
+    /// Which controls to render: Previous/Next buttons, a "Load more" button, or none for
+    /// infinite scroll.
+    #[prop_or_default]
+    pub pagination_mode: PaginationMode,
+
+    /// Callback triggered by the "Load more" button; defaults to moving `page` forward.
+    #[prop_or_default]
+    pub on_load_more: Option<Callback<()>>,
+
     /// Class names used to style pagination elements.
     #[prop_or_default]
     pub classes: TableClasses,
@@ -261,19 +685,43 @@ pub struct PaginationControlsProps {
 
 /// Props for rendering the body of the table.
 #[derive(Properties, PartialEq, Clone)]
-pub struct TableBodyProps {
+pub struct TableBodyProps<T = HashMap<&'static str, String>>
+where
+    T: TableRow + PartialEq + Clone + 'static,
+{
     /// Column definitions.
     #[prop_or_default]
     pub columns: Vec<Column>,
 
-    /// List of row data to render.
+    /// List of row data to render, shared with the table so that renders do not clone it.
     #[prop_or_default]
-    pub rows: Vec<HashMap<&'static str, String>>,
+    pub rows: Rc<Vec<T>>,
 
     /// Indicates if the body is in a loading state.
     #[prop_or(false)]
     pub loading: bool,
 
+    /// Error message of a failed load; shows the error row instead of the rows.
+    #[prop_or_default]
+    pub error: Option<String>,
+
+    /// Callback triggered by the retry button of the error row.
+    #[prop_or(Callback::noop())]
+    pub on_retry: Callback<()>,
+
+    /// In the appending modes, the rows stay visible while the next page loads, and with
+    /// `InfiniteScroll` a sentinel row after them triggers `on_load_more`.
+    #[prop_or_default]
+    pub pagination_mode: PaginationMode,
+
+    /// Whether there are more rows to load after the ones shown.
+    #[prop_or(false)]
+    pub has_more: bool,
+
+    /// Callback triggered when the sentinel row of infinite scroll comes into view.
+    #[prop_or(Callback::noop())]
+    pub on_load_more: Callback<()>,
+
     /// Class names used to style the table body.
     #[prop_or_default]
     pub classes: TableClasses,
@@ -281,4 +729,57 @@ pub struct TableBodyProps {
     /// Text labels used in the body (e.g., loading, empty).
     #[prop_or_default]
     pub texts: TableTexts,
+
+    /// Fuzzy search query whose matches are wrapped in `<mark>`; empty to highlight nothing.
+    #[prop_or_default]
+    pub highlight: String,
+
+    /// Normalization used to match `highlight` against the cells.
+    #[prop_or_default]
+    pub search_options: SearchOptions,
+
+    /// Rows to render out of `rows` when virtualized, with the height of the rows around them.
+    #[prop_or_default]
+    pub virtual_window: Option<VirtualWindow>,
+
+    /// Cells to render in each row when the columns are virtualized.
+    #[prop_or_default]
+    pub column_window: Option<ColumnWindow>,
+
+    /// Callback receiving the position and rendered height of each row after a render, for
+    /// virtualized rows of measured height.
+    #[prop_or_default]
+    pub on_measure: Option<Callback<Vec<(usize, f64)>>>,
+
+    /// Whether rows can be selected, adding a checkbox cell to each row.
+    #[prop_or_default]
+    pub selection: SelectionMode,
+
+    /// Key of each row of `rows`, keying its `<tr>` and identifying it in `selected` and `expanded`.
+    #[prop_or_default]
+    pub row_keys: Rc<Vec<String>>,
+
+    /// The selected rows.
+    #[prop_or_default]
+    pub selected: Rc<Selection>,
+
+    /// Callback triggered by the checkbox of a row, with its key and whether shift was held.
+    #[prop_or(Callback::noop())]
+    pub on_select: Callback<(String, bool)>,
+
+    /// Whether rows can be expanded, adding a cell with an expand button to each row.
+    #[prop_or(false)]
+    pub expandable: bool,
+
+    /// Keys of the expanded rows.
+    #[prop_or_default]
+    pub expanded: Rc<BTreeSet<String>>,
+
+    /// Callback triggered by the expand button of a row, with its key.
+    #[prop_or(Callback::noop())]
+    pub on_expand: Callback<String>,
+
+    /// Renders the detail panel of an expanded row.
+    #[prop_or_default]
+    pub render_detail: Option<Callback<T, Html>>,
 }
@@ -0,0 +1,362 @@
+//! Derive macros for [`table-rs`](https://docs.rs/table-rs).
+//!
+//! Enable them through the `derive` feature of `table-rs` and import the derive from
+//! `table_rs::core`, next to the trait it implements.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Fields, GenericArgument, Ident, LitBool, LitStr, Path, PathArguments, Type,
+    parse_macro_input,
+};
+
+/// Derives `table_rs::core::TableRow` for a struct with named fields.
+///
+/// Every field becomes a cell keyed by its name, converted to text through
+/// `table_rs::core::ToCell`. The derive also adds an inherent
+/// `columns<C: From<ColumnDef>>() -> Vec<C>` function returning one column per field, in
+/// declaration order, so `let columns: Vec<Column> = User::columns();` works with the `Column`
+/// type of any backend.
+///
+/// # Field attributes
+/// - `#[table(id = "...")]`: column id, defaults to the field name.
+/// - `#[table(header = "...")]`: header text, defaults to the field name in title case.
+/// - `#[table(sortable)]`: marks the column as sortable.
+/// - `#[table(skip)]`: leaves the field out of both the row and the columns.
+/// - `#[table(format = path::to::fn)]`: renders the cell with a `fn(&FieldType) -> String`.
+/// - `#[table(kind = date)]`: column kind used for sorting, one of `text`, `integer`, `float`,
+///   `bool`, `date` or `datetime`. Integer, float and bool fields (optionally wrapped in
+///   `Option`) are detected automatically; everything else defaults to `text`.
+/// - `#[table(natural)]`: sorts the column in natural order, so `item2` comes before `item10`.
+/// - `#[table(sort_fn = path::to::fn)]`: sorts the column with a
+///   `fn(&CellValue, &CellValue) -> Ordering` comparator.
+/// - `#[table(first_sort_order = desc)]`: direction of the first click on the header, `asc`
+///   (default) or `desc`.
+/// - `#[table(filter = text)]`: filter shown in the filter row, one of `text`, `equals`,
+///   `number_range` or `date_range`. Select filters need their options and are set on the
+///   column instead.
+/// - `#[table(searchable = false)]`: keeps the search input from looking at the column.
+///
+/// Sorting reads fields directly through `ToCell::to_value` instead of re-parsing their text, so a
+/// formatted number still sorts by the number. Formatted fields of other types, and primitive
+/// fields given another `kind` (such as `#[table(kind = text)] code: u32`), sort by their rendered
+/// text, parsed as the column kind.
+///
+/// # Examples
+/// ```rust,ignore
+/// use table_rs::core::TableRow;
+/// use table_rs::yew::types::Column;
+///
+/// #[derive(Clone, PartialEq, TableRow)]
+/// struct User {
+///     #[table(sortable)]
+///     name: String,
+///     #[table(header = "E-mail")]
+///     email: String,
+///     #[table(skip)]
+///     password_hash: String,
+/// }
+///
+/// let columns: Vec<Column> = User::columns();
+/// ```
+#[proc_macro_derive(TableRow, attributes(table))]
+pub fn derive_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Options collected from the `#[table(...)]` attributes of a single field.
+#[derive(Default)]
+struct FieldOptions {
+    id: Option<LitStr>,
+    header: Option<LitStr>,
+    sortable: bool,
+    skip: bool,
+    format: Option<Path>,
+    kind: Option<Ident>,
+    natural: bool,
+    sort_fn: Option<Path>,
+    first_sort_order: Option<Ident>,
+    filter: Option<Ident>,
+    searchable: Option<LitBool>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "`TableRow` can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`TableRow` can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut arms = Vec::new();
+    let mut value_arms = Vec::new();
+    let mut columns = Vec::new();
+
+    for field in fields {
+        let options = parse_options(field)?;
+        if options.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let field_name = ident.to_string();
+        let field_name = field_name.trim_start_matches("r#");
+        let id = options
+            .id
+            .unwrap_or_else(|| LitStr::new(field_name, ident.span()));
+        let header = options
+            .header
+            .unwrap_or_else(|| LitStr::new(&title_case(field_name), ident.span()));
+        let sortable = options.sortable;
+        let sort_fn = match (&options.sort_fn, options.natural) {
+            (Some(path), false) => quote! {
+                ::core::option::Option::Some(::table_rs::core::SortFn::Values(#path))
+            },
+            (None, true) => quote! {
+                ::core::option::Option::Some(::table_rs::core::SortFn::Natural)
+            },
+            (None, false) => quote! { ::core::option::Option::None },
+            (Some(path), true) => {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "`natural` and `sort_fn` cannot be used together",
+                ));
+            }
+        };
+
+        let first_sort_order = match &options.first_sort_order {
+            Some(order) if order == "asc" => Ident::new("Asc", order.span()),
+            Some(order) if order == "desc" => Ident::new("Desc", order.span()),
+            Some(order) => {
+                return Err(syn::Error::new_spanned(order, "expected `asc` or `desc`"));
+            }
+            None => Ident::new("Asc", ident.span()),
+        };
+
+        let filter = match &options.filter {
+            Some(filter) => {
+                let variant = filter_variant(filter)?;
+                let variant = Ident::new(variant, filter.span());
+                quote! {
+                    ::core::option::Option::Some(::table_rs::core::ColumnFilter::#variant)
+                }
+            }
+            None => quote! { ::core::option::Option::None },
+        };
+
+        let searchable = options.searchable.as_ref().is_none_or(LitBool::value);
+
+        let inferred = infer_kind(&field.ty);
+        let kind = match &options.kind {
+            Some(kind) => kind_variant(kind)?,
+            None => inferred,
+        };
+        let kind_ident = Ident::new(kind, ident.span());
+
+        let value = match &options.format {
+            Some(format) => quote! {
+                ::core::option::Option::Some(::std::borrow::Cow::Owned(#format(&self.#ident)))
+            },
+            None => quote! {
+                ::table_rs::core::ToCell::to_cell(&self.#ident)
+            },
+        };
+
+        arms.push(quote! { #id => #value, });
+        // Primitive fields sort by their typed value even when they are rendered through
+        // `format`, unless `kind` asks for another kind; then, like formatted fields of other
+        // types, they sort by their text parsed as that kind.
+        let typed = if inferred == "Text" {
+            options.format.is_none()
+        } else {
+            kind == inferred
+        };
+        if typed {
+            value_arms.push(quote! {
+                #id => ::table_rs::core::ToCell::to_value(&self.#ident, kind),
+            });
+        }
+        columns.push(quote! {
+            C::from(::table_rs::core::ColumnDef {
+                id: #id,
+                header: #header,
+                sortable: #sortable,
+                kind: ::table_rs::core::ColumnKind::#kind_ident,
+                sort_fn: #sort_fn,
+                first_sort_order: ::table_rs::core::SortOrder::#first_sort_order,
+                filter: #filter,
+                searchable: #searchable,
+            }),
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::table_rs::core::TableRow for #name #ty_generics #where_clause {
+            fn cell(&self, id: &str) -> ::core::option::Option<::std::borrow::Cow<'_, str>> {
+                match id {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn value(
+                &self,
+                id: &str,
+                kind: ::table_rs::core::ColumnKind,
+            ) -> ::table_rs::core::CellValue<'_> {
+                match id {
+                    #(#value_arms)*
+                    _ => match ::table_rs::core::TableRow::cell(self, id) {
+                        ::core::option::Option::Some(text) => {
+                            ::table_rs::core::CellValue::parse(text, kind)
+                        }
+                        ::core::option::Option::None => ::table_rs::core::CellValue::Null,
+                    },
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Column definitions for the fields of this row type, in declaration order.
+            pub fn columns<C>() -> ::std::vec::Vec<C>
+            where
+                C: ::core::convert::From<::table_rs::core::ColumnDef>,
+            {
+                ::std::vec![#(#columns)*]
+            }
+        }
+    })
+}
+
+fn parse_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("table"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                options.id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("header") {
+                options.header = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("sortable") {
+                options.sortable = true;
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("format") {
+                options.format = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("kind") {
+                options.kind = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("natural") {
+                options.natural = true;
+            } else if meta.path.is_ident("sort_fn") {
+                options.sort_fn = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("first_sort_order") {
+                options.first_sort_order = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("filter") {
+                options.filter = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("searchable") {
+                options.searchable = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported `table` attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Maps a `kind = ...` attribute value to the matching `ColumnKind` variant.
+fn kind_variant(kind: &Ident) -> syn::Result<&'static str> {
+    Ok(match kind.to_string().as_str() {
+        "text" => "Text",
+        "integer" => "Integer",
+        "float" => "Float",
+        "bool" => "Bool",
+        "date" => "Date",
+        "datetime" => "DateTime",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                kind,
+                "expected one of `text`, `integer`, `float`, `bool`, `date` or `datetime`",
+            ));
+        }
+    })
+}
+
+/// Maps a `filter = ...` attribute value to the matching `ColumnFilter` variant.
+fn filter_variant(filter: &Ident) -> syn::Result<&'static str> {
+    Ok(match filter.to_string().as_str() {
+        "text" => "Text",
+        "equals" => "Equals",
+        "number_range" => "NumberRange",
+        "date_range" => "DateRange",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                filter,
+                "expected one of `text`, `equals`, `number_range` or `date_range`",
+            ));
+        }
+    })
+}
+
+/// Guesses the `ColumnKind` variant of a field from its type, looking through `Option`.
+fn infer_kind(ty: &Type) -> &'static str {
+    let Type::Path(path) = ty else {
+        return "Text";
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return "Text";
+    };
+
+    match segment.ident.to_string().as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => "Integer",
+        "f32" | "f64" => "Float",
+        "bool" => "Bool",
+        "Option" => match &segment.arguments {
+            PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(GenericArgument::Type(inner)) => infer_kind(inner),
+                _ => "Text",
+            },
+            _ => "Text",
+        },
+        _ => "Text",
+    }
+}
+
+/// Turns `first_name` into `First Name`.
+fn title_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
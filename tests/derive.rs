@@ -0,0 +1,132 @@
+//! Tests of `#[derive(TableRow)]`, run with `cargo test --features derive`.
+#![cfg(feature = "derive")]
+
+use table_rs::core::{
+    CellValue, ColumnDef, ColumnFilter, ColumnKind, SortFn, SortOrder, TableRow, sort_rows,
+};
+
+fn years(age: &u8) -> String {
+    format!("{age} years")
+}
+
+#[derive(Clone, PartialEq, TableRow)]
+struct Item {
+    #[table(sortable, natural)]
+    first_name: String,
+    #[table(id = "mail", header = "E-mail", searchable = false)]
+    email: String,
+    #[table(sortable, format = years, filter = number_range)]
+    age: u8,
+    #[table(sortable, kind = text)]
+    code: u32,
+    #[table(kind = date, first_sort_order = desc)]
+    joined: String,
+    score: Option<f64>,
+    #[table(skip)]
+    #[allow(dead_code)]
+    secret: String,
+}
+
+fn item(first_name: &str, age: u8, code: u32, joined: &str, score: Option<f64>) -> Item {
+    Item {
+        first_name: first_name.into(),
+        email: format!("{}@example.org", first_name.to_lowercase()),
+        age,
+        code,
+        joined: joined.into(),
+        score,
+        secret: "hunter2".into(),
+    }
+}
+
+fn items() -> Vec<Item> {
+    vec![
+        item("Ferris", 9, 10, "2024-05-01", Some(2.5)),
+        item("Crab", 10, 9, "2023-12-31", None),
+        item("Lobster", 100, 100, "2024-01-15", Some(-1.0)),
+    ]
+}
+
+fn sorted(column: &'static str) -> Vec<usize> {
+    let columns = Item::columns::<ColumnDef>();
+    let mut indices: Vec<usize> = (0..3).collect();
+    sort_rows(
+        &items(),
+        &columns,
+        &mut indices,
+        &[(column, SortOrder::Asc)],
+    );
+    indices
+}
+
+#[test]
+fn columns_follow_the_field_attributes() {
+    let columns = Item::columns::<ColumnDef>();
+    let ids: Vec<_> = columns.iter().map(|col| col.id).collect();
+    assert_eq!(
+        ids,
+        ["first_name", "mail", "age", "code", "joined", "score"]
+    );
+
+    assert_eq!(columns[0].header, "First Name");
+    assert_eq!(columns[0].sort_fn, Some(SortFn::Natural));
+    assert_eq!(columns[1].header, "E-mail");
+    assert!(!columns[1].searchable && columns[0].searchable);
+    assert_eq!(columns[2].kind, ColumnKind::Integer);
+    assert_eq!(columns[2].filter, Some(ColumnFilter::NumberRange));
+    assert_eq!(columns[3].kind, ColumnKind::Text);
+    assert_eq!(columns[4].kind, ColumnKind::Date);
+    assert_eq!(columns[4].first_sort_order, SortOrder::Desc);
+    assert_eq!(columns[5].kind, ColumnKind::Float);
+    assert!(
+        columns
+            .iter()
+            .all(|col| col.sortable == matches!(col.id, "first_name" | "age" | "code"))
+    );
+}
+
+#[test]
+fn cells_render_fields_as_text() {
+    let row = &items()[0];
+    assert_eq!(row.cell("mail").as_deref(), Some("ferris@example.org"));
+    assert_eq!(row.cell("age").as_deref(), Some("9 years"));
+    assert_eq!(row.cell("score").as_deref(), Some("2.5"));
+    assert_eq!(items()[1].cell("score"), None);
+    assert_eq!(row.cell("secret"), None);
+    assert_eq!(row.cell("email"), None);
+}
+
+#[test]
+fn values_are_typed_by_the_column_kind() {
+    let row = &items()[0];
+    assert_eq!(row.value("age", ColumnKind::Integer), CellValue::Integer(9));
+    assert_eq!(row.value("score", ColumnKind::Float), CellValue::Float(2.5));
+    assert_eq!(
+        items()[1].value("score", ColumnKind::Float),
+        CellValue::Null
+    );
+    assert_eq!(
+        row.value("code", ColumnKind::Text),
+        CellValue::Text("10".into())
+    );
+    assert!(matches!(
+        row.value("joined", ColumnKind::Date),
+        CellValue::Date(_)
+    ));
+}
+
+#[test]
+fn formatted_numbers_sort_by_the_number() {
+    assert_eq!(sorted("age"), [0, 1, 2]);
+}
+
+#[test]
+fn an_explicit_text_kind_sorts_numbers_as_text() {
+    // "10" < "100" < "9"
+    assert_eq!(sorted("code"), [0, 2, 1]);
+}
+
+#[test]
+fn dates_sort_chronologically() {
+    assert_eq!(sorted("joined"), [1, 2, 0]);
+}